@@ -0,0 +1,28 @@
+//! Feeds arbitrary strings to `run` as mass, speed and energy in either measurment system.
+//! `run` must never panic and must never hand out non-finite parameters.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use muzzle::{run, Config, Units};
+
+fuzz_target!(|data: &[u8]| {
+    let Some((&selector, rest)) = data.split_first() else {
+        return;
+    };
+
+    // Input is up to three NUL separated fields, an empty field standing for a missing parameter
+    let input = String::from_utf8_lossy(rest);
+    let mut fields = input.split('\0').map(|field| Some(field.to_owned()).filter(|field| !field.is_empty()));
+    let config = Config {
+        units: if selector & 1 == 0 { Units::METRIC } else { Units::IMPERIAL },
+        mass: fields.next().flatten(),
+        speed: fields.next().flatten(),
+        energy: fields.next().flatten(),
+        ..Config::default()
+    };
+
+    if let Ok(params) = run(config) {
+        assert!(params.mass.is_finite() && params.speed.is_finite() && params.energy.is_finite());
+    }
+});
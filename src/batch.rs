@@ -0,0 +1,118 @@
+//! Line oriented input of shot specs for processing files lazily, and JSON arrays of configs for
+//! processing them in one go.
+
+use crate::{run, Config, MuzzleError, Params, Units};
+use serde_json::json;
+use std::io::{BufRead, Lines};
+
+/// Iterator running one shot spec per line of a reader, yielding results as it goes.
+///
+/// Each line holds comma separated `mass,speed,energy` in the units given to `ParamsIter::new`,
+/// with the unknown one left empty. E.g. `150,2800,` derives energy and `150,2800,2611` is taken
+/// as is. Blank lines and lines starting with `#` are skipped. A malformed line yields an error
+/// and iteration carries on with the next one.
+///
+/// Files as spreadsheets export them are fine: a leading UTF-8 byte order mark is dropped, lines
+/// may end in `\n` or `\r\n` and whitespace around cells is ignored.
+pub struct ParamsIter<R: BufRead> {
+    lines: Lines<R>,
+    units: Units,
+    first: bool,
+}
+
+impl<R: BufRead> ParamsIter<R> {
+    /// Creates an iterator over specs read from `reader` in given units of measurment
+    pub fn new(reader: R, units: Units) -> ParamsIter<R> {
+        ParamsIter { lines: reader.lines(), units, first: true }
+    }
+
+    /// Turns a single spec line into config for `run`
+    fn parse_line(&self, line: &str) -> Result<Config, MuzzleError> {
+        let cells: Vec<Option<String>> = line
+            .split(',')
+            .map(|cell| Some(cell.trim().to_owned()).filter(|cell| !cell.is_empty()))
+            .collect();
+
+        match <[Option<String>; 3]>::try_from(cells) {
+            Ok([mass, speed, energy]) => Ok(Config {
+                units: self.units,
+                mass,
+                speed,
+                energy,
+                ..Config::default()
+            }),
+            Err(cells) => Err(MuzzleError::INVALID(format!(
+                "Expected 3 comma separated values in `{}`, got {}!", line, cells.len()
+            ))),
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for ParamsIter<R> {
+    type Item = Result<Params, MuzzleError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let mut line = match self.lines.next()? {
+                Ok(line) => line,
+                Err(error) => return Some(Err(MuzzleError::IO(error))),
+            };
+
+            if self.first {
+                self.first = false;
+                if let Some(stripped) = line.strip_prefix('\u{feff}') {
+                    line = stripped.to_owned();
+                }
+            }
+
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            return Some(self.parse_line(line).and_then(run));
+        }
+    }
+}
+
+/// Runs every config of a JSON array (each as `Config` deserializes it) and returns a JSON array of
+/// the results in the same order, for use as a `jq` style filter.
+///
+/// Each result is the document `Params::to_json` renders, or `{"error": "..."}` for a config that
+/// is malformed or fails to calculate, so one bad item does not cost the rest. Fails only if the
+/// input as a whole is not a JSON array.
+pub fn filter_json(input: &str) -> Result<serde_json::Value, MuzzleError> {
+    filter_json_with_progress(input, &mut |_, _| {})
+}
+
+/// Runs every config of a JSON array like `filter_json` does, calling `progress` with the number
+/// of configs done so far and their total after each one
+pub fn filter_json_with_progress(input: &str, progress: &mut dyn FnMut(usize, usize)) -> Result<serde_json::Value, MuzzleError> {
+    let items: Vec<serde_json::Value> = serde_json::from_str(input)
+        .map_err(|error| MuzzleError::INVALID(format!("Expected a JSON array of configs, {}!", error)))?;
+
+    let total = items.len();
+    let results = items
+        .into_iter()
+        .enumerate()
+        .map(|(done, item)| {
+            let result = run_json(item);
+            progress(done + 1, total);
+            result
+        })
+        .collect();
+
+    Ok(serde_json::Value::Array(results))
+}
+
+/// Runs a single JSON config, returning the `Params::to_json` document or `{"error": "..."}`
+pub(crate) fn run_json(item: serde_json::Value) -> serde_json::Value {
+    let result = serde_json::from_value::<Config>(item)
+        .map_err(|error| MuzzleError::INVALID(format!("Incorrect config. {}", error)))
+        .and_then(run);
+
+    match result {
+        Ok(params) => params.json_value(),
+        Err(error) => json!({ "error": error.to_string() }),
+    }
+}
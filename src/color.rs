@@ -0,0 +1,91 @@
+//! Whether and how output is colored, one decision for every ANSI escape sequence muzzle writes.
+
+use serde::Deserialize;
+use std::env;
+
+/// Ends the color of whatever follows an escape sequence of `ColorTheme`
+pub const RESET: &str = "\x1b[0m";
+
+/// When to write ANSI escape sequences, `--color`.
+/// Deserializes from the names `from_name` takes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorChoice {
+    /// Only into terminals
+    #[default]
+    AUTO,
+    /// Into files and pipes as well
+    ALWAYS,
+    /// Plain text only
+    NEVER,
+}
+
+impl ColorChoice {
+    /// Choice of given name: auto, always or never
+    pub fn from_name(name: &str) -> Option<ColorChoice> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "auto" => Some(ColorChoice::AUTO),
+            "always" => Some(ColorChoice::ALWAYS),
+            "never" => Some(ColorChoice::NEVER),
+            _ => None,
+        }
+    }
+}
+
+/// Terminal background colors are picked for, `--color-theme`.
+/// Deserializes from the names `from_name` takes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorTheme {
+    /// Light text on a dark background
+    #[default]
+    DARK,
+    /// Dark text on a light background
+    LIGHT,
+}
+
+impl ColorTheme {
+    /// Theme of given name: dark or light
+    pub fn from_name(name: &str) -> Option<ColorTheme> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "dark" => Some(ColorTheme::DARK),
+            "light" => Some(ColorTheme::LIGHT),
+            _ => None,
+        }
+    }
+
+    /// Escape sequence values that stand out, like derived ones, are written in
+    pub fn highlight(self) -> &'static str {
+        match self {
+            // Bright cyan washes out on white, plain blue is too dark on black
+            ColorTheme::DARK => "\x1b[1;96m",
+            ColorTheme::LIGHT => "\x1b[1;34m",
+        }
+    }
+
+    /// Escape sequence the better of two compared values is written in
+    pub fn better(self) -> &'static str {
+        match self {
+            ColorTheme::DARK => "\x1b[1;92m",
+            ColorTheme::LIGHT => "\x1b[1;32m",
+        }
+    }
+}
+
+/// Whether ANSI escape sequences (colors, clearing a line) may be written to a stream, given
+/// the `choice` and whether it `is_terminal`. Never with the `NO_COLOR` environment variable set
+/// to anything but empty, as https://no-color.org asks of every program, whatever the choice.
+pub fn escapes_allowed(choice: ColorChoice, is_terminal: bool) -> bool {
+    let no_color = env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+    match choice {
+        _ if no_color => false,
+        ColorChoice::AUTO => is_terminal,
+        ColorChoice::ALWAYS => true,
+        ColorChoice::NEVER => false,
+    }
+}
+
+/// Theme to color a stream in, or none for plain text, as `escapes_allowed` decides
+pub fn colors(choice: ColorChoice, theme: ColorTheme, is_terminal: bool) -> Option<ColorTheme> {
+    escapes_allowed(choice, is_terminal).then_some(theme)
+}
@@ -0,0 +1,42 @@
+//! Calculation server on a Unix domain socket, for frequent queries without spawning a process each.
+//!
+//! Clients send one JSON config per line (as `Config` deserializes it) and get one line back for
+//! each: the `Params::to_json` document, or `{"error": "..."}` for a config that is malformed or
+//! fails to calculate. A connection stays open for as many requests as the client sends. Only
+//! built with the `daemon` feature.
+
+use crate::batch::run_json;
+use crate::FormatPolicy;
+use std::io::{self, BufRead, BufReader, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::thread;
+
+/// Serves calculation requests of every client connecting to `listener`, each on its own thread.
+/// Runs until accepting a connection fails.
+pub fn serve(listener: &UnixListener) -> io::Result<()> {
+    loop {
+        let (stream, _) = listener.accept()?;
+        // A client going away mid-request only ends its own connection
+        thread::spawn(move || handle(stream));
+    }
+}
+
+/// Answers requests of a single client until it disconnects
+pub fn handle(stream: UnixStream) -> io::Result<()> {
+    let mut writer = stream.try_clone()?;
+
+    for line in BufReader::new(stream).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let response = match serde_json::from_str(&line) {
+            Ok(item) => run_json(item),
+            Err(error) => serde_json::json!({ "error": format!("Expected a JSON config, {}!", error) }),
+        };
+        writeln!(writer, "{}", FormatPolicy::default().to_json(&response, false))?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,154 @@
+//! Coarse point-mass drag model used for downrange estimates.
+//!
+//! Deceleration follows a standard reference drag function: drag coefficient of the bullet is
+//! that of the reference projectile at the same Mach number, scaled down by the ballistic
+//! coefficient. Two functions are built in, G1 (flat base, the traditional default) and G7 (long
+//! boat-tail), with coefficients as tabulated by R. L. McCoy, "Modern Exterior Ballistics" (1999)
+//! and thinned out to keep the tables short. Air is taken at ICAO standard sea level conditions
+//! and gravity, spin, wind and any other effect besides drag are ignored. Good for "how much is
+//! left" figures, not for dialing a scope.
+//!
+//! The same model is inverted to estimate a ballistic coefficient from two chronograph readings.
+//! Such an estimate is only as good as the readings: chronographs are commonly off by 1 % or so,
+//! an error that the short distances between them inflate many times over in the BC. It also
+//! describes the bullet over the measured speed range only.
+
+use crate::DragModel;
+
+/// Air density at standard sea level conditions, kg/m³
+const AIR_DENSITY: f64 = 1.225;
+/// Speed of sound at standard sea level conditions (15 °C), m/s
+const SPEED_OF_SOUND: f64 = 340.294;
+/// Conversion factor of ballistic coefficient from lb/in² to kg/m²
+const BC_TO_SI: f64 = 703.0696;
+/// Distance step the model is integrated with, m
+const STEP: f64 = 0.5;
+/// Upper bound on integration steps, longer distances get proportionally coarser steps
+const MAX_STEPS: f64 = 100_000.0;
+/// Range of ballistic coefficients (lb/in²) searched when estimating one, wider than any real bullet
+const BC_RANGE: (f64, f64) = (0.001, 10.0);
+/// Default cap on bisection steps of the BC estimate, well past those `BC_TOLERANCE` takes
+pub const BC_ITERATIONS: usize = 60;
+/// Relative width of the bracket the BC estimate stops narrowing at, far past printed precision
+const BC_TOLERANCE: f64 = 1e-9;
+
+/// G1 drag coefficients indexed by Mach number, as published for the standard G1 projectile
+const G1: &[(f64, f64)] = &[
+    (0.00, 0.2629), (0.20, 0.2344), (0.40, 0.2104), (0.50, 0.2032), (0.60, 0.2034),
+    (0.70, 0.2165), (0.75, 0.2313), (0.80, 0.2546), (0.85, 0.2901), (0.90, 0.3415),
+    (0.95, 0.4084), (1.00, 0.4805), (1.05, 0.5427), (1.10, 0.5883), (1.15, 0.6191),
+    (1.20, 0.6393), (1.30, 0.6589), (1.40, 0.6625), (1.50, 0.6573), (1.60, 0.6474),
+    (1.80, 0.6210), (2.00, 0.5934), (2.20, 0.5685), (2.40, 0.5481), (2.60, 0.5325),
+    (2.80, 0.5211), (3.00, 0.5133), (3.50, 0.5040), (4.00, 0.5006), (5.00, 0.4988),
+];
+
+/// G7 drag coefficients indexed by Mach number, as published for the standard G7 projectile
+const G7: &[(f64, f64)] = &[
+    (0.00, 0.1198), (0.20, 0.1193), (0.40, 0.1193), (0.50, 0.1194), (0.60, 0.1194),
+    (0.70, 0.1202), (0.75, 0.1215), (0.80, 0.1242), (0.85, 0.1306), (0.90, 0.1464),
+    (0.925, 0.1660), (0.95, 0.2054), (0.975, 0.2993), (1.00, 0.3803), (1.05, 0.4043),
+    (1.10, 0.4014), (1.20, 0.3884), (1.30, 0.3732), (1.40, 0.3580), (1.50, 0.3440),
+    (1.60, 0.3315), (1.80, 0.3117), (2.00, 0.2980), (2.20, 0.2864), (2.40, 0.2752),
+    (2.60, 0.2643), (2.80, 0.2533), (3.00, 0.2424), (3.50, 0.2154), (4.00, 0.1935),
+    (5.00, 0.1618),
+];
+
+/// Drag coefficient table of given reference drag function
+fn table(model: DragModel) -> &'static [(f64, f64)] {
+    match model {
+        DragModel::G1 => G1,
+        DragModel::G7 => G7,
+    }
+}
+
+/// Looks up drag coefficient for given Mach number, interpolating linearly between table rows
+fn drag_coefficient(table: &[(f64, f64)], mach: f64) -> f64 {
+    let upper = table.iter().position(|&(m, _)| m >= mach).unwrap_or(table.len() - 1).max(1);
+    let (m0, cd0) = table[upper - 1];
+    let (m1, cd1) = table[upper];
+
+    cd0 + (cd1 - cd0) * ((mach - m0) / (m1 - m0)).clamp(0.0, 1.0)
+}
+
+/// Rate of speed loss per meter travelled at `speed` (m/s) for given ballistic coefficient (kg/m²)
+fn deceleration(table: &[(f64, f64)], speed: f64, bc: f64) -> f64 {
+    AIR_DENSITY * std::f64::consts::PI * drag_coefficient(table, speed / SPEED_OF_SOUND) * speed / (8.0 * bc)
+}
+
+/// Estimates speed (m/s) retained by a projectile launched at `speed` (m/s) after travelling
+/// `distance` (m). Ballistic coefficient `bc` is against `model` in the conventional lb/in².
+pub fn retained_speed(model: DragModel, speed: f64, bc: f64, distance: f64) -> f64 {
+    flight(model, speed, bc, distance).0
+}
+
+/// Estimates speed (m/s) retained after travelling `distance` (m) like `retained_speed` does,
+/// along with the time of flight (s) it takes to get there. Time is summed up over the same
+/// small distance steps speed is integrated over, each taking step length over its midpoint speed.
+/// Time is infinite if the projectile comes to a stop short of the distance.
+pub fn flight(model: DragModel, speed: f64, bc: f64, distance: f64) -> (f64, f64) {
+    let table = table(model);
+    let bc = bc * BC_TO_SI;
+    let mut speed = speed;
+    let mut time = 0.0;
+    let mut travelled = 0.0;
+    let step_size = STEP.max(distance / MAX_STEPS);
+
+    // Midpoint integration over fixed steps, the last one cut short to land on the distance
+    while travelled < distance {
+        let step = step_size.min(distance - travelled);
+        let midpoint = speed - deceleration(table, speed, bc) * step / 2.0;
+        if midpoint <= 0.0 {
+            // Stopped within the step, so never gets any further
+            return (0.0, f64::INFINITY);
+        }
+        speed = (speed - deceleration(table, midpoint, bc) * step).max(0.0);
+        time += step / midpoint;
+        travelled += step;
+    }
+
+    (speed, time)
+}
+
+/// Ballistic coefficient estimate along with how the search for it went
+pub struct BcEstimate {
+    /// Ballistic coefficient, lb/in²
+    pub bc: f64,
+    /// Bisection steps taken
+    pub iterations: usize,
+    /// Retained speed the coefficient gives minus the one read, m/s
+    pub residual: f64,
+    /// Bracket narrowed down to `BC_TOLERANCE` within the steps allowed
+    pub converged: bool,
+}
+
+/// Estimates ballistic coefficient (lb/in², against `model`) of a projectile slowing down from
+/// `speed` to `retained` (both m/s) over `distance` (m). Bisects over `BC_RANGE`, as retained
+/// speed only grows with the coefficient, for at most `max_iterations` steps. Returns `None` if
+/// no coefficient in range explains the readings, e.g. when the projectile did not slow down at all.
+pub fn estimate_bc(model: DragModel, speed: f64, retained: f64, distance: f64, max_iterations: usize) -> Option<BcEstimate> {
+    let (mut low, mut high) = BC_RANGE;
+    let reachable = retained_speed(model, speed, low, distance) <= retained && retained <= retained_speed(model, speed, high, distance);
+    if !reachable {
+        return None;
+    }
+
+    // Coefficients span orders of magnitude, so bisect on a log scale
+    let mut iterations = 0;
+    while high / low - 1.0 > BC_TOLERANCE && iterations < max_iterations {
+        let middle = (low * high).sqrt();
+        if retained_speed(model, speed, middle, distance) < retained {
+            low = middle;
+        } else {
+            high = middle;
+        }
+        iterations += 1;
+    }
+
+    let bc = (low * high).sqrt();
+    Some(BcEstimate {
+        bc,
+        iterations,
+        residual: retained_speed(model, speed, bc, distance) - retained,
+        converged: high / low - 1.0 <= BC_TOLERANCE,
+    })
+}
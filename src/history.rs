@@ -0,0 +1,57 @@
+//! Persistent log of calculations, one JSON object (as produced by `Params::to_json`) per line.
+
+use crate::Params;
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Location of the history file in the user's data directory:
+/// `$XDG_DATA_HOME/muzzle/history.jsonl`, falling back to `$HOME/.local/share/muzzle/history.jsonl`.
+/// Returns `None` if neither variable is set.
+pub fn default_path() -> Option<PathBuf> {
+    let data_dir = match env::var_os("XDG_DATA_HOME").filter(|dir| !dir.is_empty()) {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(env::var_os("HOME").filter(|dir| !dir.is_empty())?).join(".local/share"),
+    };
+
+    Some(data_dir.join("muzzle").join("history.jsonl"))
+}
+
+/// Appends given shot parameters to the history file at `path`, creating it (and its directory) if needed
+pub fn append(path: &Path, params: &Params) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", params.to_json())
+}
+
+/// Reads up to `count` most recent entries of the history file at `path`, oldest first.
+/// Missing file is treated as empty history. Lines that are not valid JSON objects are skipped,
+/// their number is returned alongside the entries.
+pub fn last_entries(path: &Path, count: usize) -> io::Result<(Vec<String>, usize)> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok((Vec::new(), 0)),
+        Err(error) => return Err(error),
+    };
+
+    let mut skipped = 0;
+    let entries: Vec<String> = contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter(|line| {
+            let valid = serde_json::from_str::<serde_json::Value>(line).map(|value| value.is_object()).unwrap_or(false);
+            if !valid {
+                skipped += 1;
+            }
+            valid
+        })
+        .map(str::to_owned)
+        .collect();
+
+    let start = entries.len().saturating_sub(count);
+    Ok((entries[start..].to_vec(), skipped))
+}
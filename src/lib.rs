@@ -1,25 +1,899 @@
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::json;
+use std::cmp::Ordering;
+use std::{fmt, io};
+
+mod batch;
+mod color;
+#[cfg(all(unix, feature = "daemon"))]
+pub mod daemon;
+mod drag;
+pub mod history;
+mod metadata;
+#[cfg(feature = "monte-carlo")]
+pub mod monte_carlo;
+mod notation;
+mod quantity;
+mod report;
+#[cfg(feature = "qr")]
+pub mod qr;
+mod schema;
+pub mod settings;
+mod unit_system;
+
+pub use batch::{filter_json, filter_json_with_progress, ParamsIter};
+pub use color::{colors, escapes_allowed, ColorChoice, ColorTheme, RESET};
+pub use drag::BC_ITERATIONS;
+pub use metadata::{Metadata, VERSION};
+pub use notation::{FormatPolicy, Notation, ENERGY_SCIENTIFIC_FROM};
+pub use quantity::Quantity;
+pub use report::{Better, Compared, Criterion, Preset, ReportOptions, Winner, BOGUS_WARNING};
+pub use schema::json_schema;
+pub use unit_system::{Imperial, Metric, UnitSystem};
+
 const GEE_FPS: f64 = 32.174;
 const GRAMS_IN_KILO: f64 = 1000f64;
 const GRAINS_IN_POUND: f64 = 7000f64;
+const GRAINS_IN_GRAM: f64 = 15.432358352941431;
+const METERS_IN_FOOT: f64 = 0.3048;
+const METERS_IN_YARD: f64 = 0.9144;
+const JOULES_IN_FPE: f64 = 1.3558179483314004;
+const JOULES_IN_KGFM: f64 = 9.80665;
+const PASCALS_IN_PSI: f64 = 6894.757293168361;
+/// Kilometer per hour in m/s, exactly 1000 m over 3600 s
+const METERS_PER_SECOND_IN_KMH: f64 = 1.0 / 3.6;
+/// Mile per hour in m/s, exactly 1609.344 m over 3600 s
+const METERS_PER_SECOND_IN_MPH: f64 = 0.44704;
+/// Avoirdupois dram, a sixteenth of an ounce: exactly 27.34375 grains
+const GRAINS_IN_DRAM: f64 = 27.34375;
+/// Speed in m/s no firearm gets near, the fastest cartridges top out around 1400 m/s
+const MAX_PLAUSIBLE_SPEED: f64 = 1800.0;
+/// Relative difference between given energy and the one mass and speed give that still counts as
+/// consistent, loose enough for values copied from a manual rounded to whole FPS and FPE
+pub const CONSISTENCY_TOLERANCE: f64 = 0.005;
+/// Most rows a sweep runs, far past any table worth reading yet small enough to finish
+pub const MAX_SWEEP_ROWS: usize = 1_000_000;
+
+/// Version of the JSON document shape produced by `Params::to_json`.
+///
+/// Bumped every time a field is added to, removed from or renamed in the document, so
+/// consumers can check it before reading anything else. Values of existing fields never change
+/// meaning within the same version.
+/// `json_schema` describes every field of the current version.
+pub const SCHEMA_VERSION: u32 = 17;
+
+/// Header line naming the columns of records produced by `Params::to_csv`. Columns follow the fields
+/// of the JSON document of the same `SCHEMA_VERSION`.
+pub const CSV_HEADER: &str = "units,mass,speed,energy,bogus,derived,retained_speed,retained_energy,retained_fraction,\
+time_of_flight,estimated_bc,sectional_density,pressure_proxy,estimated_charge,recoil_velocity,recoil_energy,stability,drop,drop_moa,drop_mil,penetration_index,wind_drift,overdetermined,estimated_bc_iterations,estimated_bc_residual,expected_energy";
+
+/// Length in bytes of the packed form produced by `Params::to_bytes`.
+pub const PACKED_LEN: usize = 26;
+
+/// Everything that can go wrong while calculating shot parameters.
+#[derive(Debug)]
+pub enum MuzzleError {
+    /// Input value (held) could not be parsed as a number
+    PARSE(String),
+    /// None of the three primary parameters were given
+    EMPTY,
+    /// Only one of the three primary parameters was given
+    INSUFFICIENT,
+    /// Input is well formed but can not be worked with, the message tells why
+    INVALID(String),
+    /// Calculation of the named value did not give a finite number for finite inputs
+    DOMAIN(String),
+    /// Reading input failed
+    IO(io::Error),
+}
+
+impl fmt::Display for MuzzleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MuzzleError::PARSE(input) => write!(f, "Failed to parse `{}` as input parameter!", input),
+            MuzzleError::EMPTY => write!(f, "No parameters given. Two out of three parameters are needed to derive the third."),
+            MuzzleError::INSUFFICIENT => write!(
+                f,
+                "Incorrect parameters set. At least two out of three parameters must be given to derive the third.
+Please check input."
+            ),
+            MuzzleError::INVALID(message) => write!(f, "{}", message),
+            MuzzleError::DOMAIN(name) => write!(
+                f,
+                "Calculated {} is not a finite number, the calculation overflowed, divided by zero or took the square root of a negative number.\nPlease check magnitude and sign of input.",
+                name
+            ),
+            MuzzleError::IO(error) => write!(f, "Failed to read input with: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for MuzzleError {}
+
+impl From<io::Error> for MuzzleError {
+    fn from(error: io::Error) -> MuzzleError {
+        MuzzleError::IO(error)
+    }
+}
+
+/// Receiver of warnings `run_with_diagnostics` raises about input it still calculates with,
+/// so that embedders get them as values instead of on stderr.
+pub trait Diagnostics {
+    /// Takes a single warning, a full sentence without any label
+    fn warning(&mut self, message: String);
+}
+
+/// Collects warnings in order
+impl Diagnostics for Vec<String> {
+    fn warning(&mut self, message: String) {
+        self.push(message);
+    }
+}
 
 /// Measurment system to perform calculations in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Units {
     /// Metric system (e.g. "meters per secons", "joules", "grams")
+    #[default]
     METRIC,
     /// Imperial system (e.g. "feet per second", "foot-pounds of energy", "grains")
     IMPERIAL,
 }
 
+impl Units {
+    /// Every measurment system, metric first
+    pub const ALL: [Units; 2] = [Units::METRIC, Units::IMPERIAL];
+
+    /// Returns the opposite measurment system
+    pub fn other(self) -> Units {
+        match self {
+            Units::METRIC => Units::IMPERIAL,
+            Units::IMPERIAL => Units::METRIC,
+        }
+    }
+
+    /// Measurment system of given name as used on the command line and in the settings file:
+    /// `metric` or `imperial`
+    pub fn from_name(name: &str) -> Option<Units> {
+        match name {
+            "metric" => Some(Units::METRIC),
+            "imperial" => Some(Units::IMPERIAL),
+            _ => None,
+        }
+    }
+
+    /// Converts a downrange `distance` in this measurment system (meters or yards) to `units`
+    pub fn convert_distance(self, distance: f64, units: Units) -> f64 {
+        distance * self.system().meters_in_distance() / units.system().meters_in_distance()
+    }
+
+    /// Converts an air `temperature` in this measurment system (°C or °F) to `units`
+    pub fn convert_temperature(self, temperature: f64, units: Units) -> f64 {
+        match (self, units) {
+            (Units::METRIC, Units::IMPERIAL) => temperature * 1.8 + 32.0,
+            (Units::IMPERIAL, Units::METRIC) => (temperature - 32.0) / 1.8,
+            _ => temperature,
+        }
+    }
+}
+
+/// Every primary quantity, in canonical order, for frontends that list them rather than hardcode them.
+///
+/// Within a major version entries are only ever added at the end, never removed or reordered.
+pub fn supported_quantities() -> &'static [Quantity] {
+    &Quantity::ALL
+}
+
+/// Every measurment system calculations can be made in, metric first, for frontends to list.
+///
+/// Within a major version entries are only ever added at the end, never removed or reordered.
+pub fn supported_units() -> &'static [Units] {
+    &Units::ALL
+}
+
+/// Well known shot a computed energy can be compared against for perspective.
+pub struct Reference {
+    /// Short name used to pick the reference (e.g. on the command line)
+    pub name: &'static str,
+    /// What the reference shot is
+    pub description: &'static str,
+    /// Mass of the reference projectile in grams
+    pub mass: f64,
+    /// Speed of the reference projectile in m/s
+    pub speed: f64,
+}
+
+impl Reference {
+    /// The reference shot as shot parameters (in given measurment system), e.g. to compare against
+    pub fn params(&self, units: Units) -> Params {
+        let (mass, speed) = self.mass_and_speed(units);
+        Params::new(units, mass, speed, self.energy(units), Some(Quantity::ENERGY))
+    }
+
+    /// Muzzle energy of the reference shot (in joules or FPE depending on measurment system given).
+    /// Derived the way `run` derives it in that system, so the reference compares as equal to itself.
+    pub fn energy(&self, units: Units) -> f64 {
+        let (mass, speed) = self.mass_and_speed(units);
+        derive_energy(&mass, &speed, units.system())
+    }
+
+    /// Mass and speed of the reference shot in given measurment system
+    fn mass_and_speed(&self, units: Units) -> (f64, f64) {
+        match units {
+            Units::METRIC => (self.mass, self.speed),
+            Units::IMPERIAL => (self.mass * GRAINS_IN_GRAM, self.speed / METERS_IN_FOOT),
+        }
+    }
+}
+
+/// Body armor test threats of NIJ Standard-0101.06, the most energetic round of each protection level.
+///
+/// Informational only: armor ratings depend on far more than energy (projectile construction,
+/// backface deformation, etc). These are not a statement about what any armor stops.
+pub const ARMOR_REFERENCES: &[Reference] = &[
+    Reference { name: "nij-iia", description: "NIJ level IIA test round, .40 S&W 180 gr FMJ", mass: 11.7, speed: 352.0 },
+    Reference { name: "nij-ii", description: "NIJ level II test round, .357 Magnum 158 gr JSP", mass: 10.2, speed: 436.0 },
+    Reference { name: "nij-iiia", description: "NIJ level IIIA test round, .44 Magnum 240 gr SJHP", mass: 15.6, speed: 436.0 },
+    Reference { name: "nij-iii", description: "NIJ level III test round, 7.62x51 mm M80 147 gr FMJ", mass: 9.6, speed: 847.0 },
+    Reference { name: "nij-iv", description: "NIJ level IV test round, .30-06 M2 AP 166 gr", mass: 10.8, speed: 878.0 },
+];
+
+/// Load shots are normalized against for unitless indices: a 100 grain bullet at 1000 FPS.
+/// On this scale its mass, speed, energy, momentum and power factor are all exactly 1.
+pub const NORMALIZATION_REFERENCE: Reference = Reference {
+    name: "100gr-1000fps",
+    description: "100 gr at 1000 FPS",
+    mass: 100.0 / GRAINS_IN_GRAM,
+    speed: 1000.0 * METERS_IN_FOOT,
+};
+
+/// Everyday things in motion to get a feel for an energy by, about a joule to over ten kilojoules.
+///
+/// Just for fun: the figures are round guesses at typical cases, and how much harm a moving thing
+/// does has little to do with its energy alone.
+pub const INTUITION_REFERENCES: &[Reference] = &[
+    Reference { name: "apple", description: "a 100 g apple dropped from a meter up", mass: 100.0, speed: 4.43 },
+    Reference { name: "paintball", description: "a paintball leaving the marker", mass: 3.2, speed: 90.0 },
+    Reference { name: "baseball", description: "a fastball thrown at 90 mph", mass: 145.0, speed: 40.2 },
+    Reference { name: "bowling-ball", description: "a bowling ball rolled down the lane", mass: 7260.0, speed: 8.0 },
+    Reference { name: "car-walking", description: "a small car rolling at walking pace", mass: 1_000_000.0, speed: 1.4 },
+    Reference { name: "car-parking", description: "a small car driven at 18 km/h through a parking lot", mass: 1_000_000.0, speed: 5.0 },
+];
+
+/// Looks up a reference in given table by its name, ignoring case
+pub fn find_reference<'a>(table: &'a [Reference], name: &str) -> Option<&'a Reference> {
+    table.iter().find(|reference| reference.name.eq_ignore_ascii_case(name))
+}
+
+/// Cartridge with the bore diameter and typical top muzzle speed of its factory loads.
+pub struct Caliber {
+    /// Short name used to pick the caliber (e.g. on the command line)
+    pub name: &'static str,
+    /// Full name of the cartridge
+    pub description: &'static str,
+    /// Bullet diameter in millimeters
+    pub diameter: f64,
+    /// Typical top muzzle speed in m/s, reached by the lightest bullets in the hottest loads
+    pub max_speed: f64,
+}
+
+impl Caliber {
+    /// Bullet diameter (in millimeters or inches depending on measurment system given)
+    pub fn diameter(&self, units: Units) -> f64 {
+        match units {
+            Units::METRIC => self.diameter,
+            Units::IMPERIAL => self.diameter / 25.4,
+        }
+    }
+
+    /// Typical top muzzle speed (in m/s or FPS depending on measurment system given)
+    pub fn max_speed(&self, units: Units) -> f64 {
+        match units {
+            Units::METRIC => self.max_speed,
+            Units::IMPERIAL => self.max_speed / METERS_IN_FOOT,
+        }
+    }
+}
+
+/// Common cartridges for sanity checking speeds and looking up diameters.
+///
+/// Informational only: ceilings are rounded figures of what fast factory loads reach from
+/// typical barrels, not SAAMI or CIP data. Staying below one says nothing about a load being safe.
+pub const CALIBERS: &[Caliber] = &[
+    Caliber { name: "22lr", description: ".22 Long Rifle", diameter: 5.70, max_speed: 520.0 },
+    Caliber { name: "9mm", description: "9 mm Luger", diameter: 9.02, max_speed: 460.0 },
+    Caliber { name: "357mag", description: ".357 Magnum", diameter: 9.07, max_speed: 550.0 },
+    Caliber { name: "44mag", description: ".44 Magnum", diameter: 10.97, max_speed: 550.0 },
+    Caliber { name: "45acp", description: ".45 ACP", diameter: 11.48, max_speed: 380.0 },
+    Caliber { name: "223rem", description: ".223 Remington", diameter: 5.70, max_speed: 1190.0 },
+    Caliber { name: "65cm", description: "6.5 mm Creedmoor", diameter: 6.71, max_speed: 950.0 },
+    Caliber { name: "308win", description: ".308 Winchester", diameter: 7.82, max_speed: 980.0 },
+    Caliber { name: "3006", description: ".30-06 Springfield", diameter: 7.82, max_speed: 1040.0 },
+    Caliber { name: "300wm", description: ".300 Winchester Magnum", diameter: 7.82, max_speed: 1070.0 },
+];
+
+/// Looks up a caliber in given table by its name, ignoring case
+pub fn find_caliber<'a>(table: &'a [Caliber], name: &str) -> Option<&'a Caliber> {
+    table.iter().find(|caliber| caliber.name.eq_ignore_ascii_case(name))
+}
+
+/// Class of game with the minimum muzzle energy commonly recommended for hunting it humanely.
+#[derive(Clone, Copy, Debug)]
+pub struct GameClass {
+    /// Short name used to pick the class (e.g. on the command line or in the settings file)
+    pub name: &'static str,
+    /// What game the class covers
+    pub description: &'static str,
+    /// Minimum muzzle energy in joules
+    pub min_energy: f64,
+}
+
+impl GameClass {
+    /// Minimum muzzle energy (in joules or FPE depending on measurment system given)
+    pub fn threshold(&self, units: Units) -> f64 {
+        match units {
+            Units::METRIC => self.min_energy,
+            Units::IMPERIAL => self.min_energy / JOULES_IN_FPE,
+        }
+    }
+}
+
+/// Game classes with the rule of thumb minimum energies of North American hunting guides.
+///
+/// Informational defaults only, overridable in the settings file: legal minimums differ between
+/// jurisdictions (and some set none), and shot placement and bullet construction matter more
+/// than energy. Check local regulations.
+pub const GAME_CLASSES: &[GameClass] = &[
+    GameClass { name: "varmint", description: "varmints and predators such as coyote and fox", min_energy: 400.0 * JOULES_IN_FPE },
+    GameClass { name: "deer", description: "deer sized game such as whitetail, roe deer and pronghorn", min_energy: 1000.0 * JOULES_IN_FPE },
+    GameClass { name: "elk", description: "elk, red deer and other large deer", min_energy: 1500.0 * JOULES_IN_FPE },
+    GameClass { name: "moose", description: "moose, bear and other heavy game", min_energy: 2000.0 * JOULES_IN_FPE },
+    GameClass { name: "dangerous", description: "dangerous game such as cape buffalo", min_energy: 4000.0 * JOULES_IN_FPE },
+];
+
+/// Looks up a game class in given table by its name, ignoring case
+pub fn find_game_class<'a>(table: &'a [GameClass], name: &str) -> Option<&'a GameClass> {
+    table.iter().find(|class| class.name.eq_ignore_ascii_case(name))
+}
+
+/// Scoring class of a load in practical shooting, by its power factor.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PowerClass {
+    /// At or above the major floor, scored major
+    MAJOR,
+    /// At or above the minor floor, scored minor
+    MINOR,
+    /// Below the minor floor, not allowed to score at all
+    BELOW,
+}
+
+impl fmt::Display for PowerClass {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PowerClass::MAJOR => write!(f, "major"),
+            PowerClass::MINOR => write!(f, "minor"),
+            PowerClass::BELOW => write!(f, "below minor"),
+        }
+    }
+}
+
+/// Practical shooting division with the power factor floors of its loads.
+pub struct Division {
+    /// Short name used to pick the division (e.g. on the command line)
+    pub name: &'static str,
+    /// Sanctioning body and full name of the division
+    pub description: &'static str,
+    /// Lowest power factor scored major, `None` if the division scores everything minor
+    pub major: Option<f64>,
+    /// Lowest power factor allowed to score
+    pub minor: f64,
+}
+
+impl Division {
+    /// Class given shot scores in the division by its power factor
+    pub fn classify(&self, params: &Params) -> PowerClass {
+        let power_factor = params.power_factor();
+        match self.major {
+            Some(major) if power_factor >= major => PowerClass::MAJOR,
+            _ if power_factor >= self.minor => PowerClass::MINOR,
+            _ => PowerClass::BELOW,
+        }
+    }
+}
+
+/// Pistol divisions of USPSA and IPSC handgun rules, the generic 165/125 floors first.
+///
+/// Informational only: floors change between rulebook editions and chronographing at a match is
+/// what counts, so check the current rules and load with a margin above the floor.
+pub const DIVISIONS: &[Division] = &[
+    Division { name: "generic", description: "Generic", major: Some(165.0), minor: 125.0 },
+    Division { name: "uspsa-open", description: "USPSA Open", major: Some(165.0), minor: 125.0 },
+    Division { name: "uspsa-limited", description: "USPSA Limited", major: Some(165.0), minor: 125.0 },
+    Division { name: "uspsa-single-stack", description: "USPSA Single Stack", major: Some(165.0), minor: 125.0 },
+    Division { name: "uspsa-production", description: "USPSA Production", major: None, minor: 125.0 },
+    Division { name: "uspsa-carry-optics", description: "USPSA Carry Optics", major: None, minor: 125.0 },
+    Division { name: "ipsc-open", description: "IPSC Open", major: Some(160.0), minor: 125.0 },
+    Division { name: "ipsc-standard", description: "IPSC Standard", major: Some(170.0), minor: 125.0 },
+    Division { name: "ipsc-production", description: "IPSC Production", major: None, minor: 125.0 },
+];
+
+/// Looks up a division in given table by its name, ignoring case
+pub fn find_division<'a>(table: &'a [Division], name: &str) -> Option<&'a Division> {
+    table.iter().find(|division| division.name.eq_ignore_ascii_case(name))
+}
+
+/// Unit metric energy is entered and displayed in. Imperial energy is always in FPE.
+/// Calculations and machine readable output always use joules.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EnergyUnit {
+    /// Joules
+    #[default]
+    JOULES,
+    /// Kilogram-force meters, common in older and air rifle literature. 1 kgf·m = 9.80665 J exactly,
+    /// by the definition of standard gravity.
+    KGFM,
+}
+
+impl EnergyUnit {
+    /// Converts energy expressed in this unit to joules
+    pub fn to_joules(self, value: f64) -> f64 {
+        match self {
+            EnergyUnit::JOULES => value,
+            EnergyUnit::KGFM => value * JOULES_IN_KGFM,
+        }
+    }
+
+    /// Converts energy in joules to this unit
+    pub fn from_joules(self, joules: f64) -> f64 {
+        match self {
+            EnergyUnit::JOULES => joules,
+            EnergyUnit::KGFM => joules / JOULES_IN_KGFM,
+        }
+    }
+}
+
+/// Air temperature of ICAO standard atmosphere at sea level, °C
+pub const STANDARD_TEMPERATURE: f64 = 15.0;
+
+/// Speed of sound (m/s) in dry air at given temperature (°C).
+/// Ideal gas model `c = 331.3 · √(1 + T / 273.15)`, giving about 340 m/s at standard temperature.
+/// Humidity and pressure are ignored as their effect is small.
+pub fn speed_of_sound(temperature: f64) -> f64 {
+    331.3 * (1.0 + temperature / 273.15).sqrt()
+}
+
+/// Speed of a projectile relative to the speed of sound, as it matters for suppressed shooting.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SoundRegime {
+    /// Below Mach 1, no sonic crack
+    SUBSONIC,
+    /// From Mach 1 up to Mach 1.2, where the shock wave forms and drag rises steeply
+    TRANSONIC,
+    /// Mach 1.2 and above
+    SUPERSONIC,
+}
+
+impl fmt::Display for SoundRegime {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SoundRegime::SUBSONIC => write!(f, "subsonic"),
+            SoundRegime::TRANSONIC => write!(f, "transonic"),
+            SoundRegime::SUPERSONIC => write!(f, "supersonic"),
+        }
+    }
+}
+
+/// Reference drag function ballistic coefficients are given against. See `drag` module for the model.
+#[derive(Clone, Copy, Default, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DragModel {
+    /// Flat base reference projectile, the traditional default most published BCs use
+    #[default]
+    G1,
+    /// Long boat-tail reference projectile, a closer match for modern long range bullets
+    G7,
+}
+
+/// Community "stopping power" formulas a load can be scored with. All are empirical rules of thumb
+/// defined in imperial terms (grains, FPS, inches), metric loads are converted first. Scores are
+/// only comparable within the same formula and none of them predicts terminal effect reliably.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PowerFormula {
+    /// Taylor knock-out factor: grains × FPS × diameter (in) / 7000. Needs diameter.
+    /// Meant for big game rifles at close range, rewards heavy, wide, slow bullets.
+    TAYLOR,
+    /// Optimal game weight (lb): FPS³ × grains² × 1.5e-12, the heaviest game the load is deemed
+    /// suited for. Ignores diameter and bullet construction entirely.
+    OGW,
+    /// Power factor as used in practical shooting, see `Params::power_factor`
+    PF,
+}
+
+impl PowerFormula {
+    /// All formulas, in the order they are listed in help
+    pub const ALL: [PowerFormula; 3] = [PowerFormula::TAYLOR, PowerFormula::OGW, PowerFormula::PF];
+
+    /// Short name the formula is selected by on the command line
+    pub fn name(self) -> &'static str {
+        match self {
+            PowerFormula::TAYLOR => "taylor",
+            PowerFormula::OGW => "ogw",
+            PowerFormula::PF => "pf",
+        }
+    }
+
+    /// Formula of given short name, ignoring case
+    pub fn from_name(name: &str) -> Option<PowerFormula> {
+        PowerFormula::ALL.into_iter().find(|formula| formula.name().eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// Label the score is reported under, with its unit if it has one
+    pub fn label(self) -> &'static str {
+        match self {
+            PowerFormula::TAYLOR => "Taylor KO factor",
+            PowerFormula::OGW => "Optimal game weight (lb)",
+            PowerFormula::PF => "Power factor",
+        }
+    }
+
+    /// Scores given load. `diameter` is in millimeters or inches depending on measurment system
+    /// of the load, `None` if the formula needs one but none is given.
+    pub fn score(self, params: &Params, diameter: Option<f64>) -> Option<f64> {
+        let (grains, fps, inches) = match params.units {
+            Units::METRIC => (params.mass * GRAINS_IN_GRAM, params.speed / METERS_IN_FOOT, diameter.map(|d| d / 25.4)),
+            Units::IMPERIAL => (params.mass, params.speed, diameter),
+        };
+
+        match self {
+            PowerFormula::TAYLOR => inches.map(|inches| grains * fps * inches / GRAINS_IN_POUND),
+            PowerFormula::OGW => Some(fps.powi(3) * grains.powi(2) * 1.5e-12),
+            PowerFormula::PF => Some(params.power_factor()),
+        }
+    }
+}
+
+/// Text formats shot parameters can be printed in.
+/// Deserializes from the names `from_name` takes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// Human readable report
+    #[default]
+    HUMAN,
+    /// Single line JSON object (see `Params::to_json`)
+    JSON,
+    /// LaTeX math block showing how the derived parameter was obtained (see `Params::to_latex`)
+    LATEX,
+    /// GitHub flavored Markdown table (see `Params::to_markdown`)
+    #[serde(rename = "markdown-table")]
+    MARKDOWN,
+    /// Comma separated values under `CSV_HEADER` (see `Params::to_csv`)
+    CSV,
+}
+
+impl OutputFormat {
+    /// Format of given name as used on the command line and in the settings file: `human`,
+    /// `json`, `latex`, `markdown-table` or `csv`
+    pub fn from_name(name: &str) -> Option<OutputFormat> {
+        match name {
+            "human" => Some(OutputFormat::HUMAN),
+            "json" => Some(OutputFormat::JSON),
+            "latex" => Some(OutputFormat::LATEX),
+            "markdown-table" => Some(OutputFormat::MARKDOWN),
+            "csv" => Some(OutputFormat::CSV),
+            _ => None,
+        }
+    }
+}
+
+/// Conventions for reading and writing numbers and unit labels in human facing text.
+/// Machine readable formats (JSON, binary) never depend on it.
+#[derive(Clone, Copy)]
+pub enum Locale {
+    /// C/English conventions (e.g. "9.81", "grams", "FPS")
+    EN,
+    /// German conventions (e.g. "9,81", "Gramm", "ft/s")
+    DE,
+}
+
+impl Locale {
+    /// Picks a locale out of a tag like "de", "de_DE" or "en-US". "C" and "POSIX" stand for English.
+    pub fn from_tag(tag: &str) -> Result<Locale, MuzzleError> {
+        let language = tag.split(['_', '-', '.']).next().unwrap_or("").to_ascii_lowercase();
+
+        match language.as_str() {
+            "c" | "posix" | "en" => Ok(Locale::EN),
+            "de" => Ok(Locale::DE),
+            _ => Err(MuzzleError::INVALID(format!("Unsupported locale `{}`!", tag))),
+        }
+    }
+
+    /// Rewrites a number typed using this locale into the form `run` understands
+    pub fn normalize_number(&self, input: &str) -> String {
+        match self {
+            Locale::EN => input.to_owned(),
+            Locale::DE => input.replace(',', "."),
+        }
+    }
+
+    /// Formats a number with given amount of decimals using this locale's decimal separator.
+    ///
+    /// Rounds to the nearest (halfway values away from zero) rather than to the closest binary
+    /// float, so a value a hair off a whole number such as 149.9997 shows as 150.000, and anything
+    /// that rounds to zero shows as zero without a minus sign.
+    pub fn format_number(&self, value: f64, decimals: usize) -> String {
+        let rounded = round_to_increment(value, 10f64.powi(-(decimals.min(300) as i32)));
+        // Rounding may overflow past the largest float, which has no decimals worth rounding anyway
+        let value = if rounded.is_finite() { rounded } else { value };
+        let mut formatted = format!("{:.*}", decimals, value);
+        if formatted.starts_with('-') && formatted.chars().all(|c| matches!(c, '-' | '0' | '.')) {
+            formatted.remove(0);
+        }
+
+        match self {
+            Locale::EN => formatted,
+            Locale::DE => formatted.replace('.', ","),
+        }
+    }
+
+    /// Inserts this locale's thousands separator into the whole part of a number formatted by
+    /// `format_number`, "," in English and "." in German (e.g. "12,345.6" or "12.345,6").
+    /// Anything that is not such a number, like "inf", is returned as is.
+    pub fn group_digits(&self, formatted: &str) -> String {
+        let (separator, decimal) = match self {
+            Locale::EN => (',', '.'),
+            Locale::DE => ('.', ','),
+        };
+        let (sign, unsigned) = formatted.strip_prefix('-').map_or(("", formatted), |rest| ("-", rest));
+        let (whole, fraction) = unsigned.split_at(unsigned.find(decimal).unwrap_or(unsigned.len()));
+        if whole.is_empty() || !whole.bytes().all(|byte| byte.is_ascii_digit()) {
+            return formatted.to_owned();
+        }
+
+        let mut grouped = String::with_capacity(formatted.len() + whole.len() / 3);
+        for (i, digit) in whole.chars().enumerate() {
+            if i > 0 && (whole.len() - i).is_multiple_of(3) {
+                grouped.push(separator);
+            }
+            grouped.push(digit);
+        }
+        format!("{}{}{}", sign, grouped, fraction)
+    }
+
+    /// Mass, speed and energy unit labels of given measurment system in this locale
+    pub fn unit_labels(&self, units: Units) -> (&'static str, &'static str, &'static str) {
+        match (self, units) {
+            (Locale::EN, Units::METRIC) => ("grams", "m/s", "Joules"),
+            (Locale::EN, Units::IMPERIAL) => ("grains", "FPS", "FPE"),
+            (Locale::DE, Units::METRIC) => ("Gramm", "m/s", "Joule"),
+            (Locale::DE, Units::IMPERIAL) => ("Grain", "ft/s", "ft·lbf"),
+        }
+    }
+
+    /// Distance unit label of given measurment system in this locale
+    pub fn distance_label(&self, units: Units) -> &'static str {
+        match (self, units) {
+            (Locale::EN, Units::METRIC) => "meters",
+            (Locale::EN, Units::IMPERIAL) => "yards",
+            (Locale::DE, Units::METRIC) => "Meter",
+            (Locale::DE, Units::IMPERIAL) => "Yard",
+        }
+    }
+}
+
 /// Configuration object that needs to be passed to `run` function to perform calculations on.
+/// Can be deserialized from JSON with the same (lowercase) field and variant names, e.g.
+/// `{"units": "imperial", "mass": "150", "speed": 2800}`. Numbers may be given as JSON numbers
+/// or strings, missing fields take their defaults. Serializes back to the same form, numbers as strings.
+#[derive(Clone, Default, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct Config {
     /// Chosen units of measurment
     pub units: Units,
-    /// Mass of the projectile (in grams of grains depending on measurment system chosen)
+    /// Mass of the projectile (in grams of grains depending on measurment system chosen).
+    /// May also be in avoirdupois drams of 27.34375 grains suffixed with `dr`, e.g. "1.125dr".
+    /// That is the weight of shot or ball, not the "dram equivalent" shotshell powder is rated in.
+    #[serde(deserialize_with = "number_or_string")]
     pub mass: Option<String>,
-    /// Speed of the projectile (in m/s of FPS depending on measurment system chosen)
+    /// Mass is given in grains even for metric, as air rifle pellets are commonly sold by.
+    /// Converted to grams before anything else, results are in grams.
+    pub mass_in_grains: bool,
+    /// Speed of the projectile (in m/s of FPS depending on measurment system chosen).
+    /// May also be a Mach number suffixed with `mach`, e.g. "2.5mach", or road speed in
+    /// kilometers or miles per hour suffixed with `kmh` or `mph`, e.g. "100kmh".
+    #[serde(deserialize_with = "number_or_string")]
     pub speed: Option<String>,
-    /// Energy of the projectile (in joules of FPE depending on measurment system chosen)
+    /// Energy of the projectile (in `energy_unit` or FPE depending on measurment system chosen)
+    #[serde(deserialize_with = "number_or_string")]
     pub energy: Option<String>,
+    /// Momentum of the projectile (in kg·m/s or lb·ft/s depending on measurment system chosen).
+    /// Combines with `energy` alone, for a bullet of unknown mass: speed is derived as `v = 2E/p`
+    /// and mass from speed and energy. Combines with `mass` alone as well, for the speed a bullet
+    /// needs to match the momentum of another load: speed is derived as `v = p/m`.
+    #[serde(deserialize_with = "number_or_string")]
+    pub momentum: Option<String>,
+    /// Power factor the load has to make, as in `Params::power_factor`. Combines with `energy`
+    /// alone to solve for the one mass and speed giving both: speed is derived as `v = 14 g E / PF`
+    /// (in FPS and FPE, g in ft/s²) and mass from speed and energy.
+    #[serde(deserialize_with = "number_or_string")]
+    pub power_factor: Option<String>,
+    /// Unit metric energy is given in, must be joules for imperial
+    pub energy_unit: EnergyUnit,
+    /// Ballistic coefficient of the projectile against `drag_model` (in lb/in² regardless of measurment system chosen)
+    #[serde(deserialize_with = "number_or_string")]
+    pub bc: Option<String>,
+    /// Reference drag function `bc` is given against
+    pub drag_model: DragModel,
+    /// Distance to estimate downrange parameters at (in meters or yards depending on measurment system chosen)
+    #[serde(deserialize_with = "number_or_string")]
+    pub distance: Option<String>,
+    /// Air temperature Mach numbers are converted at (in °C or °F depending on measurment system
+    /// chosen), ICAO standard 15 °C when not given
+    #[serde(deserialize_with = "number_or_string")]
+    pub temperature: Option<String>,
+    /// Speed read on the near chronograph for BC estimation (in m/s or FPS depending on measurment system chosen)
+    #[serde(deserialize_with = "number_or_string")]
+    pub v1: Option<String>,
+    /// Distance of the near chronograph from the muzzle (in meters or yards depending on measurment system chosen)
+    #[serde(deserialize_with = "number_or_string")]
+    pub d1: Option<String>,
+    /// Speed read on the far chronograph for BC estimation (in m/s or FPS depending on measurment system chosen)
+    #[serde(deserialize_with = "number_or_string")]
+    pub v2: Option<String>,
+    /// Distance of the far chronograph from the muzzle (in meters or yards depending on measurment system chosen)
+    #[serde(deserialize_with = "number_or_string")]
+    pub d2: Option<String>,
+    /// Cap on bisection steps of the BC estimate, `BC_ITERATIONS` when not given. An estimate
+    /// still not narrowed down by then is an error rather than a guess.
+    #[serde(deserialize_with = "number_or_string")]
+    pub max_iterations: Option<String>,
+    /// Diameter of the projectile for sectional density (in millimeters or inches depending on measurment system chosen)
+    #[serde(deserialize_with = "number_or_string")]
+    pub diameter: Option<String>,
+    /// Length of the barrel bore the projectile travels for the pressure proxy (in millimeters or
+    /// inches depending on measurment system chosen), needs `diameter`
+    #[serde(deserialize_with = "number_or_string")]
+    pub bore_length: Option<String>,
+    /// Powder charge of a reference load for charge estimation (in grams or grains depending on measurment system chosen)
+    #[serde(deserialize_with = "number_or_string")]
+    pub ref_charge: Option<String>,
+    /// Speed the reference load gives (in m/s or FPS depending on measurment system chosen)
+    #[serde(deserialize_with = "number_or_string")]
+    pub ref_velocity: Option<String>,
+    /// Speed to estimate the charge for (in m/s or FPS depending on measurment system chosen)
+    #[serde(deserialize_with = "number_or_string")]
+    pub target_velocity: Option<String>,
+    /// Mass of the firearm for recoil (in kilograms or pounds depending on measurment system chosen)
+    #[serde(deserialize_with = "number_or_string")]
+    pub gun_mass: Option<String>,
+    /// Length of the projectile for gyroscopic stability (in millimeters or inches depending on
+    /// measurment system chosen), needs `diameter` and `twist`
+    #[serde(deserialize_with = "number_or_string")]
+    pub length: Option<String>,
+    /// Barrel twist rate as the distance of one full turn of the rifling (in millimeters or inches
+    /// depending on measurment system chosen), e.g. 10 for a 1:10" barrel
+    #[serde(deserialize_with = "number_or_string")]
+    pub twist: Option<String>,
+    /// Speed of the wind for drift at the downrange distance (in m/s or mph depending on
+    /// measurment system chosen), needs `bc` and `distance`
+    #[serde(deserialize_with = "number_or_string")]
+    pub wind_speed: Option<String>,
+    /// Direction the wind blows from in degrees clockwise from the line of fire, 90 (full value
+    /// from the right) when not given: 0 is a headwind, 270 full value from the left
+    #[serde(deserialize_with = "number_or_string")]
+    pub wind_angle: Option<String>,
+}
+
+impl Config {
+    /// Layers `overrides` on top of this config.
+    /// Every parameter given in `overrides` replaces the one in `self`, missing ones are kept as is.
+    /// Units, energy unit and drag model are always taken from `overrides`.
+    pub fn merge(self, overrides: Config) -> Config {
+        Config {
+            units: overrides.units,
+            mass: overrides.mass.or(self.mass),
+            mass_in_grains: overrides.mass_in_grains,
+            speed: overrides.speed.or(self.speed),
+            energy: overrides.energy.or(self.energy),
+            momentum: overrides.momentum.or(self.momentum),
+            power_factor: overrides.power_factor.or(self.power_factor),
+            energy_unit: overrides.energy_unit,
+            bc: overrides.bc.or(self.bc),
+            drag_model: overrides.drag_model,
+            distance: overrides.distance.or(self.distance),
+            temperature: overrides.temperature.or(self.temperature),
+            v1: overrides.v1.or(self.v1),
+            d1: overrides.d1.or(self.d1),
+            v2: overrides.v2.or(self.v2),
+            d2: overrides.d2.or(self.d2),
+            max_iterations: overrides.max_iterations.or(self.max_iterations),
+            diameter: overrides.diameter.or(self.diameter),
+            bore_length: overrides.bore_length.or(self.bore_length),
+            ref_charge: overrides.ref_charge.or(self.ref_charge),
+            ref_velocity: overrides.ref_velocity.or(self.ref_velocity),
+            target_velocity: overrides.target_velocity.or(self.target_velocity),
+            gun_mass: overrides.gun_mass.or(self.gun_mass),
+            length: overrides.length.or(self.length),
+            twist: overrides.twist.or(self.twist),
+            wind_speed: overrides.wind_speed.or(self.wind_speed),
+            wind_angle: overrides.wind_angle.or(self.wind_angle),
+        }
+    }
+}
+
+/// Core shot parameters in SI units regardless of measurment system, as returned by `run_si`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ParamsSi {
+    /// Mass of the projectile, kg
+    pub mass: f64,
+    /// Speed of the projectile, m/s
+    pub speed: f64,
+    /// Kinetic energy of the projectile, J
+    pub energy: f64,
+}
+
+/// Hashable stand-in for shot parameters, as made by `Params::key`, for deduplicating loads.
+///
+/// Mass, speed and energy are each quantized to the nearest multiple of the precision the key
+/// was made with, so loads whose values round to the same multiples (and that share units) are
+/// equal. Being rounding, two values closer than the precision can still straddle a halfway
+/// point and land in neighbouring multiples. Keys made with different precisions are not comparable.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ParamsKey {
+    units: Units,
+    mass: i64,
+    speed: i64,
+    energy: i64,
+}
+
+/// Verdict on mass, speed and energy given all at once, when nothing is left to derive.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Consistency {
+    /// Energy is within `CONSISTENCY_TOLERANCE` of what mass and speed give
+    CONSISTENT,
+    /// Energy is further off what mass and speed give, at least one of the three is wrong
+    INCONSISTENT,
+    /// Agreement can not be judged, as mass and speed give no positive finite energy to compare to
+    OVERDETERMINED,
+}
+
+impl Consistency {
+    /// Judges whether given `mass`, `speed` and `energy` agree in given measurment system
+    pub fn judge(units: Units, mass: f64, speed: f64, energy: f64) -> Consistency {
+        Consistency::against(expected_energy(units, mass, speed), energy)
+    }
+
+    /// Judges given `energy` against the one mass and speed give, `None` if they give none
+    fn against(expected: Option<f64>, energy: f64) -> Consistency {
+        match expected {
+            Some(expected) if energy.is_finite() && ((energy - expected) / expected).abs() <= CONSISTENCY_TOLERANCE => Consistency::CONSISTENT,
+            Some(_) if energy.is_finite() => Consistency::INCONSISTENT,
+            _ => Consistency::OVERDETERMINED,
+        }
+    }
+
+    /// Lowercase name of the verdict as used in output
+    pub fn name(self) -> &'static str {
+        match self {
+            Consistency::CONSISTENT => "consistent",
+            Consistency::INCONSISTENT => "inconsistent",
+            Consistency::OVERDETERMINED => "overdetermined",
+        }
+    }
+}
+
+/// Energy given `mass` and `speed` make, what a given energy is judged against, `None` unless
+/// it is a positive finite number
+fn expected_energy(units: Units, mass: f64, speed: f64) -> Option<f64> {
+    Some(derive_energy(&mass, &speed, units.system())).filter(|energy| energy.is_finite() && *energy > 0.0)
+}
+
+impl fmt::Display for Consistency {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
 }
 
 /// Object that is the output of the `run` function.
@@ -34,73 +908,1486 @@ pub struct Params {
     /// Energy of the projectile (in joules of FPE depending on measurment system chosen)
     pub energy: f64,
     /// A flag that points to the fact that `run` function got all three of the input parameters
-    /// as input in `Config` object and no calculations have been performed
+    /// as input in `Config` object and no calculations have been performed.
+    ///
+    /// Deprecated, kept for existing consumers: it is always `overdetermined.is_some()`.
     pub bogus: bool,
+    /// How well the three primary parameters agree when all of them were given, `None` when one
+    /// was derived
+    pub overdetermined: Option<Consistency>,
+    /// Energy mass and speed give (in joules of FPE depending on measurment system chosen), that
+    /// the given one was judged against for `overdetermined`. `None` when one was derived, or
+    /// when mass and speed give no positive finite energy.
+    pub expected_energy: Option<f64>,
+    /// Quantity that was computed from the other two, `None` when all three were given
+    pub derived: Option<Quantity>,
+    /// Speed left at the downrange distance, if ballistic coefficient and distance were given
+    pub retained_speed: Option<f64>,
+    /// Energy left at the downrange distance, if ballistic coefficient and distance were given
+    pub retained_energy: Option<f64>,
+    /// Downrange energy as a fraction of muzzle energy (0 to 1), if ballistic coefficient and
+    /// distance were given. Comes from the same coarse drag estimate as `retained_energy`.
+    pub retained_fraction: Option<f64>,
+    /// Estimated time of flight to the downrange distance, s
+    pub time_of_flight: Option<f64>,
+    /// Drop below the line of the bore at the downrange distance (in centimeters or inches
+    /// depending on measurment system chosen), if the projectile gets there.
+    ///
+    /// Free fall over the time of flight, `g t² / 2`, with the bore level. Sight height, zero
+    /// distance and gravity's effect on drag are ignored, so it is the drop a scope zeroed at
+    /// the muzzle would see, good for flat shots only.
+    pub drop: Option<f64>,
+    /// `drop` as an angle seen from the muzzle, in true minutes of angle (1/60 degree, about
+    /// 1.047 inches at 100 yards or 2.91 cm at 100 meters)
+    pub drop_moa: Option<f64>,
+    /// `drop` as an angle seen from the muzzle, in milliradians (10 cm at 100 meters)
+    pub drop_mil: Option<f64>,
+    /// Sideways deflection by the wind at the downrange distance (in centimeters or inches
+    /// depending on measurment system chosen), if wind speed was given and the projectile gets there.
+    /// Positive is to the left, as a wind from the right pushes it.
+    ///
+    /// Lag time rule: crosswind times the time of flight lost to drag, `w (t - d / v)`. Drift comes
+    /// from how much slower the projectile is than it would be in a vacuum, not from its time of
+    /// flight as such. Wind is taken as steady and the same all the way, which it never is.
+    pub wind_drift: Option<f64>,
+    /// Ballistic coefficient (lb/in², against the chosen drag model) implied by chronograph readings, if given
+    pub estimated_bc: Option<f64>,
+    /// Bisection steps the BC estimate took, if made
+    pub estimated_bc_iterations: Option<usize>,
+    /// Far chronograph speed the estimated BC gives minus the one read (in m/s or FPS depending
+    /// on measurment system chosen), if made. Near zero, a check on the estimate.
+    pub estimated_bc_residual: Option<f64>,
+    /// Sectional density, if diameter was given. In lb/in² for imperial, typically 0.15 to 0.35
+    /// for rifle bullets. In g/mm² for metric, typically 0.10 to 0.25 (1 lb/in² = 0.70307 g/mm²).
+    pub sectional_density: Option<f64>,
+    /// Crude penetration index, sectional density (lb/in², whatever the measurment system) times
+    /// impact speed (FPS) over 100, if diameter was given. Impact speed is the downrange speed
+    /// when there is one, muzzle speed otherwise. A 150 gr .308 bullet at 2800 FPS scores about 6.
+    ///
+    /// This is a rough APPROXIMATION for comparing loads against each other, not a depth. Real
+    /// penetration depends above all on how the bullet expands or breaks up and on what it hits,
+    /// neither of which mass, diameter and speed tell anything about: a fragmenting varmint bullet
+    /// and a solid of equal sectional density score the same here and perform nothing alike.
+    pub penetration_index: Option<f64>,
+    /// Muzzle energy per swept bore volume, if diameter and bore length were given. In psi for
+    /// imperial, MPa for metric.
+    ///
+    /// This is NOT chamber pressure and not internal ballistics of any kind. It is the average push
+    /// on the base that would account for the energy over the bore if all of it went into the
+    /// projectile, ignoring the pressure curve, friction, gas and powder mass, and everything else a
+    /// real pressure figure depends on. Peak pressures are several times higher. Only good to tell
+    /// an airgun from a magnum rifle, never to compare against a load's pressure limit.
+    pub pressure_proxy: Option<f64>,
+    /// Powder charge expected to give the target velocity, if a reference load was given (in
+    /// grams or grains depending on measurment system chosen).
+    ///
+    /// Scaled in proportion to velocity from the reference load, which holds only roughly and only
+    /// close to it. This is interpolation, not a pressure model: it knows nothing of powder,
+    /// case or pressure limits. Never load past published maximum charges on its word.
+    pub estimated_charge: Option<f64>,
+    /// Rearward speed of the firearm, if its mass was given (in m/s or FPS depending on measurment
+    /// system chosen). Free recoil by momentum conservation with the projectile alone, the push of
+    /// powder gas (often a third or more of real recoil in rifles) is ignored.
+    pub recoil_velocity: Option<f64>,
+    /// Kinetic energy of the firearm moving at `recoil_velocity` (in Joules or FPE depending on
+    /// measurment system chosen)
+    pub recoil_energy: Option<f64>,
+    /// Gyroscopic stability factor by the Miller twist rule, if length, twist and diameter were given.
+    ///
+    /// Corrected for velocity and air temperature, taken at standard sea level pressure. Below 1.0
+    /// the bullet is unstable, 1.0 to 1.5 is marginal and may tumble in colder or denser air,
+    /// above 1.5 is stable. Miller fitted the rule to solid lead-core bullets, so polymer tips and
+    /// monolithic bullets read somewhat off.
+    pub stability: Option<f64>,
+}
+
+impl Params {
+    /// Creates shot parameters without any downrange estimates
+    fn new(units: Units, mass: f64, speed: f64, energy: f64, derived: Option<Quantity>) -> Params {
+        let expected_energy = derived.is_none().then(|| expected_energy(units, mass, speed)).flatten();
+        Params {
+            units,
+            mass,
+            speed,
+            energy,
+            bogus: derived.is_none(),
+            overdetermined: derived.is_none().then(|| Consistency::against(expected_energy, energy)),
+            expected_energy,
+            derived,
+            retained_speed: None,
+            retained_energy: None,
+            retained_fraction: None,
+            time_of_flight: None,
+            drop: None,
+            drop_moa: None,
+            drop_mil: None,
+            wind_drift: None,
+            estimated_bc: None,
+            estimated_bc_iterations: None,
+            estimated_bc_residual: None,
+            sectional_density: None,
+            penetration_index: None,
+            pressure_proxy: None,
+            estimated_charge: None,
+            recoil_velocity: None,
+            recoil_energy: None,
+            stability: None,
+        }
+    }
+
+    /// Name and value of the quantity that was computed from the other two, `None` when all three were given
+    pub fn derived_value(&self) -> Option<(String, f64)> {
+        let derived = self.derived?;
+        Some((derived.name().to_owned(), self.value(derived)))
+    }
+
+    /// Value of given primary quantity (energy always in Joules or FPE)
+    pub fn value(&self, quantity: Quantity) -> f64 {
+        match quantity {
+            Quantity::MASS => self.mass,
+            Quantity::SPEED => self.speed,
+            Quantity::ENERGY => self.energy,
+        }
+    }
+
+    /// Mass of the projectile in grams or grains depending on measurment system given, whichever
+    /// system the shot is in
+    pub fn mass_in(&self, units: Units) -> f64 {
+        match (self.units, units) {
+            (Units::METRIC, Units::IMPERIAL) => self.mass * GRAINS_IN_GRAM,
+            (Units::IMPERIAL, Units::METRIC) => self.mass / GRAINS_IN_GRAM,
+            _ => self.mass,
+        }
+    }
+
+    /// Speed of the projectile in kilometers per hour, whichever system the shot is in
+    pub fn speed_kmh(&self) -> f64 {
+        self.speed_in(Units::METRIC) / METERS_PER_SECOND_IN_KMH
+    }
+
+    /// Speed of the projectile in miles per hour, whichever system the shot is in
+    pub fn speed_mph(&self) -> f64 {
+        self.speed_in(Units::METRIC) / METERS_PER_SECOND_IN_MPH
+    }
+
+    /// Speed of the projectile in m/s or FPS depending on measurment system given
+    fn speed_in(&self, units: Units) -> f64 {
+        match (self.units, units) {
+            (Units::METRIC, Units::IMPERIAL) => self.speed / METERS_IN_FOOT,
+            (Units::IMPERIAL, Units::METRIC) => self.speed * METERS_IN_FOOT,
+            _ => self.speed,
+        }
+    }
+
+    /// Primary quantities in display order (mass, speed, energy), each with its value and whether it was derived
+    pub fn summary(&self) -> [(Quantity, f64, bool); 3] {
+        Quantity::ALL.map(|quantity| (quantity, self.value(quantity), self.derived == Some(quantity)))
+    }
+
+    /// Key equal for loads with the same units whose mass, speed and energy round to the same
+    /// multiples of `precision` (e.g. 0.01). See `ParamsKey` for the caveats.
+    /// Fails unless `precision` is a positive finite number.
+    pub fn key(&self, precision: f64) -> Result<ParamsKey, MuzzleError> {
+        if !(precision > 0.0 && precision.is_finite()) {
+            return Err(MuzzleError::INVALID("Incorrect key precision. It must be a positive number.".to_owned()));
+        }
+
+        Ok(ParamsKey {
+            units: self.units,
+            mass: (self.mass / precision).round() as i64,
+            speed: (self.speed / precision).round() as i64,
+            energy: (self.energy / precision).round() as i64,
+        })
+    }
+
+    /// Core parameters converted to SI: grams or grains to kg, FPS to m/s, FPE to J
+    pub fn to_si(&self) -> ParamsSi {
+        match self.units {
+            Units::METRIC => ParamsSi { mass: self.mass / GRAMS_IN_KILO, speed: self.speed, energy: self.energy },
+            Units::IMPERIAL => ParamsSi {
+                mass: self.mass / GRAINS_IN_GRAM / GRAMS_IN_KILO,
+                speed: self.speed * METERS_IN_FOOT,
+                energy: self.energy * JOULES_IN_FPE,
+            },
+        }
+    }
+
+    /// Returns the same shot parameters expressed in `units`, estimates included. Values are
+    /// converted rather than derived again, ratios and angles are kept as they are.
+    pub fn in_units(&self, units: Units) -> Params {
+        // Metric to imperial factors, inverted the other way around
+        let factor = |to_imperial: f64| match (self.units, units) {
+            (Units::METRIC, Units::IMPERIAL) => to_imperial,
+            (Units::IMPERIAL, Units::METRIC) => 1.0 / to_imperial,
+            _ => 1.0,
+        };
+        let (mass, speed, energy) = (factor(GRAINS_IN_GRAM), factor(1.0 / METERS_IN_FOOT), factor(1.0 / JOULES_IN_FPE));
+        // Centimeters to inches, g/mm² to lb/in² and MPa to psi
+        let (drop, density, pressure) = (factor(1.0 / 2.54), factor(GRAINS_IN_GRAM / GRAINS_IN_POUND * 25.4f64.powi(2)), factor(1e6 / PASCALS_IN_PSI));
+
+        Params {
+            units,
+            mass: self.mass * mass,
+            speed: self.speed * speed,
+            energy: self.energy * energy,
+            bogus: self.bogus,
+            overdetermined: self.overdetermined,
+            expected_energy: self.expected_energy.map(|value| value * energy),
+            derived: self.derived,
+            retained_speed: self.retained_speed.map(|value| value * speed),
+            retained_energy: self.retained_energy.map(|value| value * energy),
+            retained_fraction: self.retained_fraction,
+            time_of_flight: self.time_of_flight,
+            drop: self.drop.map(|value| value * drop),
+            drop_moa: self.drop_moa,
+            drop_mil: self.drop_mil,
+            wind_drift: self.wind_drift.map(|value| value * drop),
+            estimated_bc: self.estimated_bc,
+            estimated_bc_iterations: self.estimated_bc_iterations,
+            estimated_bc_residual: self.estimated_bc_residual.map(|value| value * speed),
+            sectional_density: self.sectional_density.map(|value| value * density),
+            penetration_index: self.penetration_index,
+            pressure_proxy: self.pressure_proxy.map(|value| value * pressure),
+            estimated_charge: self.estimated_charge.map(|value| value * mass),
+            recoil_velocity: self.recoil_velocity.map(|value| value * speed),
+            recoil_energy: self.recoil_energy.map(|value| value * energy),
+            stability: self.stability,
+        }
+    }
+
+    /// Core parameters as `to_si` converts them, as a `(mass, speed, energy)` tuple of
+    /// kg, m/s and J for libraries that take plain SI numbers
+    pub fn as_si_tuple(&self) -> (f64, f64, f64) {
+        let ParamsSi { mass, speed, energy } = self.to_si();
+        (mass, speed, energy)
+    }
+
+    /// Momentum of the projectile (in kg·m/s or lb·ft/s depending on measurment system chosen)
+    pub fn momentum(&self) -> f64 {
+        (self.mass / self.units.system().mass_scale()) * self.speed
+    }
+
+    /// Kinetic energy per unit of projectile mass (in J/kg or ft·lbf/lb depending on measurment system
+    /// chosen), for comparing how efficiently loads of different bullet weights carry energy
+    pub fn specific_energy(&self) -> f64 {
+        match self.units {
+            Units::METRIC => self.energy / (self.mass / GRAMS_IN_KILO),
+            Units::IMPERIAL => self.energy / (self.mass / GRAINS_IN_POUND),
+        }
+    }
+
+    /// Power factor of the projectile as used in practical shooting: grains × FPS / 1000.
+    /// Always expressed in imperial terms, metric parameters are converted first.
+    pub fn power_factor(&self) -> f64 {
+        match self.units {
+            Units::METRIC => (self.mass * GRAINS_IN_GRAM) * (self.speed / METERS_IN_FOOT) / 1000.0,
+            Units::IMPERIAL => self.mass * self.speed / 1000.0,
+        }
+    }
+
+    /// Mach number of the projectile in air of given temperature (in °C or °F depending on measurment system chosen)
+    pub fn mach(&self, temperature: f64) -> f64 {
+        match self.units {
+            Units::METRIC => self.speed / speed_of_sound(temperature),
+            Units::IMPERIAL => self.speed * METERS_IN_FOOT / speed_of_sound(Units::IMPERIAL.convert_temperature(temperature, Units::METRIC)),
+        }
+    }
+
+    /// Classifies projectile speed against the speed of sound in air of given temperature
+    /// (in °C or °F depending on measurment system chosen)
+    pub fn sound_regime(&self, temperature: f64) -> SoundRegime {
+        match self.mach(temperature) {
+            mach if mach < 1.0 => SoundRegime::SUBSONIC,
+            mach if mach < 1.2 => SoundRegime::TRANSONIC,
+            _ => SoundRegime::SUPERSONIC,
+        }
+    }
+
+    /// Energy of the shot as a fraction of `reference` shot energy (1.0 means equal)
+    pub fn energy_ratio(&self, reference: &Reference) -> f64 {
+        self.energy / reference.energy(self.units)
+    }
+
+    /// Reference in `table` with energy closest to that of the shot, as in `energy_ratio` being
+    /// closest to 1 on a log scale: twice the energy is as far off as half of it. `None` for an
+    /// empty table.
+    pub fn nearest_reference<'a>(&self, table: &'a [Reference]) -> Option<&'a Reference> {
+        let distance = |reference: &Reference| self.energy_ratio(reference).ln().abs();
+        table.iter().min_by(|a, b| distance(a).total_cmp(&distance(b)))
+    }
+
+    /// Whether muzzle energy of the shot is at least the minimum of given game class
+    pub fn meets(&self, class: &GameClass) -> bool {
+        self.energy >= class.threshold(self.units)
+    }
+
+    /// Mass, speed, energy, momentum and power factor of the shot each as a fraction of the same
+    /// quantity of `reference` shot (1.0 means equal), labelled by name
+    pub fn ratios(&self, reference: &Reference) -> [(&'static str, f64); 5] {
+        let energy = self.energy_ratio(reference);
+        let reference = reference.params(self.units);
+        [
+            (Quantity::MASS.name(), self.mass / reference.mass),
+            (Quantity::SPEED.name(), self.speed / reference.speed),
+            (Quantity::ENERGY.name(), energy),
+            ("momentum", self.momentum() / reference.momentum()),
+            ("power factor", self.power_factor() / reference.power_factor()),
+        ]
+    }
+
+    /// Orders shot parameters by energy, breaking ties by momentum. Meant for sorting loads, e.g.
+    /// `loads.sort_by(|a, b| b.cmp_by_energy(a))` puts the most energetic first.
+    /// NaN values get a stable place in the order instead of breaking the sort.
+    pub fn cmp_by_energy(&self, other: &Self) -> Ordering {
+        let total_cmp = |a: f64, b: f64| a.partial_cmp(&b).unwrap_or_else(|| a.total_cmp(&b));
+
+        total_cmp(self.energy, other.energy).then_with(|| total_cmp(self.momentum(), other.momentum()))
+    }
+
+    /// Returns parameters of the same projectile with its speed multiplied by `factor`.
+    /// Energy (and so momentum) is recomputed from the new speed to keep the result consistent,
+    /// downrange estimates are dropped.
+    pub fn scale_speed(&self, factor: f64) -> Params {
+        self.with_speed(self.speed * factor)
+    }
+
+    /// Returns parameters of a projectile with mass multiplied by `factor` going at the same speed.
+    /// Energy (and so momentum) is recomputed from the new mass to keep the result consistent,
+    /// downrange estimates are dropped.
+    pub fn scale_mass(&self, factor: f64) -> Params {
+        self.with_mass(self.mass * factor)
+    }
+
+    /// Returns parameters of the same projectile going at `speed`.
+    /// Mass is held, energy is recomputed, downrange estimates are dropped.
+    pub fn with_speed(&self, speed: f64) -> Params {
+        let energy = derive_energy(&self.mass, &speed, self.units.system());
+        Params::new(self.units, self.mass, speed, energy, Some(Quantity::ENERGY))
+    }
+
+    /// Returns parameters of a projectile of `mass` going at the same speed.
+    /// Speed is held, energy is recomputed, downrange estimates are dropped.
+    pub fn with_mass(&self, mass: f64) -> Params {
+        let energy = derive_energy(&mass, &self.speed, self.units.system());
+        Params::new(self.units, mass, self.speed, energy, Some(Quantity::ENERGY))
+    }
+
+    /// Returns parameters of the same projectile carrying `energy` (in Joules or FPE).
+    /// Mass is held, speed is recomputed, downrange estimates are dropped.
+    /// Energy of the opposite sign to mass has no real speed and is `MuzzleError::DOMAIN`.
+    pub fn with_energy(&self, energy: f64) -> Result<Params, MuzzleError> {
+        let speed = derive_speed(&self.mass, &energy, self.units.system())?;
+        Ok(Params::new(self.units, self.mass, speed, energy, Some(Quantity::SPEED)))
+    }
+
+    /// Renders a `muzzle` invocation giving all three parameters that reproduces these shot parameters.
+    /// Numbers are printed with full precision so the command yields exactly the same values.
+    pub fn to_command(&self) -> String {
+        let units = match self.units {
+            Units::METRIC => "",
+            Units::IMPERIAL => " --imperial",
+        };
+
+        let values: Vec<String> = self.summary().iter().map(|(quantity, value, _)| format!(" --{} {}", quantity, format_machine(*value))).collect();
+        format!("muzzle{}{}", units, values.concat())
+    }
+
+    /// Packs shot parameters into a fixed-size binary record for zero-parse consumption.
+    /// Only the core parameters are packed, downrange estimates are left out.
+    ///
+    /// Layout (all multi-byte values are little-endian):
+    ///
+    /// | Offset | Size | Field                                 |
+    /// |--------|------|---------------------------------------|
+    /// | 0      | 8    | mass as `f64`                         |
+    /// | 8      | 8    | speed as `f64`                        |
+    /// | 16     | 8    | energy as `f64`                       |
+    /// | 24     | 1    | units (`0` - metric, `1` - imperial)  |
+    /// | 25     | 1    | bogus flag (`0` or `1`)               |
+    pub fn to_bytes(&self) -> [u8; PACKED_LEN] {
+        let mut bytes = [0u8; PACKED_LEN];
+
+        bytes[0..8].copy_from_slice(&self.mass.to_le_bytes());
+        bytes[8..16].copy_from_slice(&self.speed.to_le_bytes());
+        bytes[16..24].copy_from_slice(&self.energy.to_le_bytes());
+        bytes[24] = match self.units {
+            Units::METRIC => 0,
+            Units::IMPERIAL => 1,
+        };
+        bytes[25] = self.bogus as u8;
+
+        bytes
+    }
+
+    /// Renders shot parameters as a single line JSON object tagged with `SCHEMA_VERSION`.
+    /// Missing downrange estimates as well as non-finite numbers are rendered as `null`.
+    pub fn to_json(&self) -> String {
+        self.to_json_with_policy(&FormatPolicy::default(), false)
+    }
+
+    /// Renders shot parameters as `to_json` does, indented over multiple lines for reading
+    pub fn to_json_pretty(&self) -> String {
+        self.to_json_with_policy(&FormatPolicy::default(), true)
+    }
+
+    /// Renders shot parameters as `to_json` (or `to_json_pretty`) does, with mass, speed and
+    /// energy in the notation `policy` gives them instead of the default one
+    pub fn to_json_with_policy(&self, policy: &FormatPolicy, pretty: bool) -> String {
+        policy.to_json(&self.json_value(), pretty)
+    }
+
+    /// Renders shot parameters as `to_json` (or `to_json_pretty`) does with `metadata` added under
+    /// a trailing `meta` key, for records that have to tell when and from what they were made
+    pub fn to_json_with_metadata(&self, metadata: &Metadata, pretty: bool) -> String {
+        let mut document = self.json_value();
+        document["meta"] = metadata.to_json_value();
+        FormatPolicy::default().to_json(&document, pretty)
+    }
+
+    /// Renders shot parameters in both measurment systems, converted by `in_units`, as `to_json`
+    /// (or `to_json_pretty`) does under `metric` and `imperial` keys of a document tagged with
+    /// `SCHEMA_VERSION`
+    pub fn to_json_both_systems(&self, pretty: bool) -> String {
+        let document = json!({
+            "schema_version": SCHEMA_VERSION,
+            "metric": self.in_units(Units::METRIC).json_value(),
+            "imperial": self.in_units(Units::IMPERIAL).json_value(),
+        });
+        FormatPolicy::default().to_json(&document, pretty)
+    }
+
+    /// JSON document `to_json` and `to_json_pretty` render
+    pub(crate) fn json_value(&self) -> serde_json::Value {
+        let units = match self.units {
+            Units::METRIC => "metric",
+            Units::IMPERIAL => "imperial",
+        };
+
+        json!({
+            "schema_version": SCHEMA_VERSION,
+            "units": units,
+            "mass": self.mass,
+            "speed": self.speed,
+            "energy": self.energy,
+            "bogus": self.bogus,
+            "derived": self.derived.map(Quantity::name),
+            "overdetermined": self.overdetermined.map(Consistency::name),
+            "expected_energy": self.expected_energy,
+            "retained_speed": self.retained_speed,
+            "retained_energy": self.retained_energy,
+            "retained_fraction": self.retained_fraction,
+            "time_of_flight": self.time_of_flight,
+            "drop": self.drop,
+            "drop_moa": self.drop_moa,
+            "drop_mil": self.drop_mil,
+            "wind_drift": self.wind_drift,
+            "estimated_bc": self.estimated_bc,
+            "estimated_bc_iterations": self.estimated_bc_iterations,
+            "estimated_bc_residual": self.estimated_bc_residual,
+            "sectional_density": self.sectional_density,
+            "penetration_index": self.penetration_index,
+            "pressure_proxy": self.pressure_proxy,
+            "estimated_charge": self.estimated_charge,
+            "recoil_velocity": self.recoil_velocity,
+            "recoil_energy": self.recoil_energy,
+            "stability": self.stability,
+        })
+    }
+
+    /// Renders shot parameters as a LaTeX `align*` block, one line per parameter. The line of
+    /// the derived parameter also shows the formula it came from.
+    /// Energy is always shown in joules or foot-pounds.
+    pub fn to_latex(&self) -> String {
+        let (mass_unit, speed_unit, energy_unit) = match self.units {
+            Units::METRIC => (r"\mathrm{g}", r"\mathrm{m/s}", r"\mathrm{J}"),
+            Units::IMPERIAL => (r"\mathrm{gr}", r"\mathrm{ft/s}", r"\mathrm{ft \cdot lbf}"),
+        };
+
+        // Formulas keep the unit conversion constants visible so the numbers can be checked by hand
+        let formula = |quantity: Quantity| match (quantity, self.units) {
+            (Quantity::MASS, Units::METRIC) => r"\frac{2 E}{v^2} \cdot 1000 = ",
+            (Quantity::MASS, Units::IMPERIAL) => r"\frac{2 g E}{v^2} \cdot 7000 = ",
+            (Quantity::SPEED, Units::METRIC) => r"\sqrt{\frac{2 E}{m / 1000}} = ",
+            (Quantity::SPEED, Units::IMPERIAL) => r"\sqrt{\frac{2 g E}{m / 7000}} = ",
+            (Quantity::ENERGY, Units::METRIC) => r"\frac{1}{2} \cdot \frac{m}{1000} \cdot v^2 = ",
+            (Quantity::ENERGY, Units::IMPERIAL) => r"\frac{m / 7000 \cdot v^2}{2 g} = ",
+        };
+        let line = |symbol: &str, quantity: Quantity, value: f64, unit: &str| {
+            let formula = if self.derived == Some(quantity) { formula(quantity) } else { "" };
+            format!(r"{} &= {}{:.3}\,{}", symbol, formula, value, unit)
+        };
+
+        let mut lines = vec![
+            line("m", Quantity::MASS, self.mass, mass_unit),
+            line("v", Quantity::SPEED, self.speed, speed_unit),
+            line("E", Quantity::ENERGY, self.energy, energy_unit),
+        ];
+        if self.units == Units::IMPERIAL && self.derived.is_some() {
+            lines.push(format!(r"g &= {}\,\mathrm{{ft/s^2}}", GEE_FPS));
+        }
+
+        format!("\\begin{{align*}}\n{}\n\\end{{align*}}", lines.join(" \\\\\n"))
+    }
+
+    /// Renders shot parameters as a single CSV record (without line terminator) with columns as
+    /// named by `CSV_HEADER`. Missing estimates are left empty.
+    pub fn to_csv(&self) -> String {
+        self.to_csv_with_policy(&FormatPolicy::default())
+    }
+
+    /// Renders shot parameters as `to_csv` does, with mass, speed and energy in the notation
+    /// `policy` gives them instead of the default one
+    pub fn to_csv_with_policy(&self, policy: &FormatPolicy) -> String {
+        let units = match self.units {
+            Units::METRIC => "metric",
+            Units::IMPERIAL => "imperial",
+        };
+        let optional = |value: Option<f64>| value.map(format_machine).unwrap_or_default();
+        let (speed, energy) = (policy.speed, policy.energy);
+
+        [
+            units.to_owned(),
+            policy.mass.format(self.mass),
+            speed.format(self.speed),
+            energy.format(self.energy),
+            self.bogus.to_string(),
+            self.derived.map(Quantity::name).unwrap_or_default().to_owned(),
+            self.retained_speed.map(|value| speed.format(value)).unwrap_or_default(),
+            self.retained_energy.map(|value| energy.format(value)).unwrap_or_default(),
+            optional(self.retained_fraction),
+            optional(self.time_of_flight),
+            optional(self.estimated_bc),
+            optional(self.sectional_density),
+            optional(self.pressure_proxy),
+            optional(self.estimated_charge),
+            optional(self.recoil_velocity),
+            optional(self.recoil_energy),
+            optional(self.stability),
+            optional(self.drop),
+            optional(self.drop_moa),
+            optional(self.drop_mil),
+            optional(self.penetration_index),
+            optional(self.wind_drift),
+            self.overdetermined.map(Consistency::name).unwrap_or_default().to_owned(),
+            self.estimated_bc_iterations.map(|count| count.to_string()).unwrap_or_default(),
+            optional(self.estimated_bc_residual),
+            self.expected_energy.map(|value| energy.format(value)).unwrap_or_default(),
+        ]
+        .join(",")
+    }
+
+    /// Renders shot parameters as a Markdown table of quantity, value and unit, columns padded to
+    /// line up in plain text too. Primary parameters come first in `summary` order, the derived one
+    /// marked as such, followed by whichever optional estimates were made.
+    /// Energy is always shown in joules or foot-pounds.
+    pub fn to_markdown(&self) -> String {
+        let header = ["Quantity", "Value", "Unit"].map(str::to_owned);
+        let rows: Vec<[String; 3]> = self
+            .rows()
+            .into_iter()
+            .map(|(label, value, unit)| [label, format!("{:.3}", value), unit.to_owned()])
+            .collect();
+
+        let widths = [0, 1, 2].map(|column| {
+            rows.iter().chain([&header]).map(|row| row[column].chars().count()).max().unwrap_or(0)
+        });
+        let render = |row: &[String; 3]| {
+            format!("| {:<w0$} | {:>w1$} | {:<w2$} |", row[0], row[1], row[2], w0 = widths[0], w1 = widths[1], w2 = widths[2])
+        };
+
+        let mut lines = vec![
+            render(&header),
+            format!("| {} | {}: | {} |", "-".repeat(widths[0]), "-".repeat(widths[1] - 1), "-".repeat(widths[2])),
+        ];
+        lines.extend(rows.iter().map(render));
+        lines.join("\n")
+    }
+
+    /// Label, value and unit of every quantity known about the shot, for tabular formats.
+    /// Primary parameters come first, optional estimates only if made.
+    fn rows(&self) -> Vec<(String, f64, &'static str)> {
+        let imperial = self.units == Units::IMPERIAL;
+        let pick = |metric: &'static str, imperial_unit: &'static str| if imperial { imperial_unit } else { metric };
+
+        let mut rows: Vec<(String, f64, &'static str)> = self
+            .summary()
+            .into_iter()
+            .map(|(quantity, value, derived)| {
+                let label = if derived { format!("{} (derived)", quantity) } else { quantity.to_string() };
+                (label, value, quantity.unit(self.units))
+            })
+            .collect();
+
+        let optional = [
+            ("retained speed", self.retained_speed, Quantity::SPEED.unit(self.units)),
+            ("retained energy", self.retained_energy, Quantity::ENERGY.unit(self.units)),
+            ("energy retained", self.retained_fraction.map(|fraction| fraction * 100.0), "%"),
+            ("time of flight", self.time_of_flight, "s"),
+            ("drop", self.drop, pick("cm", "in")),
+            ("drop", self.drop_moa, "MOA"),
+            ("drop", self.drop_mil, "mil"),
+            ("wind drift", self.wind_drift, pick("cm", "in")),
+            ("estimated bc", self.estimated_bc, "lb/in²"),
+            ("sectional density", self.sectional_density, pick("g/mm²", "lb/in²")),
+            ("penetration index", self.penetration_index, ""),
+            ("pressure proxy", self.pressure_proxy, pick("MPa", "psi")),
+            ("estimated charge", self.estimated_charge, Quantity::MASS.unit(self.units)),
+            ("recoil velocity", self.recoil_velocity, Quantity::SPEED.unit(self.units)),
+            ("recoil energy", self.recoil_energy, Quantity::ENERGY.unit(self.units)),
+            ("stability", self.stability, ""),
+        ];
+        rows.extend(optional.into_iter().filter_map(|(label, value, unit)| value.map(|value| (label.to_owned(), value, unit))));
+
+        rows
+    }
+
+    /// Restores shot parameters from a record produced by `Params::to_bytes`.
+    /// Fails if the record is malformed.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Params, MuzzleError> {
+        if bytes.len() != PACKED_LEN {
+            return Err(MuzzleError::INVALID(format!(
+                "Packed parameters must be exactly {} bytes long, got {}!", PACKED_LEN, bytes.len()
+            )));
+        }
+
+        let read_f64 = |offset: usize| {
+            let mut buf = [0u8; 8];
+            buf.copy_from_slice(&bytes[offset..offset + 8]);
+            f64::from_le_bytes(buf)
+        };
+
+        let units = match bytes[24] {
+            0 => Units::METRIC,
+            1 => Units::IMPERIAL,
+            other => return Err(MuzzleError::INVALID(format!("Unknown units byte `{}` in packed parameters!", other))),
+        };
+
+        let bogus = match bytes[25] {
+            0 => false,
+            1 => true,
+            other => return Err(MuzzleError::INVALID(format!("Unknown bogus flag byte `{}` in packed parameters!", other))),
+        };
+
+        // Which quantity was derived is not packed, only whether any was
+        let mut params = Params::new(units, read_f64(0), read_f64(8), read_f64(16), None);
+        params.bogus = bogus;
+        params.overdetermined = params.overdetermined.filter(|_| bogus);
+        Ok(params)
+    }
+}
+
+/// Iterates over label, value and unit of every quantity known about the shot, e.g.
+/// `for (label, value, unit) in &params`. Primary parameters come first in `summary` order,
+/// the derived one labelled as such, followed by whichever optional estimates were made.
+/// Energy is always in joules or foot-pounds.
+impl IntoIterator for &Params {
+    type Item = (String, f64, &'static str);
+    type IntoIter = std::vec::IntoIter<(String, f64, &'static str)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.rows().into_iter()
+    }
 }
 
 /// Performs calculations based on given input config.
-/// Returns either shot parameters struct, or an error string.
-pub fn run(config: Config) -> Result<Params, String> {
+/// Returns either shot parameters struct, or an error describing what is wrong with the input.
+///
+/// Never panics, whatever the input strings hold, and every number in returned parameters is
+/// finite: results that would not be come back as `MuzzleError::DOMAIN` instead. The `run` fuzz
+/// target (`cargo +nightly fuzz run run`) checks this.
+///
+/// Warnings about input that is used regardless are dropped, see `run_with_diagnostics` to get
+/// them or `calculate` to get them along with everything else.
+pub fn run(config: Config) -> Result<Params, MuzzleError> {
+    run_with_diagnostics(config, &mut Vec::new())
+}
+
+/// Everything about a single calculation, as returned by `calculate`: what went in, what came
+/// out and what was said about it. The one object a frontend or logger needs to keep.
+pub struct Calculation {
+    /// Config the calculation was made from, as given
+    pub config: Config,
+    /// Resulting shot parameters
+    pub params: Params,
+    /// Quantity that was computed from the other two, `None` when all three were given
+    pub derived: Option<Quantity>,
+    /// Warnings about input that was used regardless, in the order they were made
+    pub warnings: Vec<String>,
+}
+
+/// Performs calculations like `run` does, keeping the config, the derived quantity and any
+/// warnings along with the resulting parameters
+pub fn calculate(config: Config) -> Result<Calculation, MuzzleError> {
+    let mut warnings = Vec::new();
+    let params = run_with_diagnostics(config.clone(), &mut warnings)?;
+
+    Ok(Calculation { config, derived: params.derived, params, warnings })
+}
+
+/// Parses and converts temperature and the primary parameters given in `config`, the part of
+/// `run` that comes before anything is derived. Primary parameters come in `Quantity::ALL` order,
+/// energy converted to joules (FPE for imperial), mass to grams (grains) and suffixed speeds to
+/// m/s (FPS) at that temperature.
+fn parse_primary(config: &Config) -> Result<(f64, [Option<f64>; 3]), MuzzleError> {
     let units = config.units;
 
-    match (get_float(config.mass)?, get_float(config.speed)?, get_float(config.energy)?) {
+    if units == Units::IMPERIAL && config.energy_unit != EnergyUnit::JOULES {
+        return Err(MuzzleError::INVALID("Imperial energy is always in FPE, other energy units are metric only.".to_owned()));
+    }
+
+    let temperature = match get_float(config.temperature.clone())? {
+        Some(t) => units.convert_temperature(t, Units::METRIC),
+        None => STANDARD_TEMPERATURE,
+    };
+    let above_absolute_zero = temperature > -273.15;
+    if !above_absolute_zero {
+        return Err(MuzzleError::INVALID("Incorrect temperature. It must be above absolute zero.".to_owned()));
+    }
+
+    let energy = get_float(config.energy.clone())?.map(|e| config.energy_unit.to_joules(e));
+    let speed = get_speed(config.speed.clone(), &temperature, &units)?;
+    let mass = get_mass(config.mass.clone(), config.mass_in_grains, &units)?;
+
+    Ok((temperature, [mass, speed, energy]))
+}
+
+/// Performs calculations like `run` does, handing warnings about implausible input that is used
+/// regardless to `diagnostics`. E.g. speeds no firearm reaches, likely FPS given as metric.
+pub fn run_with_diagnostics(config: Config, diagnostics: &mut dyn Diagnostics) -> Result<Params, MuzzleError> {
+    let units = config.units;
+    let (temperature, [mass, speed, energy]) = parse_primary(&config)?;
+
+    let momentum = get_float(config.momentum)?;
+    if momentum.is_some() && (speed.is_some() || mass.is_some() == energy.is_some()) {
+        return Err(MuzzleError::INVALID(
+            "Incorrect momentum parameters. Momentum combines with energy alone, to derive speed and mass from, or with mass alone, to derive speed from.".to_owned()
+        ));
+    }
+    let power_factor = get_float(config.power_factor)?;
+    if power_factor.is_some() && (mass.is_some() || speed.is_some() || energy.is_none() || momentum.is_some()) {
+        return Err(MuzzleError::INVALID(
+            "Incorrect power factor parameters. Target power factor combines with energy alone, to solve for mass and speed.".to_owned()
+        ));
+    }
+
+    let mut params = match (mass, speed, energy) {
         // Mass and speed given. Derive energy.
         (Some(m), Some(s), None) => {
-            let derived_energy = derive_energy(&m, &s, &units);
-            Ok(Params { units, mass: m, speed: s, energy: derived_energy, bogus: false })
+            let derived_energy = derive_energy(&m, &s, units.system());
+            Params::new(units, m, s, derived_energy, Some(Quantity::ENERGY))
         },
         // Mass and energy given. Derive speed.
         (Some(m), None, Some(e)) => {
-            let derived_speed = derive_speed(&m, &e, &units);
-            Ok(Params { units, mass: m, speed: derived_speed, energy: e, bogus: false })
+            let derived_speed = derive_speed(&m, &e, units.system())?;
+            Params::new(units, m, derived_speed, e, Some(Quantity::SPEED))
         },
         // Speed and energy given. Derive mass.
         (None, Some(s), Some(e)) => {
-            let derived_mass = derive_mass(&s, &e, &units);
-            Ok(Params { units, mass: derived_mass, speed: s, energy: e, bogus: false })
+            let derived_mass = derive_mass(&s, &e, units.system());
+            Params::new(units, derived_mass, s, e, Some(Quantity::MASS))
+        },
+        // Momentum and energy given. Derive speed, then mass from speed and energy.
+        // Both are derived, speed is reported as the one as mass follows from it.
+        (None, None, Some(e)) if momentum.is_some() => match momentum {
+            Some(p) if p > 0.0 && e > 0.0 => {
+                let derived_speed = derive_speed_from_momentum(&p, &e, units.system());
+                Params::new(units, derive_mass(&derived_speed, &e, units.system()), derived_speed, e, Some(Quantity::SPEED))
+            },
+            _ => return Err(MuzzleError::INVALID("Incorrect momentum parameters. Both energy and momentum must be positive.".to_owned())),
+        },
+        // Momentum and mass given, e.g. of a load to match. Derive speed, then energy.
+        (Some(m), None, None) if momentum.is_some() => match momentum {
+            Some(p) if p > 0.0 && m > 0.0 => {
+                let derived_speed = derive_speed_from_mass_and_momentum(&m, &p, units.system());
+                Params::new(units, m, derived_speed, derive_energy(&m, &derived_speed, units.system()), Some(Quantity::SPEED))
+            },
+            _ => return Err(MuzzleError::INVALID("Incorrect momentum parameters. Both momentum and mass must be positive.".to_owned())),
+        },
+        // Energy and power factor given. One load makes both, derive its speed, then mass as above.
+        (None, None, Some(e)) if power_factor.is_some() => match power_factor {
+            Some(pf) if pf > 0.0 && e > 0.0 => {
+                let derived_speed = derive_speed_from_power_factor(&pf, &e, &units);
+                Params::new(units, derive_mass(&derived_speed, &e, units.system()), derived_speed, e, Some(Quantity::SPEED))
+            },
+            _ => return Err(MuzzleError::INVALID(
+                "Incorrect power factor parameters. Both target energy and power factor must be positive for a load to make them.".to_owned()
+            )),
         },
         // All parameters passed. Nothing to derive.
-        (Some(m), Some(s), Some(e)) => Ok(Params { units, mass: m, speed: s, energy: e, bogus: true }),
+        (Some(m), Some(s), Some(e)) => Params::new(units, m, s, e, None),
+        // Nothing passed at all. Likely a new user.
+        (None, None, None) => return Err(MuzzleError::EMPTY),
         // Everything else is an error.
-        _ => Err(
-            "Incorrect parameters set. At least two out of three parameters must be given to derive the third.
-Please check input.".to_owned()
-        ),
+        _ => return Err(MuzzleError::INSUFFICIENT),
+    };
+
+    let speed_si = match units {
+        Units::METRIC => params.speed,
+        Units::IMPERIAL => params.speed * METERS_IN_FOOT,
+    };
+    if speed_si > MAX_PLAUSIBLE_SPEED {
+        let (limit, unit) = match units {
+            Units::METRIC => (MAX_PLAUSIBLE_SPEED, "m/s"),
+            Units::IMPERIAL => (MAX_PLAUSIBLE_SPEED / METERS_IN_FOOT, "FPS"),
+        };
+        diagnostics.warning(format!(
+            "Speed of {:.0} {} is beyond any firearm (about {:.0} {} at most). Check the units it is given in.",
+            params.speed, unit, limit, unit
+        ));
+    }
+
+    let wind = match (get_float(config.wind_speed)?, get_float(config.wind_angle)?) {
+        (None, None) => None,
+        (Some(speed), angle) if speed >= 0.0 => Some((speed, angle.unwrap_or(90.0))),
+        (Some(_), _) => return Err(MuzzleError::INVALID("Incorrect wind speed. It must not be negative.".to_owned())),
+        (None, Some(_)) => return Err(MuzzleError::INVALID("Incorrect wind parameters. Wind speed must be given along with its angle.".to_owned())),
+    };
+
+    match (get_float(config.bc)?, get_float(config.distance)?) {
+        // Downrange estimates are only made when asked for
+        (None, None) if wind.is_some() => return Err(MuzzleError::INVALID(
+            "Incorrect wind parameters. Both ballistic coefficient and distance must be given to estimate drift.".to_owned()
+        )),
+        (None, None) => {},
+        (Some(bc), Some(distance)) if bc > 0.0 && distance >= 0.0 => {
+            let (retained_speed, time_of_flight) = derive_flight(&config.drag_model, &params.speed, &bc, &distance, units.system());
+            let retained_energy = derive_energy(&params.mass, &retained_speed, units.system());
+
+            params.retained_speed = Some(retained_speed);
+            params.retained_energy = Some(retained_energy);
+            params.retained_fraction = Some(retained_energy / params.energy);
+            // Projectile stopped by drag never gets there
+            params.time_of_flight = Some(time_of_flight).filter(|time| time.is_finite());
+            if let Some(time) = params.time_of_flight.filter(|_| distance > 0.0) {
+                let (drop, moa, mil) = derive_drop(&time, &distance, &units);
+                params.drop = Some(drop);
+                params.drop_moa = Some(moa);
+                params.drop_mil = Some(mil);
+                params.wind_drift = wind.map(|(wind_speed, angle)| derive_wind_drift(&time, &distance, &params.speed, &wind_speed, &angle, &units));
+            }
+        },
+        (Some(_), Some(_)) => return Err(MuzzleError::INVALID(
+            "Incorrect downrange parameters. Ballistic coefficient must be positive and distance must not be negative.".to_owned()
+        )),
+        _ => return Err(MuzzleError::INVALID(
+            "Incorrect downrange parameters. Both ballistic coefficient and distance must be given to estimate them.".to_owned()
+        )),
+    }
+
+    let max_iterations = match given(config.max_iterations).map(|st| st.trim().parse::<usize>()) {
+        None => BC_ITERATIONS,
+        Some(Ok(count)) if count > 0 => count,
+        Some(_) => return Err(MuzzleError::INVALID("Incorrect maximum iterations. It must be a positive whole number.".to_owned())),
+    };
+    match (get_float(config.v1)?, get_float(config.d1)?, get_float(config.v2)?, get_float(config.d2)?) {
+        // BC is only estimated when asked for
+        (None, None, None, None) => {},
+        (Some(v1), Some(d1), Some(v2), Some(d2)) if v1 > v2 && v2 > 0.0 && d2 > d1 && d1 >= 0.0 => {
+            let estimate = derive_estimated_bc(&config.drag_model, &v1, &v2, &(d2 - d1), max_iterations, units.system());
+            match estimate {
+                Some(estimate) if estimate.converged => {
+                    params.estimated_bc = Some(estimate.bc);
+                    params.estimated_bc_iterations = Some(estimate.iterations);
+                    params.estimated_bc_residual = Some(estimate.residual / units.system().meters_in_length());
+                },
+                Some(estimate) => return Err(MuzzleError::INVALID(format!(
+                    "Incorrect chronograph readings. Ballistic coefficient estimate did not converge within {} iterations.", estimate.iterations
+                ))),
+                None => return Err(MuzzleError::INVALID(
+                    "Incorrect chronograph readings. No realistic ballistic coefficient explains such a speed loss.".to_owned()
+                )),
+            }
+        },
+        (Some(_), Some(_), Some(_), Some(_)) => return Err(MuzzleError::INVALID(
+            "Incorrect chronograph readings. Far speed must be positive and below near speed and far distance must exceed near distance.".to_owned()
+        )),
+        _ => return Err(MuzzleError::INVALID(
+            "Incorrect chronograph readings. Speeds and distances of both chronographs must be given to estimate BC.".to_owned()
+        )),
+    }
+
+    let diameter = get_float(config.diameter)?;
+    match (diameter, get_float(config.bore_length)?) {
+        (Some(diameter), _) if diameter <= 0.0 => return Err(MuzzleError::INVALID("Incorrect diameter. It must be positive.".to_owned())),
+        (_, Some(length)) if length <= 0.0 => return Err(MuzzleError::INVALID("Incorrect bore length. It must be positive.".to_owned())),
+        (Some(diameter), bore_length) => {
+            let sectional_density = derive_sectional_density(&params.mass, &diameter, &units);
+            params.sectional_density = Some(sectional_density);
+            let impact_speed = params.retained_speed.unwrap_or(params.speed);
+            params.penetration_index = Some(derive_penetration_index(&sectional_density, &impact_speed, &units));
+            params.pressure_proxy = bore_length.map(|length| derive_pressure_proxy(&params.energy, &diameter, &length, &units));
+        },
+        (None, Some(_)) => return Err(MuzzleError::INVALID("Incorrect bore length. Diameter must be given as well to get bore volume.".to_owned())),
+        (None, None) => {},
+    }
+
+    match (get_float(config.ref_charge)?, get_float(config.ref_velocity)?, get_float(config.target_velocity)?) {
+        // Charge is only estimated when asked for
+        (None, None, None) => {},
+        (Some(charge), Some(reference), Some(target)) if charge > 0.0 && reference > 0.0 && target > 0.0 => {
+            params.estimated_charge = Some(derive_estimated_charge(&charge, &reference, &target));
+            if target > reference {
+                diagnostics.warning(
+                    "Target velocity is above the reference load, the estimated charge goes past known data. Check it against published maximum loads.".to_owned()
+                );
+            }
+        },
+        (Some(_), Some(_), Some(_)) => return Err(MuzzleError::INVALID(
+            "Incorrect charge estimation parameters. Reference charge and both velocities must be positive.".to_owned()
+        )),
+        _ => return Err(MuzzleError::INVALID(
+            "Incorrect charge estimation parameters. Reference charge, reference velocity and target velocity must all be given.".to_owned()
+        )),
+    }
+
+    match get_float(config.gun_mass)? {
+        Some(gun_mass) if gun_mass > 0.0 => {
+            let recoil_velocity = derive_recoil_velocity(&params.mass, &params.speed, &gun_mass, units.system());
+            params.recoil_velocity = Some(recoil_velocity);
+            params.recoil_energy = Some(derive_recoil_energy(&gun_mass, &recoil_velocity, units.system()));
+        },
+        Some(_) => return Err(MuzzleError::INVALID("Incorrect firearm mass. It must be positive.".to_owned())),
+        None => {},
+    }
+
+    match (get_float(config.length)?, get_float(config.twist)?, diameter) {
+        // Stability is only estimated when asked for
+        (None, None, _) => {},
+        (Some(length), Some(twist), Some(diameter)) if length > 0.0 && twist > 0.0 => {
+            params.stability = Some(derive_stability(&params.mass, &params.speed, &diameter, &length, &twist, &temperature, &units));
+        },
+        (Some(_), Some(_), Some(_)) => return Err(MuzzleError::INVALID(
+            "Incorrect stability parameters. Projectile length and twist rate must be positive.".to_owned()
+        )),
+        _ => return Err(MuzzleError::INVALID(
+            "Incorrect stability parameters. Projectile length, twist rate and diameter must all be given.".to_owned()
+        )),
+    }
+
+    // Squares of huge but finite inputs overflow silently, refuse to hand out infinities
+    let results = [
+        (Quantity::MASS.name(), Some(params.mass)),
+        (Quantity::SPEED.name(), Some(params.speed)),
+        (Quantity::ENERGY.name(), Some(params.energy)),
+        ("retained speed", params.retained_speed),
+        ("retained energy", params.retained_energy),
+        ("retained energy fraction", params.retained_fraction),
+        ("time of flight", params.time_of_flight),
+        ("drop", params.drop),
+        ("drop angle", params.drop_moa),
+        ("drop angle", params.drop_mil),
+        ("wind drift", params.wind_drift),
+        ("sectional density", params.sectional_density),
+        ("penetration index", params.penetration_index),
+        ("pressure proxy", params.pressure_proxy),
+        ("estimated charge", params.estimated_charge),
+        ("recoil velocity", params.recoil_velocity),
+        ("recoil energy", params.recoil_energy),
+        ("stability", params.stability),
+    ];
+    if let Some((name, _)) = results.iter().find(|(_, value)| value.is_some_and(|value| !value.is_finite())) {
+        return Err(MuzzleError::DOMAIN(name.to_string()));
+    }
+
+    Ok(params)
+}
+
+/// Samples speed and energy of the shot along its way, for plotting how they decay.
+/// Returns `samples` evenly spaced `(distance, speed, energy)` points, the first at the muzzle and
+/// the last at `distance` (in meters or yards depending on measurment system chosen). Each point
+/// is a downrange estimate on its own, made with the same drag model as `run` uses.
+pub fn velocity_decay(params: &Params, model: DragModel, bc: f64, distance: f64, samples: usize) -> Result<Vec<(f64, f64, f64)>, MuzzleError> {
+    if samples < 2 {
+        return Err(MuzzleError::INVALID("Incorrect sample count. At least two samples are needed to span muzzle to target.".to_owned()));
+    }
+    let valid_range = bc > 0.0 && distance >= 0.0 && distance.is_finite();
+    if !valid_range {
+        return Err(MuzzleError::INVALID(
+            "Incorrect downrange parameters. Ballistic coefficient must be positive and distance must not be negative.".to_owned()
+        ));
+    }
+
+    Ok((0..samples).map(|i| downrange_point(params, model, bc, distance * i as f64 / (samples - 1) as f64)).collect())
+}
+
+/// Distances of the classic energy drop chart, in yards or meters depending on measurment system
+pub const CHART_DISTANCES: [f64; 5] = [0.0, 50.0, 100.0, 200.0, 300.0];
+
+/// Tabulates speed and energy of the shot at each of `distances` (in meters or yards depending on
+/// measurment system chosen) as `(distance, speed, energy)` rows, the ballistics table hunters
+/// know, e.g. at `CHART_DISTANCES`. Rows are estimated the way `velocity_decay` samples are.
+pub fn energy_chart(params: &Params, model: DragModel, bc: f64, distances: &[f64]) -> Result<Vec<(f64, f64, f64)>, MuzzleError> {
+    let valid_range = bc > 0.0 && distances.iter().all(|distance| *distance >= 0.0 && distance.is_finite());
+    if !valid_range {
+        return Err(MuzzleError::INVALID(
+            "Incorrect downrange parameters. Ballistic coefficient must be positive and distance must not be negative.".to_owned()
+        ));
+    }
+
+    Ok(distances.iter().map(|&distance| downrange_point(params, model, bc, distance)).collect())
+}
+
+/// Estimated `(distance, speed, energy)` of the shot `at` given distance
+fn downrange_point(params: &Params, model: DragModel, bc: f64, at: f64) -> (f64, f64, f64) {
+    let speed = derive_retained_speed(&model, &params.speed, &bc, &at, params.units.system());
+    (at, speed, derive_energy(&params.mass, &speed, params.units.system()))
+}
+
+/// Performs calculations like `run`, returning core parameters in SI units whatever units the
+/// config is given in. See `Params::to_si` for the conversions.
+pub fn run_si(config: Config) -> Result<ParamsSi, MuzzleError> {
+    run(config).map(|params| params.to_si())
+}
+
+/// Runs `base` config over a range of values of one quantity, e.g. masses at a fixed energy.
+/// `vary` takes values from `from` to `to` (inclusive) in increments of `step`, replacing whatever
+/// `base` held for it, and each is run like `run` does. An incorrect range, or one of more than
+/// `MAX_SWEEP_ROWS` values, yields a single error.
+pub fn sweep(base: Config, vary: Quantity, from: f64, to: f64, step: f64) -> impl Iterator<Item = Result<Params, MuzzleError>> {
+    let valid_range = from.is_finite() && to >= from && to.is_finite() && step > 0.0;
+    let rows = if valid_range {
+        sweep_rows(from, to, step)
+    } else {
+        Err(MuzzleError::INVALID("Incorrect sweep range. Expecting finite start, end not smaller than start and positive step.".to_owned()))
+    };
+    let (count, error) = match rows {
+        Ok(count) => (count, None),
+        Err(error) => (0, Some(Err(error))),
+    };
+
+    // Stepping by multiplication avoids accumulating floating point error over the range
+    error.into_iter().chain((0..count).map(move |i| {
+        let value = Some(format_machine(from + i as f64 * step));
+        let mut config = base.clone();
+        match vary {
+            Quantity::MASS => config.mass = value,
+            Quantity::SPEED => config.speed = value,
+            Quantity::ENERGY => config.energy = value,
+        }
+        run(config)
+    }))
+}
+
+/// Tabulates loads sharing the same `energy` across a range of projectile masses.
+/// Masses go from `from` to `to` (inclusive) in increments of `step`, speed is derived for each.
+pub fn energy_sweep(units: Units, energy: f64, from: f64, to: f64, step: f64) -> Result<Vec<Params>, MuzzleError> {
+    energy_sweep_iter(units, energy, from, to, step)?.collect()
+}
+
+/// Yields the loads `energy_sweep` tabulates one at a time, each calculated as the iterator gets
+/// to it, e.g. to report progress over a long table. An incorrect mass range is an error up front.
+pub fn energy_sweep_iter(units: Units, energy: f64, from: f64, to: f64, step: f64) -> Result<impl Iterator<Item = Result<Params, MuzzleError>>, MuzzleError> {
+    let valid_range = from > 0.0 && to >= from && to.is_finite() && step > 0.0;
+    if !valid_range {
+        return Err(MuzzleError::INVALID(
+            "Incorrect mass range. Expecting positive start, end not smaller than start and positive step.".to_owned()
+        ));
+    }
+    sweep_rows(from, to, step)?;
+
+    let base = Config { units, energy: Some(format_machine(energy)), ..Config::default() };
+    Ok(sweep(base, Quantity::MASS, from, to, step))
+}
+
+/// Number of values from `from` to `to` (inclusive) in increments of `step` of a valid range,
+/// `MuzzleError::INVALID` if there are more than `MAX_SWEEP_ROWS`
+fn sweep_rows(from: f64, to: f64, step: f64) -> Result<usize, MuzzleError> {
+    let steps = ((to - from) / step + 1e-9).floor();
+    if steps >= MAX_SWEEP_ROWS as f64 {
+        return Err(MuzzleError::INVALID(format!(
+            "Incorrect sweep range. It spans more than {} rows, a larger step is needed.", MAX_SWEEP_ROWS
+        )));
+    }
+    Ok(steps as usize + 1)
+}
+
+/// Verdict of `check_units` on the magnitude of a single input value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Plausibility {
+    /// Within what real projectiles have
+    PLAUSIBLE,
+    /// Smaller than any real projectile has
+    LOW,
+    /// Larger than any real projectile has
+    HIGH,
+    /// Out of range, but within it if read in the other measurment system: likely given in the
+    /// wrong units, e.g. grains with metric
+    MISMATCHED,
+}
+
+impl fmt::Display for Plausibility {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Plausibility::PLAUSIBLE => write!(f, "plausible"),
+            Plausibility::LOW => write!(f, "too low"),
+            Plausibility::HIGH => write!(f, "too high"),
+            Plausibility::MISMATCHED => write!(f, "likely in the other measurment system"),
+        }
+    }
+}
+
+/// Magnitude check of one input value, as made by `check_units`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct UnitCheck {
+    /// Quantity the value was given for
+    pub quantity: Quantity,
+    /// Value as given (energy in joules or FPE depending on measurment system chosen)
+    pub value: f64,
+    /// How plausible the value is for the measurment system it was given in
+    pub verdict: Plausibility,
+}
+
+/// Range (in grams, m/s and joules) of values real projectiles have, airsoft BBs to .50 BMG
+fn plausible_range(quantity: Quantity) -> (f64, f64) {
+    match quantity {
+        Quantity::MASS => (0.1, 60.0),
+        Quantity::SPEED => (20.0, MAX_PLAUSIBLE_SPEED),
+        Quantity::ENERGY => (0.5, 25_000.0),
+    }
+}
+
+/// Checks magnitudes of the mass, speed and energy given in `config` against the measurment
+/// system they are declared in, without calculating anything. Meant to catch unit mistakes,
+/// such as a 150 grain bullet entered as metric, before they turn into a confusing result.
+///
+/// Returns a verdict for each of those given, in `Quantity::ALL` order. Values that do not parse
+/// are left out, `run` reports them. Ranges are deliberately wide, so a plausible verdict only
+/// means the value is not absurd.
+pub fn check_units(config: &Config) -> Vec<UnitCheck> {
+    let units = config.units;
+    let mass = get_mass(config.mass.clone(), config.mass_in_grains, &units).ok().flatten();
+    let speed = get_speed(config.speed.clone(), &STANDARD_TEMPERATURE, &units).ok().flatten();
+    let energy = get_float(config.energy.clone()).ok().flatten().map(|e| config.energy_unit.to_joules(e));
+
+    // Values in grams, m/s and joules as given and as if given in imperial or metric respectively
+    let to_si = |quantity: Quantity, value: f64, units: Units| match (quantity, units) {
+        (_, Units::METRIC) => value,
+        (Quantity::MASS, Units::IMPERIAL) => value / GRAINS_IN_GRAM,
+        (Quantity::SPEED, Units::IMPERIAL) => value * METERS_IN_FOOT,
+        (Quantity::ENERGY, Units::IMPERIAL) => value * JOULES_IN_FPE,
+    };
+
+    Quantity::ALL
+        .into_iter()
+        .zip([mass, speed, energy])
+        .filter_map(|(quantity, value)| {
+            let value = value?;
+            let (low, high) = plausible_range(quantity);
+            let within = |si: f64| (low..=high).contains(&si);
+            let verdict = match to_si(quantity, value, units) {
+                si if within(si) => Plausibility::PLAUSIBLE,
+                _ if within(to_si(quantity, value, units.other())) => Plausibility::MISMATCHED,
+                si if si < low => Plausibility::LOW,
+                _ => Plausibility::HIGH,
+            };
+            Some(UnitCheck { quantity, value, verdict })
+        })
+        .collect()
+}
+
+/// Parses, validates and converts the mass, speed and energy given in `config` the way `run` does,
+/// without deriving anything: any number of them may be given, one alone included. Returns those
+/// given in `Quantity::ALL` order, in grams, m/s and joules or grains, FPS and FPE depending on
+/// measurment system chosen, ready to be handed to `solve`.
+///
+/// Every value has to be positive. Giving none at all is `MuzzleError::EMPTY`, the rest of the
+/// config is not looked at.
+pub fn normalize(config: &Config) -> Result<Vec<(Quantity, f64)>, MuzzleError> {
+    let (_, primary) = parse_primary(config)?;
+    let given: Vec<_> = Quantity::ALL.into_iter().zip(primary).filter_map(|(quantity, value)| Some((quantity, value?))).collect();
+
+    if given.is_empty() {
+        return Err(MuzzleError::EMPTY);
+    }
+    if let Some((quantity, _)) = given.iter().find(|(_, value)| *value <= 0.0) {
+        return Err(MuzzleError::INVALID(format!("Incorrect {}. It must be positive.", quantity.name())));
+    }
+
+    Ok(given)
+}
+
+/// Solves for `target` given the other two primary quantities in `known`, in any order. Energy is
+/// in Joules or FPE depending on `units`. `known` has to hold exactly those two, each once:
+/// fewer is `MuzzleError::INSUFFICIENT`, giving the target itself or a repeated quantity is invalid.
+pub fn solve(target: Quantity, known: &[(Quantity, f64)], units: Units) -> Result<f64, MuzzleError> {
+    let lookup = |quantity: Quantity| known.iter().find(|(given, _)| *given == quantity).map(|&(_, value)| value);
+
+    if lookup(target).is_some() {
+        return Err(MuzzleError::INVALID(format!("Cannot solve for {} as it is already known.", target.name())));
+    }
+    for (i, (quantity, _)) in known.iter().enumerate() {
+        if known[i + 1..].iter().any(|(other, _)| other == quantity) {
+            return Err(MuzzleError::INVALID(format!("Known {} is given more than once.", quantity.name())));
+        }
+    }
+
+    match (target, lookup(Quantity::MASS), lookup(Quantity::SPEED), lookup(Quantity::ENERGY)) {
+        (Quantity::MASS, _, Some(s), Some(e)) => Ok(derive_mass(&s, &e, units.system())),
+        (Quantity::SPEED, Some(m), _, Some(e)) => derive_speed(&m, &e, units.system()),
+        (Quantity::ENERGY, Some(m), Some(s), _) => Ok(derive_energy(&m, &s, units.system())),
+        _ => Err(MuzzleError::INSUFFICIENT),
     }
 }
 
-/// Derives mass from given `speed` and `energy` using set units of measurment
-fn derive_mass(speed: &f64, energy: &f64, units: &Units) -> f64 {
+/// Derives mass from given `speed` and `energy` in given measurment system
+fn derive_mass(speed: &f64, energy: &f64, system: &dyn UnitSystem) -> f64 {
+    ((2.0 * system.force_scale() * energy) / speed.powi(2)) * system.mass_scale()
+}
+
+/// Derives speed from given `mass` and `energy` in given measurment system
+fn derive_speed(mass: &f64, energy: &f64, system: &dyn UnitSystem) -> Result<f64, MuzzleError> {
+    let speed_squared = (2.0 * system.force_scale() * energy) / (mass / system.mass_scale());
+
+    // Mass and energy of opposite signs have no real root, report that instead of passing NaN on
+    if speed_squared < 0.0 {
+        return Err(MuzzleError::DOMAIN(Quantity::SPEED.name().to_owned()));
+    }
+
+    Ok(speed_squared.sqrt())
+}
+
+/// Derives energy from given `mass` and `speed` in given measurment system
+fn derive_energy(mass: &f64, speed: &f64, system: &dyn UnitSystem) -> f64 {
+    ((mass / system.mass_scale()) * speed.powi(2)) / (2.0 * system.force_scale())
+}
+
+/// Derives speed from given `momentum` and `energy` in given measurment system, without knowing mass
+fn derive_speed_from_momentum(momentum: &f64, energy: &f64, system: &dyn UnitSystem) -> f64 {
+    2.0 * system.force_scale() * energy / momentum
+}
+
+/// Derives speed from given `mass` and `momentum` in given measurment system, inverting `Params::momentum`
+fn derive_speed_from_mass_and_momentum(mass: &f64, momentum: &f64, system: &dyn UnitSystem) -> f64 {
+    momentum / (mass / system.mass_scale())
+}
+
+/// Derives speed of the one load that has both given `power_factor` and `energy` using set units of measurment.
+/// Power factor is grains × FPS / 1000 regardless, so metric energy goes through FPE.
+fn derive_speed_from_power_factor(power_factor: &f64, energy: &f64, units: &Units) -> f64 {
     match units {
-        Units::METRIC => ((2.0 * energy) / speed.powi(2)) * GRAMS_IN_KILO,
-        Units::IMPERIAL => ((2.0 * GEE_FPS * energy) / speed.powi(2)) * GRAINS_IN_POUND,
+        Units::METRIC => 14.0 * GEE_FPS * (energy / JOULES_IN_FPE) / power_factor * METERS_IN_FOOT,
+        Units::IMPERIAL => 14.0 * GEE_FPS * energy / power_factor,
     }
 }
 
-/// Derives mass from given `speed` and `energy` using set units of measurment
-fn derive_speed(mass: &f64, energy: &f64, units: &Units) -> f64 {
-    let speed_squared = match units {
-        Units::METRIC => (2.0 * energy) / (mass / GRAMS_IN_KILO),
-        Units::IMPERIAL => (2.0 * GEE_FPS * energy) / (mass / GRAINS_IN_POUND),
+/// Derives drop (cm or inches), in MOA and in mils, after free fall over `time` (s) at `distance`
+/// (meters or yards) using set units of measurment
+fn derive_drop(time: &f64, distance: &f64, units: &Units) -> (f64, f64, f64) {
+    let (drop, distance) = match units {
+        Units::METRIC => (GEE_FPS * METERS_IN_FOOT * time.powi(2) / 2.0, *distance),
+        Units::IMPERIAL => (GEE_FPS * time.powi(2) / 2.0 * METERS_IN_FOOT, distance * METERS_IN_YARD),
+    };
+    let angle = (drop / distance).atan();
+    let display = match units {
+        Units::METRIC => drop * 100.0,
+        Units::IMPERIAL => drop / METERS_IN_FOOT * 12.0,
     };
 
-    speed_squared.powf(0.5)
+    (display, angle.to_degrees() * 60.0, angle * 1000.0)
 }
 
-/// Derives mass from given `speed` and `energy` using set units of measurment
-fn derive_energy(mass: &f64, speed: &f64, units: &Units) -> f64 {
+/// Derives wind drift at `distance` reached in `time` by a projectile leaving the muzzle at
+/// `speed`, in a wind of `wind_speed` blowing from `angle` degrees, using set units of measurment.
+/// Returns centimeters for metric and inches for imperial.
+fn derive_wind_drift(time: &f64, distance: &f64, speed: &f64, wind_speed: &f64, angle: &f64, units: &Units) -> f64 {
+    let crosswind = wind_speed * angle.to_radians().sin();
     match units {
-        Units::METRIC => ((mass / GRAMS_IN_KILO) * speed.powi(2)) / 2.0,
-        Units::IMPERIAL => ((mass / GRAINS_IN_POUND) * speed.powi(2)) / (2.0 * GEE_FPS),
+        Units::METRIC => crosswind * (time - distance / speed) * 100.0,
+        // Miles per hour to ft/s, yards to feet and feet to inches
+        Units::IMPERIAL => crosswind * 5280.0 / 3600.0 * (time - distance * 3.0 / speed) * 12.0,
     }
 }
 
-/// Tries to parse `f64` out of given option string
-fn get_float(param: Option<String>) -> Result<Option<f64>, String> {
-    match param {
+/// Derives speed left after `distance` from given muzzle `speed` and `bc` using set drag model and measurment system
+fn derive_retained_speed(model: &DragModel, speed: &f64, bc: &f64, distance: &f64, system: &dyn UnitSystem) -> f64 {
+    let meters = system.meters_in_length();
+    drag::retained_speed(*model, speed * meters, *bc, distance * system.meters_in_distance()) / meters
+}
+
+/// Derives speed left after `distance` along with time of flight (s) to it, using set drag model and measurment system
+fn derive_flight(model: &DragModel, speed: &f64, bc: &f64, distance: &f64, system: &dyn UnitSystem) -> (f64, f64) {
+    let meters = system.meters_in_length();
+    let (retained, time) = drag::flight(*model, speed * meters, *bc, distance * system.meters_in_distance());
+    (retained / meters, time)
+}
+
+/// Derives firearm speed from projectile `mass` and `speed` and `gun_mass` (kg or lb) by conservation of momentum in given measurment system
+fn derive_recoil_velocity(mass: &f64, speed: &f64, gun_mass: &f64, system: &dyn UnitSystem) -> f64 {
+    (mass / system.mass_scale()) * speed / gun_mass
+}
+
+/// Derives kinetic energy of the firearm of `gun_mass` (kg or lb) moving at `speed` in given measurment system
+fn derive_recoil_energy(gun_mass: &f64, speed: &f64, system: &dyn UnitSystem) -> f64 {
+    gun_mass * speed.powi(2) / (2.0 * system.force_scale())
+}
+
+/// Derives Miller gyroscopic stability factor of a projectile of given `mass`, `diameter` and
+/// `length` flying at `speed` from a barrel of given `twist` (length of one turn) in air at
+/// `temperature` (°C) using set units of measurment. The rule itself works in grains, inches and FPS.
+fn derive_stability(mass: &f64, speed: &f64, diameter: &f64, length: &f64, twist: &f64, temperature: &f64, units: &Units) -> f64 {
+    let (mass, speed, diameter, length, twist) = match units {
+        Units::METRIC => (mass * GRAINS_IN_GRAM, speed / METERS_IN_FOOT, diameter / 25.4, length / 25.4, twist / 25.4),
+        Units::IMPERIAL => (*mass, *speed, *diameter, *length, *twist),
+    };
+    let (twist, length) = (twist / diameter, length / diameter);
+
+    let stability = 30.0 * mass / (twist.powi(2) * diameter.powi(3) * length * (1.0 + length.powi(2)));
+    // Rule is fitted at 2800 FPS and 59 °F
+    stability * (speed / 2800.0).cbrt() * (Units::METRIC.convert_temperature(*temperature, Units::IMPERIAL) + 460.0) / (59.0 + 460.0)
+}
+
+/// Derives charge for `target` velocity from a reference load of `charge` giving `reference` velocity.
+/// Charge is taken as proportional to velocity, in whatever units they are given.
+fn derive_estimated_charge(charge: &f64, reference: &f64, target: &f64) -> f64 {
+    charge * target / reference
+}
+
+/// Derives sectional density from given `mass` and `diameter` using set units of measurment
+fn derive_sectional_density(mass: &f64, diameter: &f64, units: &Units) -> f64 {
+    match units {
+        Units::METRIC => mass / diameter.powi(2),
+        Units::IMPERIAL => (mass / GRAINS_IN_POUND) / diameter.powi(2),
+    }
+}
+
+/// Derives penetration index from given `sectional_density` and impact `speed` using set units of
+/// measurment. Always computed in lb/in² and FPS, so a load scores the same in either system.
+fn derive_penetration_index(sectional_density: &f64, speed: &f64, units: &Units) -> f64 {
+    let (sectional_density, speed) = match units {
+        Units::METRIC => (sectional_density * GRAINS_IN_GRAM / GRAINS_IN_POUND * 25.4f64.powi(2), speed / METERS_IN_FOOT),
+        Units::IMPERIAL => (*sectional_density, *speed),
+    };
+    sectional_density * speed / 100.0
+}
+
+/// Derives energy per bore volume from given `energy`, `diameter` and bore `length` using set units of measurment.
+/// J/mm³ is 1000 MPa, FPE/in³ is 12 psi.
+fn derive_pressure_proxy(energy: &f64, diameter: &f64, length: &f64, units: &Units) -> f64 {
+    let volume = std::f64::consts::PI * diameter.powi(2) / 4.0 * length;
+    match units {
+        Units::METRIC => energy / volume * 1000.0,
+        Units::IMPERIAL => energy / volume * 12.0,
+    }
+}
+
+/// Derives ballistic coefficient from speed `v1` slowing down to `v2` over `distance` using set drag
+/// model and measurment system, bisecting for at most `max_iterations` steps
+fn derive_estimated_bc(model: &DragModel, v1: &f64, v2: &f64, distance: &f64, max_iterations: usize, system: &dyn UnitSystem) -> Option<drag::BcEstimate> {
+    let meters = system.meters_in_length();
+    drag::estimate_bc(*model, v1 * meters, v2 * meters, distance * system.meters_in_distance(), max_iterations)
+}
+
+/// Rounds `value` to the nearest multiple of `increment` (e.g. to the nearest 5 FPS or 0.1 grain).
+/// Halfway values round away from zero. Non-positive increments leave the value as is.
+pub fn round_to_increment(value: f64, increment: f64) -> f64 {
+    if increment > 0.0 {
+        (value / increment).round() * increment
+    } else {
+        value
+    }
+}
+
+/// Formats `value` for machine readable output, and for handing computed numbers back to `run` as text.
+///
+/// Always the shortest digits that parse back to exactly the same value, with `.` for the decimal
+/// separator and neither digit grouping nor exponent, whatever locale the process runs in or
+/// `Locale` human output uses. Negative zero is written as `0`. JSON numbers are written by
+/// `serde_json`, which is just as locale independent. `FormatPolicy` may switch primary
+/// quantities to scientific notation instead.
+pub fn format_machine(value: f64) -> String {
+    if value == 0.0 {
+        return "0".to_owned();
+    }
+    // Display of `f64` is defined by Rust, the C locale never comes into it
+    value.to_string()
+}
+
+/// Counts significant digits in a number as it was written by the user.
+/// Leading zeros are never significant and neither are trailing zeros of a value written without
+/// a decimal point (e.g. "2800" has two significant digits). Exponent digits are not counted.
+pub fn significant_digits(input: &str) -> usize {
+    let mantissa = input.trim().split(['e', 'E']).next().unwrap_or("");
+    let digits: String = mantissa.chars().filter(|c| c.is_ascii_digit()).collect();
+    let digits = digits.trim_start_matches('0');
+
+    if mantissa.contains('.') {
+        digits.len()
+    } else {
+        digits.trim_end_matches('0').len()
+    }
+}
+
+/// Deserializes an optional input value given either as a JSON number or a string
+fn number_or_string<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    match Option::<serde_json::Value>::deserialize(deserializer)? {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(serde_json::Value::String(st)) => Ok(Some(st)),
+        Some(serde_json::Value::Number(number)) => Ok(Some(number.to_string())),
+        Some(other) => Err(serde::de::Error::custom(format!("expected a number or string, got `{}`", other))),
+    }
+}
+
+/// Tries to parse speed out of given option string. Besides plain numbers accepts Mach numbers
+/// suffixed with `mach` (e.g. "2.5mach"), converted using speed of sound at `temperature` (°C),
+/// and road speeds suffixed with `kmh` or `mph` (e.g. "100kmh").
+fn get_speed(param: Option<String>, temperature: &f64, units: &Units) -> Result<Option<f64>, MuzzleError> {
+    // Each suffix with the m/s one of its units is
+    let suffixed = [("mach", speed_of_sound(*temperature)), ("kmh", METERS_PER_SECOND_IN_KMH), ("mph", METERS_PER_SECOND_IN_MPH)]
+        .into_iter()
+        .find_map(|(suffix, factor)| strip_unit_suffix(param.as_deref(), suffix).map(|number| (number, factor)));
+
+    match suffixed {
+        Some((number, factor)) => match number.trim_end().parse::<f64>() {
+            Ok(value) => Ok(Some(match units {
+                Units::METRIC => value * factor,
+                Units::IMPERIAL => value * factor / METERS_IN_FOOT,
+            })),
+            Err(_) => Err(MuzzleError::PARSE(param.unwrap_or_default())),
+        },
+        None => get_float(param),
+    }
+}
+
+/// Tries to parse mass out of given option string. Besides plain numbers (in grains regardless of
+/// units if `in_grains`) accepts drams suffixed with `dr` (e.g. "1.125dr").
+fn get_mass(param: Option<String>, in_grains: bool, units: &Units) -> Result<Option<f64>, MuzzleError> {
+    let grains = match strip_unit_suffix(param.as_deref(), "dr") {
+        Some(number) => match number.trim_end().parse::<f64>() {
+            Ok(drams) => drams * GRAINS_IN_DRAM,
+            Err(_) => return Err(MuzzleError::PARSE(param.unwrap_or_default())),
+        },
+        None if in_grains => match get_float(param)? {
+            Some(grains) => grains,
+            None => return Ok(None),
+        },
+        None => return get_float(param),
+    };
+
+    Ok(Some(match units {
+        Units::METRIC => grains / GRAINS_IN_GRAM,
+        Units::IMPERIAL => grains,
+    }))
+}
+
+/// Number part of given option string if it ends in `suffix`, ignoring case
+fn strip_unit_suffix<'a>(param: Option<&'a str>, suffix: &str) -> Option<&'a str> {
+    let st = param?.trim();
+    let split = st.len().checked_sub(suffix.len())?;
+    st.get(split..).filter(|end| end.eq_ignore_ascii_case(suffix)).and(st.get(..split))
+}
+
+/// Given option string, `None` if it is blank. Blank strings are taken as not given, as scripts
+/// pass empty variables along.
+fn given(param: Option<String>) -> Option<String> {
+    param.filter(|st| !st.trim().is_empty())
+}
+
+/// Tries to parse `f64` out of given option string, blank ones taken as not given
+fn get_float(param: Option<String>) -> Result<Option<f64>, MuzzleError> {
+    match given(param) {
         Some(st) => match st.parse::<f64>() {
-            Ok(n) => Ok(Some(n)),
-            Err(_) => Err(format!("Failed to parse `{}` as input parameter!", st)),
+            Ok(n) if n.is_finite() => Ok(Some(n)),
+            _ => Err(MuzzleError::PARSE(st)),
         },
         None => Ok(None),
     }
@@ -1,71 +1,1339 @@
-use getopts::Options;
-use muzzle::{run, Config, Units};
+use getopts::{Fail, Matches, Options};
+use muzzle::{check_units, colors, energy_chart, energy_sweep_iter, escapes_allowed, filter_json_with_progress, json_schema, find_caliber, find_division, find_reference, format_machine, history, normalize, run, run_with_diagnostics, settings, significant_digits, velocity_decay, ColorChoice, ColorTheme, Config, Diagnostics, DragModel, EnergyUnit, FormatPolicy, Locale, Metadata, MuzzleError, OutputFormat, Params, Plausibility, PowerFormula, Preset, Quantity, ReportOptions, Units, ARMOR_REFERENCES, BC_ITERATIONS, BOGUS_WARNING, CALIBERS, CHART_DISTANCES, CSV_HEADER, DIVISIONS, SCHEMA_VERSION};
+use serde_json::json;
 use std::{env, process};
+use std::fs::{File, OpenOptions};
+use std::io::{self, IsTerminal, Write};
+
+/// Significant digits of an input value past which `--pedantic` hints about false precision
+const DEFAULT_SIG_DIGITS_LIMIT: usize = 5;
+/// Rows of a table printed between progress updates
+const PROGRESS_INTERVAL: usize = 1000;
 
 fn main() {
     let args: Vec<String> = env::args().collect();
     let mut opts = Options::new();
 
-    opts.optflag("h", "help", "print this help menu");
+    opts.optflag("h", "help", "print this help menu, or with a TOPIC after it (mass, speed, energy or an option name) only help on that");
     opts.optflag("i", "imperial", "use imperial units instead of metric");
+    opts.optflag("", "metric", "use metric units even if the settings file defaults to imperial");
+    opts.optopt("", "input-units", "units inputs are given in: metric or imperial, like --metric or --imperial", "UNITS");
+    opts.optopt("", "output-units", "units to report results in, converted from the input units: metric or imperial", "UNITS");
+    opts.optflag("", "both-systems", "report mass, speed and energy in both metric and imperial units, nested under metric and imperial keys with --format json");
+    opts.optflag("", "no-derive", "only parse, check and convert the mass, speed and energy given, any number of them, and echo them back without deriving anything");
+    opts.optflag("b", "binary", "write parameters to stdout as a packed binary record instead of text");
+    opts.optflag("", "emit-command", "print a muzzle command line reproducing the result instead of a report");
+    #[cfg(feature = "qr")]
+    opts.optflag("", "qr", "print a QR code of the --emit-command line instead of a report, to scan into a phone");
+    opts.optflag("", "pellet", "take --mass in grains even for metric, as air rifle pellets are sold by, and show mass in both grains and grams");
+    opts.optflag("", "kgfm", "enter and display metric energy in kilogram-force meters instead of Joules");
+    opts.optflag("", "sound-regime", "classify the projectile as subsonic, transonic or supersonic");
+    opts.optflag("", "normalize", "also show mass, speed, energy, momentum and power factor as indices of a 100 gr at 1000 FPS load");
+    opts.optflag("", "history", "append the result to the calculation history");
+    opts.optflag("q", "quiet", "do not report progress of long tables on stderr");
+    opts.optflag("", "strict", "fail on anything that would otherwise only be warned or hinted about");
+    opts.optflag("", "check-units", "check magnitudes of given mass, speed and energy against the units they are in before calculating");
+    opts.optflag("p", "pedantic", "hint about input values given with more precision than realistic");
 
-    opts.optopt("m", "mass", "mass of the projectile (grains for imperial or grams for metric)", "NUMBER");
-    opts.optopt("s", "speed", "velocity of the projectile (FPS for imperial or m/s for metric)", "NUMBER");
-    opts.optopt("e", "energy", "muzzle energy of the projectile (FPE for imperial or Joules for metric)", "NUMBER");
+    opts.optmulti("m", "mass", "mass of the projectile (grains for imperial or grams for metric, or drams of 27.34375 grains like 1.125dr)", "NUMBER");
+    opts.optmulti("s", "speed", "velocity of the projectile (FPS for imperial or m/s for metric, Mach number like 1.5mach, or road speed like 100kmh or 60mph)", "NUMBER");
+    opts.optmulti("e", "energy", "muzzle energy of the projectile (FPE for imperial or Joules for metric)", "NUMBER");
+    opts.optopt("", "momentum", "with --energy alone, momentum of a bullet of unknown mass to derive speed and mass from, or with --mass alone to derive speed from (lb·ft/s for imperial or kg·m/s for metric)", "NUMBER");
+    opts.optopt("", "compare", "compare the load metric by metric to another of MASS@SPEED (in the units of --mass and --speed), coloring the better value where there is one", "MASS@SPEED");
+    opts.optopt("", "match-load", "with --mass alone, derive the speed matching the momentum of a load of MASS@SPEED (in the units of --mass and --speed), e.g. for softer recoil at the same power factor", "MASS@SPEED");
+    opts.optopt("", "target-pf", "with --energy alone, solve for the mass and speed of the load making both that energy and this power factor", "NUMBER");
+    opts.optopt("", "bc", "ballistic coefficient of the projectile (lb/in²) for downrange estimates", "NUMBER");
+    opts.optopt("", "drag-model", "reference drag function the ballistic coefficient is given against: g1 (default) or g7", "MODEL");
+    opts.optopt("d", "distance", "distance for downrange estimates (yards for imperial or meters for metric)", "NUMBER");
+    opts.optflag("", "energy-chart", "with --bc, print speed and energy at 0, 50, 100, 200 and 300 yards (meters for metric) as a table, or as CSV or JSON with --format");
+    opts.optopt("", "trajectory-samples", "with --bc and --distance, print COUNT evenly spaced distance, speed and energy points as CSV (or JSON with --format json)", "COUNT");
+    #[cfg(feature = "monte-carlo")]
+    {
+        opts.optopt("", "monte-carlo", "sample energy COUNT times with mass and speed spread by --mass-sigma and --speed-sigma, and report its mean and percentiles", "COUNT");
+        opts.optopt("", "seed", "with --monte-carlo, seed of the random numbers to reproduce a distribution with (default taken from the clock and printed)", "NUMBER");
+        opts.optopt("", "mass-sigma", "with --monte-carlo, standard deviation of mass (in the units of --mass)", "NUMBER");
+        opts.optopt("", "speed-sigma", "with --monte-carlo, standard deviation of speed (in the units of --speed)", "NUMBER");
+    }
+    opts.optopt("", "diameter", "diameter of the projectile for sectional density (inches for imperial or mm for metric)", "NUMBER");
+    opts.optopt("", "bore-length", "with --diameter, barrel bore length for a rough energy per bore volume figure (inches for imperial or mm for metric)", "NUMBER");
+    opts.optopt("", "ref-charge", "with --ref-velocity and --target-velocity, powder charge of a known load (grains for imperial or grams for metric)", "NUMBER");
+    opts.optopt("", "ref-velocity", "speed the known load gives", "NUMBER");
+    opts.optopt("", "target-velocity", "speed to roughly estimate a charge for, by linear scaling (never exceed published maximums)", "NUMBER");
+    opts.optopt("", "power-formula", "score the load with a stopping power formula: taylor (needs --diameter), ogw or pf", "NAME");
+    opts.optopt("", "gun-mass", "mass of the firearm for free recoil (pounds for imperial or kg for metric)", "NUMBER");
+    opts.optopt("", "length", "with --twist and --diameter (or --caliber), bullet length for Miller gyroscopic stability (inches for imperial or mm for metric)", "NUMBER");
+    opts.optopt("", "twist", "barrel twist rate as length of one turn, e.g. 10 for 1:10 (inches for imperial or mm for metric)", "NUMBER");
+    opts.optopt("", "wind-speed", "with --bc and --distance, wind speed for drift at the distance (mph for imperial or m/s for metric)", "NUMBER");
+    opts.optopt("", "wind-angle", "direction the wind blows from in degrees clockwise from the line of fire (default 90, full value from the right)", "DEGREES");
+    opts.optopt("", "v1", "with --d1, --v2 and --d2, speed at the near chronograph to estimate BC from", "NUMBER");
+    opts.optopt("", "d1", "distance of the near chronograph from the muzzle (yards for imperial or meters for metric)", "NUMBER");
+    opts.optopt("", "v2", "speed at the far chronograph", "NUMBER");
+    opts.optopt("", "d2", "distance of the far chronograph from the muzzle", "NUMBER");
+    opts.optopt("", "max-iterations", &format!("with --v1, --d1, --v2 and --d2, cap on bisection steps of the BC estimate, failing when it does not converge by then (default {})", BC_ITERATIONS), "COUNT");
+    opts.optopt("", "temp", "air temperature for Mach numbers and --sound-regime (°F for imperial or °C for metric, default 59 °F/15 °C)", "NUMBER");
+    opts.optopt("f", "format", "output format: human (default), json, csv, latex or markdown-table", "FORMAT");
+    opts.optopt("", "human-out", "write the human readable report to PATH (- for stdout), combines with other --*-out", "PATH");
+    opts.optopt("", "json-out", "write JSON output to PATH (- for stdout)", "PATH");
+    opts.optopt("", "csv-out", "write CSV output to PATH (- for stdout)", "PATH");
+    opts.optflag("", "append", "append to --*-out files instead of overwriting them, writing the CSV header only to new or empty files");
+    opts.optflag("", "pretty", "indent JSON output over multiple lines");
+    opts.optflag("", "compact", "write JSON output on a single line (default)");
+    opts.optflag("", "from-json-stdin", "read a JSON array of configs from stdin and write a JSON array of results (or {\"error\": ...} items) to stdout, nothing else");
+    opts.optflag("", "to-json-stdout", "with --from-json-stdin, spell out where results go");
+    #[cfg(all(unix, feature = "daemon"))]
+    opts.optopt("", "listen", "serve JSON configs as calculation requests, one per line, on a Unix socket created at PATH", "PATH");
+    opts.optflag("", "self-describe", "print the JSON Schema of JSON output and exit");
+    opts.optflag("", "metadata", "add version, UTC timestamp and input parameters to JSON output under a meta key");
+    opts.optopt("", "decimals", "decimals to show mass, speed and energy with in human output (default 3)", "COUNT");
+    opts.optopt("", "order", "comma separated fields to show in human output, in that order (default mass,speed,energy)", "FIELDS");
+    opts.optopt("", "mass-range", "with --energy, tabulate loads of that energy across masses", "FROM:TO:STEP");
+    opts.optopt("", "locale", "number and unit label conventions for input and human output: en (default) or de", "LOCALE");
+    opts.optflag("", "group-digits", "separate thousands in human output the way the locale does, e.g. 12,345 Joules");
+    opts.optopt("", "color", "when to color output: auto (terminals only, default), always or never. NO_COLOR set in the environment turns it off regardless.", "WHEN");
+    opts.optopt("", "color-theme", "colors for a dark (default) or light terminal background", "THEME");
+    opts.optopt("", "show-history", "print COUNT most recent entries of the calculation history and exit", "COUNT");
+    opts.optopt("", "caliber", "warn when speed exceeds typical loads of the cartridge, and take --diameter from it: 22lr, 9mm, 357mag, 44mag, 45acp, 223rem, 65cm, 308win, 3006 or 300wm", "NAME");
+    opts.optopt("", "game", "check energy against an informational minimum for the game class: varmint, deer, elk, moose or dangerous", "CLASS");
+    opts.optopt("", "division", "classify the load as major or minor by power factor floors of a practical shooting division: generic (165/125), uspsa-open, uspsa-limited, uspsa-single-stack, uspsa-production, uspsa-carry-optics, ipsc-open, ipsc-standard or ipsc-production", "NAME");
+    opts.optflag("", "intuition", "just for fun, name an everyday thing in motion (a dropped apple, a thrown baseball, ...) with about the same energy");
+    opts.optopt("", "relative-to", "compare energy to a body armor test threat: nij-iia, nij-ii, nij-iiia, nij-iii or nij-iv", "NAME");
+    opts.optopt("", "round-to", "round derived values to the nearest multiple of INCREMENT", "INCREMENT");
+    opts.optflag("", "manual-style", "round all values like reloading manuals do: whole speed and energy, mass to 0.1 grain (0.01 gram)");
+    opts.optopt("", "preset", "bundle of rounding options: chrono (whole speed, mass to 0.1 grain, energy to tens, hints past 4 significant digits), manual (same as --manual-style) or precise (6 decimals, no rounding, hints past 8 significant digits)", "NAME");
+    opts.optmulti("", "assume", "value to fall back to when fewer than two parameters are given (repeatable)", "NAME=NUMBER");
+    opts.optopt("", "limit-sig-digits", "significant digits allowed before --pedantic hints (default 5)", "COUNT");
 
     // Get matches
     let matches = match opts.parse(&args[1..]) {
         Ok(m) => m,
+        // Likely a typo, point at what was meant
+        Err(Fail::UnrecognizedOption(name)) => {
+            eprintln!("Failed to parse parameters with: unknown option `{}`", name);
+            if let Some(suggestion) = closest_option(&opts, &name) {
+                eprintln!("Did you mean `--{}`?", suggestion);
+            }
+            eprintln!("\n{}", generate_usage(&opts));
+            process::exit(1);
+        },
         Err(f) => {
-            eprintln!("Failed to parse parameters with: {}", f.to_string());
+            eprintln!("Failed to parse parameters with: {}", f);
             process::exit(1);
         },
     };
 
+    // Without any options a JSON config may be piped in instead
+    let piped_config = if args.len() < 2 && !io::stdin().is_terminal() { read_piped_config() } else { None };
+
     // Display help and exit
-    if matches.opt_present("h") || args.len() < 2 {
-        println!("{}", generate_usage(&opts));
+    if matches.opt_present("h") || (args.len() < 2 && piped_config.is_none()) {
+        match matches.free.first() {
+            Some(topic) if matches.opt_present("h") => match topic_help(&opts, topic) {
+                Some(help) => println!("{}", help),
+                None => {
+                    eprintln!("Failed to parse parameters with: no help on `{}`, topics are mass, speed, energy and option names", topic);
+                    process::exit(1);
+                },
+            },
+            _ => println!("{}", generate_usage(&opts)),
+        }
+        process::exit(0);
+    }
+
+    let strict = matches.opt_present("strict");
+
+    if matches.opt_present("self-describe") {
+        println!("{}", serde_json::to_string_pretty(&json_schema()).unwrap_or_default());
+        process::exit(0);
+    }
+
+    if let Some(count) = opt_value(&matches, "show-history") {
+        let count = match count.parse::<usize>() {
+            Ok(count) => count,
+            Err(_) => {
+                eprintln!("Failed to parse parameters with: history entry count must be a whole number");
+                process::exit(1);
+            },
+        };
+
+        match history::default_path().map(|path| history::last_entries(&path, count)) {
+            Some(Ok((entries, skipped))) => {
+                if skipped > 0 {
+                    warn(strict, "WARNING", &format!("Skipped {} corrupt history entries.", skipped));
+                }
+                for entry in entries {
+                    println!("{}", entry);
+                }
+            },
+            Some(Err(error)) => {
+                eprintln!("Failed to read calculation history with: {}", error);
+                process::exit(1);
+            },
+            None => {
+                eprintln!("Failed to read calculation history with: neither XDG_DATA_HOME nor HOME is set");
+                process::exit(1);
+            },
+        }
+        process::exit(0);
+    }
+
+    let settings = match settings::default_path().map(|path| settings::load(&path)) {
+        Some(Ok(settings)) => settings,
+        Some(Err(error)) => {
+            eprintln!("Failed to read settings with: {}", error);
+            process::exit(1);
+        },
+        None => settings::Settings::default(),
+    };
+
+    let format_flag = match matches.opt_str("f") {
+        Some(name) => match OutputFormat::from_name(&name) {
+            Some(format) => Some(format),
+            None => {
+                eprintln!("Failed to parse parameters with: unknown output format `{}`", name);
+                process::exit(1);
+            },
+        },
+        None => None,
+    };
+    // Any output asked for on the command line overrides the settings file default
+    let other_output = ["b", "emit-command", "human-out", "json-out", "csv-out"].iter().any(|name| matches.opt_present(name));
+    let format = if other_output { format_flag.unwrap_or_default() } else { settings.format(format_flag) };
+
+    let color = match matches.opt_str("color").map(|name| ColorChoice::from_name(&name).ok_or(name)) {
+        Some(Ok(color)) => Some(color),
+        Some(Err(name)) => {
+            eprintln!("Failed to parse parameters with: unknown color choice `{}`, expected auto, always or never", name);
+            process::exit(1);
+        },
+        None => None,
+    };
+    let theme = match matches.opt_str("color-theme").map(|name| ColorTheme::from_name(&name).ok_or(name)) {
+        Some(Ok(theme)) => Some(theme),
+        Some(Err(name)) => {
+            eprintln!("Failed to parse parameters with: unknown color theme `{}`, expected dark or light", name);
+            process::exit(1);
+        },
+        None => None,
+    };
+    let (color, theme) = (settings.color(color), settings.color_theme(theme));
+
+    let preset = match matches.opt_str("preset").map(|name| Preset::from_name(&name).ok_or(name)) {
+        Some(Ok(preset)) => Some(preset),
+        Some(Err(name)) => {
+            eprintln!("Failed to parse parameters with: unknown preset `{}`, expected chrono, manual or precise", name);
+            process::exit(1);
+        },
+        // Manual style is the manual preset by its older name
+        None => matches.opt_present("manual-style").then_some(Preset::MANUAL),
+    };
+    if (preset.is_some() && opt_value(&matches, "round-to").is_some()) || (matches.opt_present("preset") && matches.opt_present("manual-style")) {
+        eprintln!("Failed to parse parameters with: only one of --round-to, --manual-style and --preset can be given");
+        process::exit(1);
+    }
+
+    let decimals = match opt_value(&matches, "decimals").map(|st| st.parse::<usize>()) {
+        Some(Ok(decimals)) => settings.decimals(Some(decimals)),
+        Some(Err(_)) => {
+            eprintln!("Failed to parse parameters with: decimals must be a whole number");
+            process::exit(1);
+        },
+        None => match preset.and_then(Preset::decimals) {
+            Some(decimals) => decimals,
+            None => settings.decimals(None),
+        },
+    };
+
+    // Fields of the human report in display order, all of them unless told otherwise
+    let order = match matches.opt_str("order") {
+        Some(list) => {
+            let mut order: Vec<Quantity> = Vec::new();
+            for name in list.split(',') {
+                match Quantity::from_name(name) {
+                    Some(quantity) if order.contains(&quantity) => {
+                        eprintln!("Failed to parse parameters with: field `{}` given more than once in --order", name.trim());
+                        process::exit(1);
+                    },
+                    Some(quantity) => order.push(quantity),
+                    None => {
+                        eprintln!("Failed to parse parameters with: unknown field `{}` in --order, expected mass, speed or energy", name.trim());
+                        process::exit(1);
+                    },
+                }
+            }
+            order
+        },
+        None => Quantity::ALL.to_vec(),
+    };
+
+    if matches.opt_present("pretty") && matches.opt_present("compact") {
+        eprintln!("Failed to parse parameters with: only one of --pretty and --compact can be given");
+        process::exit(1);
+    }
+    let pretty = matches.opt_present("pretty");
+
+    #[cfg(all(unix, feature = "daemon"))]
+    if let Some(path) = matches.opt_str("listen") {
+        let served = std::os::unix::net::UnixListener::bind(&path).and_then(|listener| muzzle::daemon::serve(&listener));
+        if let Err(error) = served {
+            eprintln!("Failed to serve on `{}` with: {}", path, error);
+            process::exit(1);
+        }
+    }
+
+    // Pure filter of configs to results, every config carries its own units
+    if matches.opt_present("from-json-stdin") {
+        let input = match io::read_to_string(io::stdin()) {
+            Ok(input) => input,
+            Err(error) => {
+                eprintln!("Failed to read input with: {}", error);
+                process::exit(1);
+            },
+        };
+        let progress = show_progress(&matches);
+        let results = filter_json_with_progress(&input, &mut |done, total| {
+            if progress {
+                report_progress("Calculated", done, total, "configs");
+            }
+        });
+        if progress {
+            clear_progress(color);
+        }
+        match results {
+            Ok(results) => println!("{}", FormatPolicy::default().to_json(&results, pretty)),
+            Err(error) => {
+                eprintln!("Failed to calculate parameters with: {}", error);
+                process::exit(1);
+            },
+        }
         process::exit(0);
     }
+    if matches.opt_present("to-json-stdout") {
+        eprintln!("Failed to parse parameters with: --to-json-stdout only goes with --from-json-stdin");
+        process::exit(1);
+    }
+
+    let output_modes = ["b", "f", "emit-command"].iter().filter(|name| matches.opt_present(name)).count();
+    if output_modes > 1 {
+        eprintln!("Failed to parse parameters with: only one of --format, --binary and --emit-command can be given");
+        process::exit(1);
+    }
+    if matches.opt_present("both-systems") && ["b", "emit-command", "output-units"].iter().any(|name| matches.opt_present(name)) {
+        eprintln!("Failed to parse parameters with: --both-systems goes with none of --binary, --emit-command and --output-units");
+        process::exit(1);
+    }
+    if matches.opt_present("no-derive") && ["b", "emit-command", "output-units", "both-systems"].iter().any(|name| matches.opt_present(name)) {
+        eprintln!("Failed to parse parameters with: --no-derive goes with none of --binary, --emit-command, --output-units and --both-systems");
+        process::exit(1);
+    }
+    #[cfg(feature = "qr")]
+    if matches.opt_present("qr") && output_modes > 0 {
+        eprintln!("Failed to parse parameters with: --qr replaces the report and goes with none of --format, --binary and --emit-command");
+        process::exit(1);
+    }
+
+    let destinations: Vec<(OutputFormat, String)> = [("human-out", OutputFormat::HUMAN), ("json-out", OutputFormat::JSON), ("csv-out", OutputFormat::CSV)]
+        .into_iter()
+        .filter_map(|(name, format)| matches.opt_str(name).map(|destination| (format, destination)))
+        .collect();
+    if output_modes > 0 && !destinations.is_empty() {
+        eprintln!("Failed to parse parameters with: --human-out, --json-out and --csv-out can not be combined with --format, --binary or --emit-command");
+        process::exit(1);
+    }
+    if matches.opt_present("append") && destinations.is_empty() {
+        eprintln!("Failed to parse parameters with: --append only applies to --human-out, --json-out and --csv-out");
+        process::exit(1);
+    }
 
-    let config = Config {
-        units: if matches.opt_present("i") { Units::IMPERIAL } else { Units::METRIC },
-        mass: matches.opt_str("m"),
-        speed: matches.opt_str("s"),
-        energy: matches.opt_str("e"),
+    let [input_units, output_units] = ["input-units", "output-units"].map(|name| match matches.opt_str(name).map(|st| Units::from_name(&st).ok_or(st)) {
+        Some(Ok(units)) => Some(units),
+        Some(Err(st)) => {
+            eprintln!("Failed to parse parameters with: unknown units `{}`, expected metric or imperial", st);
+            process::exit(1);
+        },
+        None => None,
+    });
+    let units = match (matches.opt_present("i"), matches.opt_present("metric"), input_units) {
+        (true, true, _) | (true, _, Some(_)) | (_, true, Some(_)) => {
+            eprintln!("Failed to parse parameters with: only one of --imperial, --metric and --input-units can be given");
+            process::exit(1);
+        },
+        (true, false, None) => settings.units(Some(Units::IMPERIAL)),
+        (false, true, None) => settings.units(Some(Units::METRIC)),
+        (false, false, input_units) => settings.units(input_units),
     };
 
-    let result = match run(config) {
+    let locale = match matches.opt_str("locale").map(|tag| Locale::from_tag(&tag)) {
+        Some(Ok(locale)) => locale,
+        Some(Err(error)) => {
+            eprintln!("Failed to parse parameters with: {}", error);
+            process::exit(1);
+        },
+        None => Locale::EN,
+    };
+    // Human output only, machine readable formats are never grouped
+    let group_digits = matches.opt_present("group-digits");
+    let number = |value: f64, decimals: usize| {
+        let formatted = locale.format_number(value, decimals);
+        if group_digits { locale.group_digits(&formatted) } else { formatted }
+    };
+
+    let [mut mass, mut speed, mut energy] = Quantity::ALL.map(|quantity| match single_value(&matches, quantity.name()) {
+        Ok(value) => value.map(|st| locale.normalize_number(&st)),
+        Err(error) => {
+            eprintln!("Failed to parse parameters with: {}", error);
+            process::exit(1);
+        },
+    });
+    // A load to match is taken in by its momentum
+    let matched = match opt_value(&matches, "match-load").map(|spec| match spec.split_once('@') {
+        Some((mass, speed)) => run(Config {
+            units,
+            mass: Some(locale.normalize_number(mass)),
+            mass_in_grains: matches.opt_present("pellet"),
+            speed: Some(locale.normalize_number(speed)),
+            ..Config::default()
+        }),
+        None => Err(MuzzleError::INVALID(format!("Failed to parse `{}` as MASS@SPEED load to match!", spec))),
+    }) {
+        Some(Ok(reference)) => Some(reference),
+        Some(Err(error)) => {
+            eprintln!("Failed to parse parameters with: {}", error);
+            process::exit(1);
+        },
+        None => None,
+    };
+    if matched.is_some() && opt_value(&matches, "momentum").is_some() {
+        eprintln!("Failed to parse parameters with: --match-load gives the momentum already, --momentum can not be given with it");
+        process::exit(1);
+    }
+    let momentum = match &matched {
+        Some(reference) => Some(format_machine(reference.momentum())),
+        None => opt_value(&matches, "momentum").map(|st| locale.normalize_number(&st)),
+    };
+    let power_factor = opt_value(&matches, "target-pf").map(|st| locale.normalize_number(&st));
+    let bc = opt_value(&matches, "bc").map(|st| locale.normalize_number(&st));
+    let distance = opt_value(&matches, "d").map(|st| locale.normalize_number(&st));
+    let [v1, d1, v2, d2, diameter, bore_length, ref_charge, ref_velocity, target_velocity, gun_mass, length, twist, wind_speed, wind_angle] =
+        ["v1", "d1", "v2", "d2", "diameter", "bore-length", "ref-charge", "ref-velocity", "target-velocity", "gun-mass", "length", "twist", "wind-speed", "wind-angle"].map(|name| opt_value(&matches, name).map(|st| locale.normalize_number(&st)));
+
+    let drag_model = match matches.opt_str("drag-model").map(|st| st.to_ascii_lowercase()).as_deref() {
+        None | Some("g1") => DragModel::G1,
+        Some("g7") => DragModel::G7,
+        Some(other) => {
+            eprintln!("Failed to parse parameters with: unknown drag model `{}`", other);
+            process::exit(1);
+        },
+    };
+
+    let power_formula = match matches.opt_str("power-formula") {
+        Some(name) => match PowerFormula::from_name(&name) {
+            Some(formula) => Some(formula),
+            None => {
+                eprintln!("Failed to parse parameters with: unknown power formula `{}`", name);
+                process::exit(1);
+            },
+        },
+        None => None,
+    };
+
+    let caliber = match matches.opt_str("caliber") {
+        Some(name) => match find_caliber(CALIBERS, &name) {
+            Some(caliber) => Some(caliber),
+            None => {
+                eprintln!("Failed to parse parameters with: unknown caliber `{}`", name);
+                process::exit(1);
+            },
+        },
+        None => None,
+    };
+
+    let game = match matches.opt_str("game") {
+        Some(name) => match settings.game_class(&name) {
+            Some(class) => Some(class),
+            None => {
+                eprintln!("Failed to parse parameters with: unknown game class `{}`", name);
+                process::exit(1);
+            },
+        },
+        None => None,
+    };
+
+    let division = match matches.opt_str("division") {
+        Some(name) => match find_division(DIVISIONS, &name) {
+            Some(division) => Some(division),
+            None => {
+                eprintln!("Failed to parse parameters with: unknown division `{}`", name);
+                process::exit(1);
+            },
+        },
+        None => None,
+    };
+
+    let reference = match matches.opt_str("relative-to") {
+        Some(name) => match find_reference(ARMOR_REFERENCES, &name) {
+            Some(reference) => Some(reference),
+            None => {
+                eprintln!("Failed to parse parameters with: unknown reference `{}`", name);
+                process::exit(1);
+            },
+        },
+        None => None,
+    };
+
+    // Increment to round derived values to along with decimals it is written with
+    let round_to = match opt_value(&matches, "round-to").map(|st| locale.normalize_number(&st)) {
+        Some(st) => match st.parse::<f64>() {
+            Ok(increment) if increment > 0.0 && increment.is_finite() => {
+                Some((increment, st.split_once('.').map(|(_, decimals)| decimals.len()).unwrap_or(0)))
+            },
+            _ => {
+                eprintln!("Failed to parse parameters with: rounding increment must be a positive number");
+                process::exit(1);
+            },
+        },
+        None => None,
+    };
+
+    let absolute_zero = match units {
+        Units::METRIC => -273.15,
+        Units::IMPERIAL => -459.67,
+    };
+    let temperature = match opt_value(&matches, "temp").map(|st| locale.normalize_number(&st).parse::<f64>()) {
+        Some(Ok(temperature)) if temperature.is_finite() && temperature > absolute_zero => Some(temperature),
+        Some(_) => {
+            eprintln!("Failed to parse parameters with: temperature must be a number above absolute zero");
+            process::exit(1);
+        },
+        None => None,
+    };
+
+    let sig_digits_limit = match opt_value(&matches, "limit-sig-digits").map(|st| st.parse::<usize>()) {
+        Some(Ok(limit)) => limit,
+        Some(Err(_)) => {
+            eprintln!("Failed to parse parameters with: significant digits limit must be a whole number");
+            process::exit(1);
+        },
+        None => preset.and_then(Preset::sig_digits_limit).unwrap_or(DEFAULT_SIG_DIGITS_LIMIT),
+    };
+
+    // Hint about false precision. Values are used as given regardless.
+    if matches.opt_present("p") {
+        for (name, value) in Quantity::ALL.into_iter().zip([&mass, &speed, &energy]) {
+            if let Some(value) = value {
+                let digits = significant_digits(value);
+                if digits > sig_digits_limit {
+                    warn(strict, "HINT", &format!(
+                        "{} `{}` has {} significant digits, which is more than measurements are usually accurate to ({}).",
+                        name, value, digits, sig_digits_limit
+                    ));
+                }
+            }
+        }
+    }
+
+    let energy_unit = if matches.opt_present("kgfm") { EnergyUnit::KGFM } else { EnergyUnit::JOULES };
+    let diameter = diameter.or_else(|| caliber.map(|caliber| format_machine(caliber.diameter(units))));
+
+    // Energy constrained sweep prints its own table and exits
+    if let Some(range) = opt_value(&matches, "mass-range") {
+        if output_modes > 0 || mass.is_some() || speed.is_some() {
+            eprintln!("Failed to parse parameters with: --mass-range only combines with --energy and units");
+            process::exit(1);
+        }
+
+        let bounds: Vec<f64> = range.split(':').map(|st| st.parse::<f64>()).collect::<Result<_, _>>().unwrap_or_default();
+        let energy = energy.as_ref().and_then(|st| st.parse::<f64>().ok()).map(|e| energy_unit.to_joules(e));
+
+        let rows = match (bounds.as_slice(), energy) {
+            (&[from, to, step], Some(energy)) => energy_sweep_iter(units, energy, from, to, step),
+            (_, None) => Err(MuzzleError::INVALID("Energy must be given as a number to sweep masses at.".to_owned())),
+            _ => Err(MuzzleError::INVALID(format!("Failed to parse `{}` as FROM:TO:STEP mass range!", range))),
+        };
+
+        if let Err(error) = rows.and_then(|rows| print_energy_sweep(rows, units, locale, show_progress(&matches), color)) {
+            eprintln!("Failed to calculate parameters with: {}", error);
+            process::exit(1);
+        }
+        process::exit(0);
+    }
+
+    // Fill in assumed values, in the order given, only as far as needed to derive the rest
+    for assumption in matches.opt_strs("assume") {
+        let (name, value) = match assumption.split_once('=') {
+            Some((name, value)) => (name.trim(), locale.normalize_number(value.trim())),
+            None => {
+                eprintln!("Failed to parse parameters with: assumption `{}` is not in NAME=NUMBER form", assumption);
+                process::exit(1);
+            },
+        };
+
+        let given = [&mass, &speed, &energy].iter().filter(|param| param.is_some()).count();
+        let (quantity, field) = match name.parse::<Quantity>() {
+            Ok(Quantity::MASS) => (Quantity::MASS, &mut mass),
+            Ok(Quantity::SPEED) => (Quantity::SPEED, &mut speed),
+            Ok(Quantity::ENERGY) => (Quantity::ENERGY, &mut energy),
+            Err(_) => {
+                eprintln!("Failed to parse parameters with: unknown parameter `{}` in assumption", name);
+                process::exit(1);
+            },
+        };
+
+        if given < 2 && field.is_none() {
+            eprintln!("NOTE: {} was not given, assuming {}.", quantity, value);
+            *field = Some(value);
+        }
+    }
+
+    let pellet = matches.opt_present("pellet");
+    // Speed given as road speed is shown as such as well
+    let road_speed = speed.as_deref().map(|st| st.trim().to_ascii_lowercase()).and_then(|st| ["kmh", "mph"].into_iter().find(|suffix| st.ends_with(suffix)));
+    let config = piped_config.unwrap_or(Config {
+        units,
+        mass,
+        mass_in_grains: pellet,
+        speed,
+        energy,
+        momentum,
+        power_factor,
+        energy_unit,
+        bc,
+        drag_model,
+        distance,
+        temperature: temperature.map(format_machine),
+        v1,
+        d1,
+        v2,
+        d2,
+        max_iterations: opt_value(&matches, "max-iterations"),
+        diameter,
+        bore_length,
+        ref_charge,
+        ref_velocity,
+        target_velocity,
+        gun_mass,
+        length,
+        twist,
+        wind_speed,
+        wind_angle,
+    });
+    let (energy_unit, drag_model, distance, bc) = (config.energy_unit, config.drag_model, config.distance.clone(), config.bc.clone());
+    // Charts come with distances of their own, --bc alone does not ask for an estimate at --distance
+    let config = if matches.opt_present("energy-chart") && distance.is_none() { Config { bc: None, ..config } } else { config };
+    let diameter = config.diameter.as_ref().and_then(|st| st.parse::<f64>().ok());
+
+    // Preflight on stderr, the calculation goes ahead regardless unless under --strict
+    if matches.opt_present("check-units") {
+        let checks = check_units(&config);
+        let (units_name, other_name) = match config.units {
+            Units::METRIC => ("metric", "imperial"),
+            Units::IMPERIAL => ("imperial", "metric"),
+        };
+        eprintln!("Unit check ({}):", units_name);
+        for check in &checks {
+            let verdict = match check.verdict {
+                Plausibility::MISMATCHED => format!("likely {}", other_name),
+                verdict => verdict.to_string(),
+            };
+            eprintln!("  {}:\t{}\t{}", check.quantity, format_machine(check.value), verdict);
+        }
+        if checks.iter().any(|check| check.verdict != Plausibility::PLAUSIBLE) {
+            warn(strict, "WARNING", "Some values are implausible for the units they are given in. Check them before trusting the result.");
+        }
+    }
+
+    // Passthrough of the inputs alone, before anything could be found missing for a derivation
+    if matches.opt_present("no-derive") {
+        let given = match normalize(&config) {
+            Ok(given) => given,
+            Err(error) => {
+                eprintln!("Failed to parse parameters with: {}", error);
+                process::exit(1);
+            },
+        };
+        let value = |quantity: Quantity| given.iter().find(|(given, _)| *given == quantity).map(|&(_, value)| value);
+        let units = match config.units {
+            Units::METRIC => "metric",
+            Units::IMPERIAL => "imperial",
+        };
+
+        match format {
+            OutputFormat::JSON => {
+                let document = json!({
+                    "schema_version": SCHEMA_VERSION,
+                    "units": units,
+                    "mass": value(Quantity::MASS),
+                    "speed": value(Quantity::SPEED),
+                    "energy": value(Quantity::ENERGY),
+                });
+                if pretty {
+                    println!("{}", serde_json::to_string_pretty(&document).unwrap_or_default());
+                } else {
+                    println!("{}", document);
+                }
+            },
+            OutputFormat::CSV => {
+                println!("units,mass,speed,energy");
+                let cell = |quantity: Quantity| value(quantity).map(format_machine).unwrap_or_default();
+                println!("{},{},{},{}", units, cell(Quantity::MASS), cell(Quantity::SPEED), cell(Quantity::ENERGY));
+            },
+            OutputFormat::HUMAN => {
+                let (mass_unit, speed_unit, mut energy_label) = locale.unit_labels(config.units);
+                if energy_unit == EnergyUnit::KGFM {
+                    energy_label = "kgf·m";
+                }
+                println!("Given shot parameters are:");
+                for (quantity, value) in &given {
+                    match quantity {
+                        Quantity::MASS => println!("Projectile mass:\t{} {}", number(*value, decimals), mass_unit),
+                        Quantity::SPEED => println!("Projectile speed:\t{} {}", number(*value, decimals), speed_unit),
+                        Quantity::ENERGY => println!("Projectile energy:\t{} {}", number(energy_unit.from_joules(*value), decimals), energy_label),
+                    }
+                }
+            },
+            _ => {
+                eprintln!("Failed to parse parameters with: --no-derive is written as human output, JSON or CSV only");
+                process::exit(1);
+            },
+        }
+        process::exit(0);
+    }
+
+    // Taken before `run` consumes the config
+    let metadata = matches.opt_present("metadata").then(|| Metadata::new(config.clone()));
+
+    let result = match run_with_diagnostics(config, &mut StderrDiagnostics { strict }) {
         Ok(params) => params,
+        // Show how to use muzzle instead of only what is missing
+        Err(MuzzleError::EMPTY) => {
+            eprintln!("Failed to calculate parameters with: {}\n", MuzzleError::EMPTY);
+            eprintln!("{}", generate_usage(&opts));
+            process::exit(1);
+        },
         Err(error) => {
             eprintln!("Failed to calculate parameters with: {}", error);
             process::exit(1);
         },
     };
 
-    // Figure out units
-    let (mass_unit, speed_unit, energy_unit) = match result.units {
-        Units::METRIC => ("grams", "m/s", "Joules"),
-        Units::IMPERIAL => ("grains", "FPS", "FPE"),
+    let power_score = match power_formula.map(|formula| (formula, formula.score(&result, diameter))) {
+        Some((formula, Some(score))) => Some((formula, score)),
+        Some((formula, None)) => {
+            eprintln!("Failed to calculate parameters with: {} formula needs --diameter", formula.name());
+            process::exit(1);
+        },
+        None => None,
+    };
+
+    // Everything from here on is reported in the output units, distance and temperature included
+    let (result, distance, temperature, energy_unit) = match output_units {
+        Some(output_units) if output_units != result.units => {
+            let distance = distance.as_ref().and_then(|st| st.parse::<f64>().ok()).map(|distance| format_machine(result.units.convert_distance(distance, output_units)));
+            let temperature = temperature.map(|temperature| result.units.convert_temperature(temperature, output_units));
+            // Kilogram-force meters are metric only
+            let energy_unit = if output_units == Units::IMPERIAL { EnergyUnit::JOULES } else { energy_unit };
+            (result.in_units(output_units), distance, temperature, energy_unit)
+        },
+        _ => (result, distance, temperature, energy_unit),
+    };
+
+    // Failing to record history should not cost the user their result
+    if matches.opt_present("history") {
+        match history::default_path().map(|path| history::append(&path, &result)) {
+            Some(Ok(())) => {},
+            Some(Err(error)) => warn(strict, "WARNING", &format!("Failed to record calculation history with: {}", error)),
+            None => warn(strict, "WARNING", "Failed to record calculation history with: neither XDG_DATA_HOME nor HOME is set"),
+        }
+    }
+
+    if let Some(caliber) = caliber {
+        if result.speed > caliber.max_speed(result.units) {
+            let (_, speed_unit, _) = locale.unit_labels(result.units);
+            warn(strict, "WARNING", &format!(
+                "Speed of {} {} is above the typical top of {} {} for {} (informational, not SAAMI or CIP data).",
+                number(result.speed, 0), speed_unit,
+                number(caliber.max_speed(result.units), 0), speed_unit,
+                caliber.description
+            ));
+        }
+    }
+
+    let json_output = || match &metadata {
+        Some(metadata) => result.to_json_with_metadata(metadata, pretty),
+        None if pretty => result.to_json_pretty(),
+        None => result.to_json(),
+    };
+
+    // Keep machine readable stdout clean of warnings
+    let warn_bogus = |to_stderr: bool| {
+        if result.bogus {
+            if to_stderr || strict { warn(strict, "WARNING", BOGUS_WARNING) } else { println!("WARNING: {}", BOGUS_WARNING) }
+        }
+    };
+
+    // Sampled decay curve replaces the report, in CSV unless JSON is asked for
+    if let Some(count) = opt_value(&matches, "trajectory-samples") {
+        let unsupported = matches.opt_present("b") || matches.opt_present("emit-command") || matches!(format, OutputFormat::LATEX | OutputFormat::MARKDOWN);
+        let samples = match (count.parse::<usize>(), bc.and_then(|st| st.parse::<f64>().ok()), distance.as_ref().and_then(|st| st.parse::<f64>().ok())) {
+            (Ok(count), Some(bc), Some(distance)) if !unsupported => velocity_decay(&result, drag_model, bc, distance, count),
+            (Err(_), _, _) => Err(MuzzleError::INVALID("Trajectory sample count must be a whole number.".to_owned())),
+            (_, None, _) | (_, _, None) => Err(MuzzleError::INVALID("Trajectory samples need both --bc and --distance.".to_owned())),
+            _ => Err(MuzzleError::INVALID("Trajectory samples are written as CSV or JSON only.".to_owned())),
+        };
+        let samples = match samples {
+            Ok(samples) => samples,
+            Err(error) => {
+                eprintln!("Failed to calculate parameters with: {}", error);
+                process::exit(1);
+            },
+        };
+
+        warn_bogus(true);
+        if let OutputFormat::JSON = format {
+            let units = match result.units {
+                Units::METRIC => "metric",
+                Units::IMPERIAL => "imperial",
+            };
+            let points: Vec<_> = samples.iter().map(|&(distance, speed, energy)| json!({ "distance": distance, "speed": speed, "energy": energy })).collect();
+            let mut document = json!({ "schema_version": SCHEMA_VERSION, "units": units, "samples": points });
+            if let Some(metadata) = &metadata {
+                document["meta"] = metadata.to_json_value();
+            }
+            if pretty {
+                println!("{}", serde_json::to_string_pretty(&document).unwrap_or_default());
+            } else {
+                println!("{}", document);
+            }
+        } else {
+            println!("distance,speed,energy");
+            for (distance, speed, energy) in samples {
+                println!("{},{},{}", format_machine(distance), format_machine(speed), format_machine(energy_unit.from_joules(energy)));
+            }
+        }
+        process::exit(0);
+    }
+
+    // Energy drop chart replaces the report as well, as an aligned table unless asked otherwise
+    if matches.opt_present("energy-chart") {
+        let unsupported = matches.opt_present("b") || matches.opt_present("emit-command") || matches!(format, OutputFormat::LATEX | OutputFormat::MARKDOWN);
+        let rows = match bc.as_ref().and_then(|st| st.parse::<f64>().ok()) {
+            Some(bc) if !unsupported => energy_chart(&result, drag_model, bc, &CHART_DISTANCES),
+            Some(_) => Err(MuzzleError::INVALID("Energy charts are written as a table, CSV or JSON only.".to_owned())),
+            None => Err(MuzzleError::INVALID("Energy charts need --bc.".to_owned())),
+        };
+        let rows = match rows {
+            Ok(rows) => rows,
+            Err(error) => {
+                eprintln!("Failed to calculate parameters with: {}", error);
+                process::exit(1);
+            },
+        };
+
+        warn_bogus(true);
+        match format {
+            OutputFormat::JSON => {
+                let units = match result.units {
+                    Units::METRIC => "metric",
+                    Units::IMPERIAL => "imperial",
+                };
+                let points: Vec<_> = rows.iter().map(|&(distance, speed, energy)| json!({ "distance": distance, "speed": speed, "energy": energy_unit.from_joules(energy) })).collect();
+                let mut document = json!({ "schema_version": SCHEMA_VERSION, "units": units, "chart": points });
+                if let Some(metadata) = &metadata {
+                    document["meta"] = metadata.to_json_value();
+                }
+                if pretty {
+                    println!("{}", serde_json::to_string_pretty(&document).unwrap_or_default());
+                } else {
+                    println!("{}", document);
+                }
+            },
+            OutputFormat::CSV => {
+                println!("distance,speed,energy");
+                for (distance, speed, energy) in rows {
+                    println!("{},{},{}", format_machine(distance), format_machine(speed), format_machine(energy_unit.from_joules(energy)));
+                }
+            },
+            _ => {
+                let (_, speed_unit, mut energy_label) = locale.unit_labels(result.units);
+                if energy_unit == EnergyUnit::KGFM {
+                    energy_label = "kgf·m";
+                }
+                let header = [
+                    format!("Distance ({})", locale.distance_label(result.units)),
+                    format!("Speed ({})", speed_unit),
+                    format!("Energy ({})", energy_label),
+                ];
+                let cells: Vec<[String; 3]> = rows
+                    .iter()
+                    .map(|&(distance, speed, energy)| [number(distance, 0), number(speed, 0), number(energy_unit.from_joules(energy), 0)])
+                    .collect();
+
+                // Numbers are right aligned under their headers
+                let widths: Vec<usize> = (0..3).map(|column| cells.iter().chain([&header]).map(|row| row[column].chars().count()).max().unwrap_or_default()).collect();
+                for row in [&header].into_iter().chain(&cells) {
+                    println!("{:>w0$}  {:>w1$}  {:>w2$}", row[0], row[1], row[2], w0 = widths[0], w1 = widths[1], w2 = widths[2]);
+                }
+            },
+        }
+        process::exit(0);
+    }
+
+    // Sampled energy distribution replaces the report, in JSON if asked for
+    #[cfg(feature = "monte-carlo")]
+    if let Some(count) = opt_value(&matches, "monte-carlo") {
+        let sigma = |name: &str| opt_value(&matches, name).map_or(Ok(0.0), |st| locale.normalize_number(&st).parse::<f64>().map_err(|_| MuzzleError::PARSE(st)));
+        // Clock seeded runs are reproduced through the seed printed with them
+        let seed = opt_value(&matches, "seed").map(|st| st.parse::<u64>()).unwrap_or_else(|| {
+            Ok(std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map(|time| time.as_nanos() as u64).unwrap_or_default())
+        });
+        let unsupported = matches.opt_present("b") || matches.opt_present("emit-command") || matches!(format, OutputFormat::LATEX | OutputFormat::MARKDOWN | OutputFormat::CSV);
+        let distribution = match (count.parse::<usize>(), seed, sigma("mass-sigma"), sigma("speed-sigma")) {
+            (Ok(count), Ok(seed), Ok(mass_sigma), Ok(speed_sigma)) if !unsupported => {
+                muzzle::monte_carlo::sample_energy(&result, mass_sigma, speed_sigma, count, seed).map(|distribution| (distribution, seed))
+            },
+            (Err(_), _, _, _) => Err(MuzzleError::INVALID("Monte Carlo sample count must be a whole number.".to_owned())),
+            (_, Err(_), _, _) => Err(MuzzleError::INVALID("Seed must be a whole number.".to_owned())),
+            (_, _, Err(error), _) | (_, _, _, Err(error)) => Err(error),
+            _ => Err(MuzzleError::INVALID("Monte Carlo results are written as human output or JSON only.".to_owned())),
+        };
+        let (distribution, seed) = match distribution {
+            Ok(distribution) => distribution,
+            Err(error) => {
+                eprintln!("Failed to calculate parameters with: {}", error);
+                process::exit(1);
+            },
+        };
+
+        warn_bogus(true);
+        let energy = |value: f64| energy_unit.from_joules(value);
+        if let OutputFormat::JSON = format {
+            let units = match result.units {
+                Units::METRIC => "metric",
+                Units::IMPERIAL => "imperial",
+            };
+            let document = json!({
+                "schema_version": SCHEMA_VERSION,
+                "units": units,
+                "samples": distribution.samples,
+                "seed": seed,
+                "energy": { "mean": energy(distribution.mean), "p5": energy(distribution.p5), "p50": energy(distribution.p50), "p95": energy(distribution.p95) },
+            });
+            if pretty {
+                println!("{}", serde_json::to_string_pretty(&document).unwrap_or_default());
+            } else {
+                println!("{}", document);
+            }
+        } else {
+            let (_, _, mut energy_label) = locale.unit_labels(result.units);
+            if energy_unit == EnergyUnit::KGFM {
+                energy_label = "kgf·m";
+            }
+            println!("Energy over {} samples (seed {}):", distribution.samples, seed);
+            for (label, value) in [("Mean", distribution.mean), ("5th percentile", distribution.p5), ("Median", distribution.p50), ("95th percentile", distribution.p95)] {
+                println!("{}:\t{} {}", label, number(energy(value), decimals), energy_label);
+            }
+        }
+        process::exit(0);
+    }
+
+    // Binary output replaces text output entirely
+    if matches.opt_present("b") {
+        warn_bogus(true);
+        if let Err(error) = io::stdout().write_all(&result.to_bytes()) {
+            eprintln!("Failed to write binary output with: {}", error);
+            process::exit(1);
+        }
+        process::exit(0);
+    }
+
+    if matches.opt_present("emit-command") {
+        warn_bogus(true);
+        println!("{}", result.to_command());
+        process::exit(0);
+    }
+
+    #[cfg(feature = "qr")]
+    if matches.opt_present("qr") {
+        warn_bogus(true);
+        let command = result.to_command();
+        match muzzle::qr::QrCode::encode(&command) {
+            Ok(code) if escapes_allowed(color, io::stdout().is_terminal()) => println!("{}{}", code.to_ansi(), command),
+            Ok(code) => println!("{}{}", code.to_text(), command),
+            Err(error) => {
+                eprintln!("Failed to write QR code with: {}", error);
+                process::exit(1);
+            },
+        }
+        process::exit(0);
+    }
+
+    // A comparison replaces the report, the other load taken in the input units and converted along
+    if let Some(spec) = opt_value(&matches, "compare") {
+        let other = match spec.split_once('@') {
+            Some((mass, speed)) => run(Config {
+                units,
+                mass: Some(locale.normalize_number(mass)),
+                mass_in_grains: matches.opt_present("pellet"),
+                speed: Some(locale.normalize_number(speed)),
+                ..Config::default()
+            }),
+            None => Err(MuzzleError::INVALID(format!("Failed to parse `{}` as MASS@SPEED load to compare to!", spec))),
+        };
+        match (other, format) {
+            (Ok(other), OutputFormat::HUMAN) => {
+                warn_bogus(true);
+                let options = ReportOptions { locale, group_digits, decimals, energy_unit, color: colors(color, theme, io::stdout().is_terminal()), ..ReportOptions::default() };
+                print!("{}", result.to_comparison(&other, &options));
+            },
+            (Ok(_), _) => {
+                eprintln!("Failed to parse parameters with: --compare is written as human output only");
+                process::exit(1);
+            },
+            (Err(error), _) => {
+                eprintln!("Failed to parse parameters with: {}", error);
+                process::exit(1);
+            },
+        }
+        process::exit(0);
+    }
+
+    // Both systems replace the report, converted from whichever the calculation was made in
+    if matches.opt_present("both-systems") {
+        match format {
+            OutputFormat::JSON => {
+                warn_bogus(true);
+                println!("{}", result.to_json_both_systems(pretty));
+            },
+            OutputFormat::HUMAN => {
+                warn_bogus(false);
+                for (units, title) in [(Units::METRIC, "Metric"), (Units::IMPERIAL, "Imperial")] {
+                    let converted = result.in_units(units);
+                    let (mass_unit, speed_unit, mut energy_label) = locale.unit_labels(units);
+                    // Kilogram-force meters are metric only
+                    let energy_unit = if units == Units::METRIC { energy_unit } else { EnergyUnit::JOULES };
+                    if energy_unit == EnergyUnit::KGFM {
+                        energy_label = "kgf·m";
+                    }
+                    println!("{}:", title);
+                    println!("Projectile mass:\t{} {}", number(converted.mass, decimals), mass_unit);
+                    println!("Projectile speed:\t{} {}", number(converted.speed, decimals), speed_unit);
+                    println!("Projectile energy:\t{} {}\n", number(energy_unit.from_joules(converted.energy), decimals), energy_label);
+                }
+            },
+            _ => {
+                eprintln!("Failed to parse parameters with: --both-systems is written as human output or JSON only");
+                process::exit(1);
+            },
+        }
+        process::exit(0);
+    }
+
+    if let OutputFormat::JSON = format {
+        warn_bogus(true);
+        println!("{}", json_output());
+        process::exit(0);
+    }
+
+    if let OutputFormat::LATEX = format {
+        warn_bogus(true);
+        println!("{}", result.to_latex());
+        process::exit(0);
+    }
+
+    if let OutputFormat::MARKDOWN = format {
+        warn_bogus(true);
+        println!("{}", result.to_markdown());
+        process::exit(0);
+    }
+
+    if let OutputFormat::CSV = format {
+        warn_bogus(true);
+        println!("{}\n{}", CSV_HEADER, result.to_csv());
+        process::exit(0);
+    }
+
+    // Human readable report, wherever it is going
+    let options = ReportOptions {
+        locale,
+        group_digits,
+        decimals,
+        order,
+        preset,
+        round_to,
+        energy_unit,
+        pellet,
+        road_speed,
+        matched: matched.as_ref(),
+        distance: distance.as_deref(),
+        drag_model,
+        sound_regime: matches.opt_present("sound-regime"),
+        temperature,
+        power_score,
+        normalize: matches.opt_present("normalize"),
+        relative_to: reference,
+        intuition: matches.opt_present("intuition"),
+        division,
+        game: game.as_ref(),
+        color: None,
     };
+    let report = result.to_human(&options);
 
-    if result.bogus {
-        println!("WARNING: All shot parameters have been given. Nothing has been derived. Displaying as is.");
+    // Under --strict a bogus result fails before anything is written
+    if strict {
+        warn_bogus(true);
     }
 
-    print!(
-        "Derived shot parameters are:\nProjectile mass:\t{:.3} {}\nProjectile speed:\t{:.3} {}\nProjectile energy:\t{:.3} {}\n\n",
-        result.mass, mass_unit,
-        result.speed, speed_unit,
-        result.energy, energy_unit
-    );
+    // Each format goes to its own destination in one run, otherwise the report goes to stdout
+    if !destinations.is_empty() {
+        if destinations.iter().any(|(format, _)| !matches!(format, OutputFormat::HUMAN)) {
+            warn_bogus(true);
+        }
+        let append = matches.opt_present("append");
+        for (format, destination) in &destinations {
+            let written = write_output(destination, append, |out, empty| match format {
+                OutputFormat::HUMAN => write!(out, "{}", report),
+                OutputFormat::JSON => writeln!(out, "{}", json_output()),
+                _ if empty => writeln!(out, "{}\n{}", CSV_HEADER, result.to_csv()),
+                _ => writeln!(out, "{}", result.to_csv()),
+            });
+            if let Err(error) = written {
+                eprintln!("Failed to write output to `{}` with: {}", destination, error);
+                process::exit(1);
+            }
+        }
+        process::exit(0);
+    }
+
+    // Only the report on stdout is colored, never those written to files
+    let report = result.to_human(&ReportOptions { color: colors(color, theme, io::stdout().is_terminal()), ..options });
+    if let Err(error) = write!(io::stdout().lock(), "{}", report) {
+        eprintln!("Failed to write output with: {}", error);
+        process::exit(1);
+    }
 
     process::exit(0);
 }
 
+/// Prints loads of equal energy as a table with momentum and power factor for each, row by row as
+/// they are calculated, optionally counting them on stderr
+fn print_energy_sweep(rows: impl Iterator<Item = Result<Params, MuzzleError>>, units: Units, locale: Locale, progress: bool, color: ColorChoice) -> Result<(), MuzzleError> {
+    let (mass_unit, speed_unit, _) = locale.unit_labels(units);
+    let momentum_unit = match units {
+        Units::METRIC => "kg·m/s",
+        Units::IMPERIAL => "lb·ft/s",
+    };
+
+    println!("Mass ({})\tSpeed ({})\tMomentum ({})\tPower factor", mass_unit, speed_unit, momentum_unit);
+    let total = rows.size_hint().0;
+    for (row, params) in rows.enumerate() {
+        let params = match params {
+            Ok(params) => params,
+            Err(error) => {
+                if progress {
+                    clear_progress(color);
+                }
+                return Err(error);
+            },
+        };
+        println!(
+            "{}\t{}\t{}\t{}",
+            locale.format_number(params.mass, 3),
+            locale.format_number(params.speed, 3),
+            locale.format_number(params.momentum(), 3),
+            locale.format_number(params.power_factor(), 3)
+        );
+        if progress {
+            report_progress("Tabulated", row + 1, total, "loads");
+        }
+    }
+
+    if progress {
+        clear_progress(color);
+    }
+    Ok(())
+}
+
+/// Whether to report progress of a long table on stderr. Only makes sense for a person watching
+/// stderr while the rows go elsewhere, and not under `--quiet`.
+fn show_progress(matches: &Matches) -> bool {
+    !matches.opt_present("q") && io::stderr().is_terminal() && !io::stdout().is_terminal()
+}
+
+/// Reports `done` of `total` rows on stderr, every `PROGRESS_INTERVAL` rows and once all are done
+fn report_progress(verb: &str, done: usize, total: usize, rows: &str) {
+    if done.is_multiple_of(PROGRESS_INTERVAL) || done == total {
+        eprint!("\r{} {} of {} {}", verb, done, total, rows);
+    }
+}
+
+/// Leaves no trace of the progress counter once done, or just ends its line where escape
+/// sequences are not allowed
+fn clear_progress(color: ColorChoice) {
+    if escapes_allowed(color, io::stderr().is_terminal()) {
+        eprint!("\r\x1b[2K");
+    } else {
+        eprintln!();
+    }
+}
+
+/// Value of an option, `None` when it is not given or blank, the way `--decimals "$DECIMALS"`
+/// reads with `DECIMALS` empty
+fn opt_value(matches: &Matches, name: &str) -> Option<String> {
+    matches.opt_str(name).filter(|value| !value.trim().is_empty())
+}
+
+/// Value of an option that may be repeated (or given in both short and long form) as long as
+/// every occurrence agrees, so that `-m 150 --mass 160` is not silently resolved either way.
+/// Blank occurrences count as not given, the way `-m "$MASS"` reads with `MASS` empty.
+fn single_value(matches: &Matches, name: &str) -> Result<Option<String>, String> {
+    let values: Vec<String> = matches.opt_strs(name).into_iter().filter(|value| !value.trim().is_empty()).collect();
+    match values.iter().find(|value| value.trim() != values[0].trim()) {
+        Some(conflicting) => Err(format!("{} given more than once with conflicting values `{}` and `{}`", name, values[0], conflicting)),
+        None => Ok(values.into_iter().next()),
+    }
+}
+
+/// Lets `write` write to the file at `destination`, created or truncated (or appended to with
+/// `append`), or to stdout for `-`. `write` is told whether the output starts out empty, so
+/// appended records get a header only once.
+fn write_output<F: FnOnce(&mut dyn Write, bool) -> io::Result<()>>(destination: &str, append: bool, write: F) -> io::Result<()> {
+    match destination {
+        "-" => write(&mut io::stdout().lock(), true),
+        path if append => {
+            let file = OpenOptions::new().create(true).append(true).open(path)?;
+            let empty = file.metadata()?.len() == 0;
+            write(&mut io::BufWriter::new(file), empty)
+        },
+        path => write(&mut io::BufWriter::new(File::create(path)?), true),
+    }
+}
+
+/// Diagnostics of the calculation reported on stderr as they come, failing under `--strict`
+struct StderrDiagnostics {
+    strict: bool,
+}
+
+impl Diagnostics for StderrDiagnostics {
+    fn warning(&mut self, message: String) {
+        warn(self.strict, "WARNING", &message);
+    }
+}
+
+/// Reports a problem that does not stop the calculation on stderr, prefixed with `label`.
+/// Under `--strict` it does stop it: the message is reported as a failure and the process exits.
+fn warn(strict: bool, label: &str, message: &str) {
+    if strict {
+        eprintln!("Failed because of --strict with: {}", message);
+        process::exit(1);
+    }
+    eprintln!("{}: {}", label, message);
+}
+
+/// Reads a JSON `Config` from stdin, `None` (with a warning unless stdin is empty) if there is none
+fn read_piped_config() -> Option<Config> {
+    let input = io::read_to_string(io::stdin()).unwrap_or_default();
+    if input.trim().is_empty() {
+        return None;
+    }
+
+    match serde_json::from_str(&input) {
+        Ok(config) => Some(config),
+        Err(error) => {
+            warn(false, "WARNING", &format!("Ignoring piped input as it is not a valid JSON config: {}", error));
+            None
+        },
+    }
+}
+
+/// Long option closest to the unrecognized `name` by edit distance, if any is close enough to be a typo of it
+fn closest_option(opts: &Options, name: &str) -> Option<String> {
+    let name = name.trim_start_matches('-');
+    // Options do not list their names, usage does
+    let usage = opts.usage("");
+    let known = usage
+        .split_whitespace()
+        .filter_map(|word| word.strip_prefix("--"))
+        .map(|word| word.trim_end_matches(|c: char| !c.is_ascii_alphanumeric()))
+        .filter(|word| !word.is_empty());
+
+    known
+        .map(|option| (edit_distance(name, option), option))
+        .filter(|&(distance, _)| distance <= 2 && distance < name.chars().count())
+        .min_by_key(|&(distance, _)| distance)
+        .map(|(_, option)| option.to_owned())
+}
+
+/// Levenshtein distance between two strings, counted in characters
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+/// Help on a single `topic`: formula and units of a primary quantity, followed by the usage
+/// entry of its option, or just the usage entry of any other option. Leading dashes are ignored.
+fn topic_help(opts: &Options, topic: &str) -> Option<String> {
+    let name = topic.trim_start_matches('-');
+    let quantity = match Quantity::from_name(name) {
+        Some(Quantity::MASS) => "mass (-m, --mass): mass of the projectile, in grams (metric) or grains (imperial).
+
+    mass = 2 × energy / speed²
+
+Metric energy and speed give kilograms, multiplied by 1000 to get grams. Imperial foot-pounds are
+pounds of force, multiplied by standard gravity to give pounds of mass of 7000 grains each:
+
+    m [g]  = 2 × E [J] / v [m/s]² × 1000
+    m [gr] = 2 × 32.174 × E [ft·lbf] / v [ft/s]² × 7000",
+        Some(Quantity::SPEED) => "speed (-s, --speed): velocity of the projectile, in m/s (metric) or feet per second (imperial).
+
+    speed = √(2 × energy / mass)
+
+Metric mass is taken in kilograms. Imperial mass is taken in pounds, with foot-pounds of force
+multiplied by standard gravity to match:
+
+    v [m/s]  = √(2 × E [J] / (m [g] / 1000))
+    v [ft/s] = √(2 × 32.174 × E [ft·lbf] / (m [gr] / 7000))",
+        Some(Quantity::ENERGY) => "energy (-e, --energy): kinetic energy of the projectile, in Joules (metric) or foot-pounds (imperial).
+
+    energy = mass × speed² / 2
+
+Metric mass is taken in kilograms. Imperial mass is taken in pounds and divided by standard gravity
+to get foot-pounds of force:
+
+    E [J]      = m [g] / 1000 × v [m/s]² / 2
+    E [ft·lbf] = m [gr] / 7000 × v [ft/s]² / (2 × 32.174)",
+        None => "",
+    };
+
+    // Rows of the usage start with the short and long names of their option
+    let option = opts.usage_with_format(|rows| {
+        let mut rows = rows.map(|row| row.to_owned());
+        rows.find(|row| {
+            let mut names = row.split_whitespace().take(2).map(|word| word.trim_end_matches(','));
+            names.any(|word| word == format!("--{}", name) || word == format!("-{}", name))
+        })
+        .unwrap_or_default()
+    });
+
+    match (quantity, option.is_empty()) {
+        ("", true) => None,
+        ("", false) => Some(format!("Options:\n{}", option)),
+        (quantity, _) => Some(format!("{}\n\nOptions:\n{}", quantity, option)),
+    }
+}
+
 /// Generates usage information string out of options object
 fn generate_usage(opts: &Options) -> String {
-    let brief = "USAGE: muzzle [--imperial] [--mass NUMBER] [--speed NUMBER] [--energy NUMBER]
+    let brief = "USAGE: muzzle [--imperial] [--format FORMAT | --binary | --emit-command] [--pedantic] [--mass NUMBER] [--speed NUMBER] [--energy NUMBER]
 \nEnter either two of the three parameters to get the third.";
-    opts.usage(&brief)
+    opts.usage(brief)
 }
@@ -0,0 +1,59 @@
+//! Audit details of a calculation, for JSON records that have to stand on their own.
+
+use crate::Config;
+use serde_json::json;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Version of the crate doing the calculations
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// When and by which version a calculation was made, and from what input.
+pub struct Metadata {
+    /// Crate version, `VERSION` unless restored from elsewhere
+    pub version: &'static str,
+    /// Seconds since the Unix epoch, UTC
+    pub timestamp: u64,
+    /// Config exactly as it was handed to `run`
+    pub input: Config,
+}
+
+impl Metadata {
+    /// Metadata of a calculation made right now from `input`
+    pub fn new(input: Config) -> Metadata {
+        // A clock set before 1970 is not worth failing the calculation over
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map(|elapsed| elapsed.as_secs()).unwrap_or(0);
+        Metadata { version: VERSION, timestamp, input }
+    }
+
+    /// JSON object of version, RFC 3339 timestamp and input, leaving out input fields that were not given
+    pub fn to_json_value(&self) -> serde_json::Value {
+        let mut input = serde_json::to_value(&self.input).unwrap_or_default();
+        if let Some(fields) = input.as_object_mut() {
+            fields.retain(|_, value| !value.is_null());
+        }
+
+        json!({
+            "version": self.version,
+            "timestamp": rfc3339(self.timestamp),
+            "input": input,
+        })
+    }
+}
+
+/// Formats seconds since the Unix epoch as an RFC 3339 UTC timestamp, e.g. `2024-03-01T12:00:00Z`
+fn rfc3339(timestamp: u64) -> String {
+    let (days, seconds) = (timestamp / 86_400, timestamp % 86_400);
+
+    // Civil date from days since the epoch, after Howard Hinnant's `civil_from_days`
+    let shifted = days + 719_468;
+    let era = shifted / 146_097;
+    let day_of_era = shifted % 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+    format!("{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z", year, month, day, seconds / 3600, seconds % 3600 / 60, seconds % 60)
+}
@@ -0,0 +1,95 @@
+//! Monte Carlo propagation of mass and speed uncertainty into energy.
+//!
+//! Mass and speed are each drawn from a normal distribution around the shot's value, e.g. with
+//! the standard deviation of a chronograph string for speed, and energy is derived for every
+//! draw. Draws are truncated at zero, as a negative mass or speed means nothing: those are
+//! simply drawn again, skewing the distribution only for spreads close to the value itself.
+//! Random numbers come from a small seeded generator, so a seed always yields the same
+//! distribution. Only built with the `monte-carlo` feature.
+
+use crate::{MuzzleError, Params};
+
+/// Energy distribution of a sampled shot, in the same units as its energy
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Distribution {
+    /// Number of draws energy was derived for
+    pub samples: usize,
+    /// Mean of the derived energies
+    pub mean: f64,
+    /// 5th percentile
+    pub p5: f64,
+    /// Median
+    pub p50: f64,
+    /// 95th percentile
+    pub p95: f64,
+}
+
+/// SplitMix64 generator, short and statistically good enough for sampling inputs
+struct Rng(u64);
+
+impl Rng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform draw from (0, 1], never zero so its logarithm is finite
+    fn uniform(&mut self) -> f64 {
+        ((self.next_u64() >> 11) + 1) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Standard normal draw, by the Box–Muller transform
+    fn normal(&mut self) -> f64 {
+        (-2.0 * self.uniform().ln()).sqrt() * (std::f64::consts::TAU * self.uniform()).cos()
+    }
+
+    /// Normal draw around `mean`, drawn again until positive
+    fn positive_normal(&mut self, mean: f64, sigma: f64) -> f64 {
+        loop {
+            let value = mean + sigma * self.normal();
+            if value > 0.0 {
+                return value;
+            }
+        }
+    }
+}
+
+/// Linearly interpolated percentile `p` (0 to 100) of ascending `sorted` values
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = p / 100.0 * (sorted.len() - 1) as f64;
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+}
+
+/// Samples energy of the shot `count` times with its mass and speed spread by standard deviations
+/// `mass_sigma` and `speed_sigma` (in the shot's units), starting the generator from `seed`
+pub fn sample_energy(params: &Params, mass_sigma: f64, speed_sigma: f64, count: usize, seed: u64) -> Result<Distribution, MuzzleError> {
+    if count == 0 {
+        return Err(MuzzleError::INVALID("Monte Carlo needs at least one sample.".to_owned()));
+    }
+    if !(mass_sigma >= 0.0 && speed_sigma >= 0.0 && mass_sigma.is_finite() && speed_sigma.is_finite()) {
+        return Err(MuzzleError::INVALID("Standard deviations must be non-negative numbers.".to_owned()));
+    }
+
+    let mut rng = Rng(seed);
+    let mut energies: Vec<f64> = (0..count)
+        .map(|_| {
+            let mass = rng.positive_normal(params.mass, mass_sigma);
+            let speed = rng.positive_normal(params.speed, speed_sigma);
+            params.with_mass(mass).with_speed(speed).energy
+        })
+        .collect();
+    energies.sort_by(f64::total_cmp);
+
+    Ok(Distribution {
+        samples: count,
+        mean: energies.iter().sum::<f64>() / count as f64,
+        p5: percentile(&energies, 5.0),
+        p50: percentile(&energies, 50.0),
+        p95: percentile(&energies, 95.0),
+    })
+}
@@ -0,0 +1,172 @@
+//! Notation numbers of structured output are written in, chosen per quantity.
+//!
+//! Shortest round-tripping digits serve most values, but where they switch to an exponent is
+//! up to the writer: `serde_json` does so past 1e16 and below 1e-5, `format_machine` never
+//! does. A `FormatPolicy` pins that down per quantity instead, e.g. a tiny airgun pellet mass
+//! stays in plain digits while artillery energy reads as `4.2e7`. Values keep the exact same
+//! digits either way and JSON values stay numbers.
+
+use crate::{format_machine, Quantity};
+use serde_json::ser::{CompactFormatter, Formatter, PrettyFormatter};
+use std::io::{self, Write};
+
+/// Magnitude energy switches to scientific notation from by default, a megajoule (or FPE)
+pub const ENERGY_SCIENTIFIC_FROM: f64 = 1e6;
+
+/// Notation a quantity is written in
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Notation {
+    /// Plain decimal digits, never an exponent
+    FIXED,
+    /// Scientific (`3.2e6`) from given magnitude up, plain digits below it
+    SCIENTIFIC(f64),
+}
+
+impl Notation {
+    /// Writes `value` in this notation, as `format_machine` does when it is plain
+    pub fn format(self, value: f64) -> String {
+        match self {
+            Notation::SCIENTIFIC(from) if value.abs() >= from => format!("{:e}", value),
+            _ => format_machine(value),
+        }
+    }
+
+    /// Writes `value` as a JSON number, keeping the `.0` of whole numbers `serde_json` writes
+    fn format_json(self, value: f64) -> String {
+        let mut number = self.format(value);
+        if !number.contains(['.', 'e']) {
+            number.push_str(".0");
+        }
+        number
+    }
+}
+
+/// Notation of each primary quantity in JSON and CSV output. Downrange speed and energy follow
+/// speed and energy, other values are not affected.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FormatPolicy {
+    /// Notation of mass, plain by default as masses are small but never tiny enough to need an exponent
+    pub mass: Notation,
+    /// Notation of speed, plain by default
+    pub speed: Notation,
+    /// Notation of energy, scientific from `ENERGY_SCIENTIFIC_FROM` by default
+    pub energy: Notation,
+}
+
+impl Default for FormatPolicy {
+    fn default() -> Self {
+        FormatPolicy { mass: Notation::FIXED, speed: Notation::FIXED, energy: Notation::SCIENTIFIC(ENERGY_SCIENTIFIC_FROM) }
+    }
+}
+
+impl FormatPolicy {
+    /// Notation of given quantity
+    pub fn notation(&self, quantity: Quantity) -> Notation {
+        match quantity {
+            Quantity::MASS => self.mass,
+            Quantity::SPEED => self.speed,
+            Quantity::ENERGY => self.energy,
+        }
+    }
+
+    /// Notation of the JSON key (or CSV column) of that name, if it holds a primary quantity
+    fn for_key(&self, key: &str) -> Option<Notation> {
+        match key {
+            "mass" => Some(self.mass),
+            "speed" | "retained_speed" => Some(self.speed),
+            "energy" | "retained_energy" => Some(self.energy),
+            _ => None,
+        }
+    }
+
+    /// Renders a JSON document (compact or indented) with numbers under primary quantity keys
+    /// written in their notation
+    pub fn to_json(&self, document: &serde_json::Value, pretty: bool) -> String {
+        let mut output = Vec::new();
+        let written = if pretty {
+            write_json(&mut output, document, PolicyFormatter::new(PrettyFormatter::new(), *self))
+        } else {
+            write_json(&mut output, document, PolicyFormatter::new(CompactFormatter, *self))
+        };
+        written.ok().and_then(|()| String::from_utf8(output).ok()).unwrap_or_default()
+    }
+}
+
+fn write_json<F: Formatter>(output: &mut Vec<u8>, document: &serde_json::Value, formatter: F) -> serde_json::Result<()> {
+    serde::Serialize::serialize(document, &mut serde_json::Serializer::with_formatter(output, formatter))
+}
+
+/// Formatter writing numbers in the notation of the key they are under, delegating everything
+/// else to the wrapped compact or pretty formatter
+struct PolicyFormatter<F> {
+    inner: F,
+    policy: FormatPolicy,
+    /// Most recent object key, values are written right after theirs
+    key: String,
+    in_key: bool,
+}
+
+impl<F> PolicyFormatter<F> {
+    fn new(inner: F, policy: FormatPolicy) -> Self {
+        PolicyFormatter { inner, policy, key: String::new(), in_key: false }
+    }
+}
+
+impl<F: Formatter> Formatter for PolicyFormatter<F> {
+    fn write_f64<W: ?Sized + Write>(&mut self, writer: &mut W, value: f64) -> io::Result<()> {
+        match self.policy.for_key(&self.key) {
+            Some(notation) => writer.write_all(notation.format_json(value).as_bytes()),
+            None => self.inner.write_f64(writer, value),
+        }
+    }
+
+    fn write_string_fragment<W: ?Sized + Write>(&mut self, writer: &mut W, fragment: &str) -> io::Result<()> {
+        if self.in_key {
+            self.key.push_str(fragment);
+        }
+        self.inner.write_string_fragment(writer, fragment)
+    }
+
+    fn begin_object_key<W: ?Sized + Write>(&mut self, writer: &mut W, first: bool) -> io::Result<()> {
+        self.key.clear();
+        self.in_key = true;
+        self.inner.begin_object_key(writer, first)
+    }
+
+    fn end_object_key<W: ?Sized + Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.in_key = false;
+        self.inner.end_object_key(writer)
+    }
+
+    fn begin_array<W: ?Sized + Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.inner.begin_array(writer)
+    }
+
+    fn end_array<W: ?Sized + Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.inner.end_array(writer)
+    }
+
+    fn begin_array_value<W: ?Sized + Write>(&mut self, writer: &mut W, first: bool) -> io::Result<()> {
+        self.inner.begin_array_value(writer, first)
+    }
+
+    fn end_array_value<W: ?Sized + Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.inner.end_array_value(writer)
+    }
+
+    fn begin_object<W: ?Sized + Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.inner.begin_object(writer)
+    }
+
+    fn end_object<W: ?Sized + Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.inner.end_object(writer)
+    }
+
+    fn begin_object_value<W: ?Sized + Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.inner.begin_object_value(writer)
+    }
+
+    fn end_object_value<W: ?Sized + Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.inner.end_object_value(writer)
+    }
+}
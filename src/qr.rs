@@ -0,0 +1,358 @@
+//! QR codes of short text, for carrying a result over to a phone.
+//!
+//! A small encoder of just what a `muzzle` command needs: byte mode, error correction level L and
+//! versions 1 to 6, which hold up to 134 bytes. Every mask is tried and the one scoring lowest on
+//! the penalty rules of ISO/IEC 18004 is kept. The code is printed with half block characters,
+//! two modules high per line, dark on an explicitly light background so it also scans from
+//! terminals with a dark theme, or plain where escape sequences are not allowed. Only built with
+//! the `qr` feature.
+
+use crate::MuzzleError;
+
+/// Total codewords, error correction codewords per block and number of blocks of versions 1 to 6 at level L
+const VERSIONS: [(usize, usize, usize); 6] = [(26, 7, 1), (44, 10, 1), (70, 15, 1), (100, 20, 1), (134, 26, 1), (172, 18, 2)];
+/// Modules of light border around the code scanners need to find it
+const QUIET_ZONE: usize = 4;
+
+/// Square grid of dark and light modules
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QrCode {
+    size: usize,
+    modules: Vec<bool>,
+    function: Vec<bool>,
+}
+
+impl QrCode {
+    /// Encodes `text` in the smallest version it fits into, `MuzzleError::INVALID` if it does not fit any
+    pub fn encode(text: &str) -> Result<QrCode, MuzzleError> {
+        let bytes = text.as_bytes();
+        // Mode and length take 12 bits, so whole bytes remain only after two data codewords
+        let (version, &(total, ecc, blocks)) = VERSIONS
+            .iter()
+            .enumerate()
+            .find(|(_, (total, ecc, blocks))| bytes.len() + 2 <= total - ecc * blocks)
+            .ok_or_else(|| MuzzleError::INVALID(format!("Text of {} bytes is too long for a QR code, at most 134 fit.", bytes.len())))?;
+
+        let data = data_codewords(bytes, total - ecc * blocks);
+        let mut code = QrCode::blank(version + 1);
+        code.draw_codewords(&interleave(&data, ecc, blocks));
+
+        // Keep the mask scoring lowest, drawing format bits first as they take part in the score
+        let best = (0..8)
+            .min_by_key(|&mask| {
+                let mut masked = code.clone();
+                masked.apply_mask(mask);
+                masked.draw_format(mask);
+                masked.penalty()
+            })
+            .unwrap_or_default();
+        code.apply_mask(best);
+        code.draw_format(best);
+
+        Ok(code)
+    }
+
+    /// Modules along a side, 21 for version 1 and 4 more for every version after
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Whether the module in column `x` and row `y` is dark, counting from the top left
+    pub fn is_dark(&self, x: usize, y: usize) -> bool {
+        self.modules[y * self.size + x]
+    }
+
+    /// Renders the code for a terminal as lines of ANSI colored half blocks, quiet zone included
+    pub fn to_ansi(&self) -> String {
+        self.render(true)
+    }
+
+    /// Renders the code as `to_ansi` does without any escape sequences, dark modules drawn in the
+    /// terminal's own colors. Only scans from a dark on light theme, for when `escapes_allowed` is not.
+    pub fn to_text(&self) -> String {
+        self.render(false)
+    }
+
+    /// Lines of half blocks, black on white if `colored`
+    fn render(&self, colored: bool) -> String {
+        let side = self.size + 2 * QUIET_ZONE;
+        let dark = |x: usize, y: usize| {
+            x >= QUIET_ZONE && y >= QUIET_ZONE && x < QUIET_ZONE + self.size && y < QUIET_ZONE + self.size && self.is_dark(x - QUIET_ZONE, y - QUIET_ZONE)
+        };
+
+        let mut out = String::new();
+        for y in (0..side).step_by(2) {
+            // Black on white
+            if colored {
+                out.push_str("\x1b[30;47m");
+            }
+            for x in 0..side {
+                out.push(match (dark(x, y), dark(x, y + 1)) {
+                    (true, true) => '█',
+                    (true, false) => '▀',
+                    (false, true) => '▄',
+                    (false, false) => ' ',
+                });
+            }
+            if colored {
+                out.push_str("\x1b[0m");
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Code of given `version` with only finder, timing, alignment and dark module drawn
+    fn blank(version: usize) -> QrCode {
+        let size = 17 + 4 * version;
+        let mut code = QrCode { size, modules: vec![false; size * size], function: vec![false; size * size] };
+
+        for i in 0..size {
+            code.set_function(6, i, i.is_multiple_of(2));
+            code.set_function(i, 6, i.is_multiple_of(2));
+        }
+        for (x, y) in [(3, 3), (size - 4, 3), (3, size - 4)] {
+            code.draw_finder(x, y);
+        }
+        if version > 1 {
+            code.draw_alignment(size - 7, size - 7);
+        }
+        // Reserve format areas, drawn once the mask is known
+        code.draw_format(0);
+
+        code
+    }
+
+    fn set_function(&mut self, x: usize, y: usize, dark: bool) {
+        self.modules[y * self.size + x] = dark;
+        self.function[y * self.size + x] = true;
+    }
+
+    /// Finder pattern centered at given module along with its light separator
+    fn draw_finder(&mut self, x: usize, y: usize) {
+        for dy in -4i64..=4 {
+            for dx in -4i64..=4 {
+                let (xx, yy) = (x as i64 + dx, y as i64 + dy);
+                if (0..self.size as i64).contains(&xx) && (0..self.size as i64).contains(&yy) {
+                    let distance = dx.abs().max(dy.abs());
+                    self.set_function(xx as usize, yy as usize, distance != 2 && distance != 4);
+                }
+            }
+        }
+    }
+
+    /// Alignment pattern centered at given module
+    fn draw_alignment(&mut self, x: usize, y: usize) {
+        for dy in -2i64..=2 {
+            for dx in -2i64..=2 {
+                self.set_function((x as i64 + dx) as usize, (y as i64 + dy) as usize, dx.abs().max(dy.abs()) != 1);
+            }
+        }
+    }
+
+    /// Both copies of the format information of level L with given `mask`, and the dark module
+    fn draw_format(&mut self, mask: u32) {
+        // Level L is 01, followed by the mask and ten BCH bits
+        let data = (1 << 3) | mask;
+        let mut remainder = data;
+        for _ in 0..10 {
+            remainder = (remainder << 1) ^ ((remainder >> 9) * 0x537);
+        }
+        let bits = ((data << 10) | remainder) ^ 0x5412;
+        let bit = |i: usize| (bits >> i) & 1 != 0;
+        let size = self.size;
+
+        for i in 0..6 {
+            self.set_function(8, i, bit(i));
+        }
+        self.set_function(8, 7, bit(6));
+        self.set_function(8, 8, bit(7));
+        self.set_function(7, 8, bit(8));
+        for i in 9..15 {
+            self.set_function(14 - i, 8, bit(i));
+        }
+
+        for i in 0..8 {
+            self.set_function(size - 1 - i, 8, bit(i));
+        }
+        for i in 8..15 {
+            self.set_function(8, size - 15 + i, bit(i));
+        }
+        self.set_function(8, size - 8, true);
+    }
+
+    /// Places `codewords` in the zigzag of two module wide columns from the bottom right, skipping
+    /// function modules. Remainder modules left over are light.
+    fn draw_codewords(&mut self, codewords: &[u8]) {
+        let size = self.size;
+        let mut i = 0;
+        let mut right = size - 1;
+        while right >= 1 {
+            // Vertical timing pattern is skipped entirely
+            if right == 6 {
+                right = 5;
+            }
+            for vertical in 0..size {
+                for j in 0..2 {
+                    let x = right - j;
+                    let upward = (right + 1) & 2 == 0;
+                    let y = if upward { size - 1 - vertical } else { vertical };
+                    if !self.function[y * size + x] && i < codewords.len() * 8 {
+                        self.modules[y * size + x] = (codewords[i / 8] >> (7 - i % 8)) & 1 != 0;
+                        i += 1;
+                    }
+                }
+            }
+            if right < 2 {
+                break;
+            }
+            right -= 2;
+        }
+    }
+
+    /// Flips the data modules selected by given `mask` pattern
+    fn apply_mask(&mut self, mask: u32) {
+        for y in 0..self.size {
+            for x in 0..self.size {
+                if !self.function[y * self.size + x] && masked(mask, x, y) {
+                    self.modules[y * self.size + x] ^= true;
+                }
+            }
+        }
+    }
+
+    /// Penalty score of the standard rules: long runs, 2×2 blocks, finder lookalikes and imbalance
+    fn penalty(&self) -> usize {
+        let size = self.size;
+        let mut penalty = 0;
+
+        let lines = (0..size).flat_map(|i| [(0..size).map(|j| self.is_dark(j, i)).collect::<Vec<_>>(), (0..size).map(|j| self.is_dark(i, j)).collect()]);
+        for line in lines {
+            let mut run = 1;
+            for j in 1..=size {
+                if j < size && line[j] == line[j - 1] {
+                    run += 1;
+                    continue;
+                }
+                if run >= 5 {
+                    penalty += run - 2;
+                }
+                run = 1;
+            }
+
+            let finder = [true, false, true, true, true, false, true];
+            for window in line.windows(11) {
+                let light = |modules: &[bool]| modules.iter().all(|&dark| !dark);
+                if (window[..7] == finder && light(&window[7..])) || (light(&window[..4]) && window[4..] == finder) {
+                    penalty += 40;
+                }
+            }
+        }
+
+        for y in 1..size {
+            for x in 1..size {
+                let color = self.is_dark(x, y);
+                if self.is_dark(x - 1, y) == color && self.is_dark(x, y - 1) == color && self.is_dark(x - 1, y - 1) == color {
+                    penalty += 3;
+                }
+            }
+        }
+
+        let dark = self.modules.iter().filter(|&&dark| dark).count();
+        let deviation = (dark * 100 / self.modules.len()).abs_diff(50);
+        penalty + deviation / 5 * 10
+    }
+}
+
+/// Whether mask pattern `mask` flips the module in column `x` and row `y`
+fn masked(mask: u32, x: usize, y: usize) -> bool {
+    match mask {
+        0 => (x + y).is_multiple_of(2),
+        1 => y.is_multiple_of(2),
+        2 => x.is_multiple_of(3),
+        3 => (x + y).is_multiple_of(3),
+        4 => (x / 3 + y / 2).is_multiple_of(2),
+        5 => x * y % 2 + x * y % 3 == 0,
+        6 => (x * y % 2 + x * y % 3).is_multiple_of(2),
+        _ => ((x + y) % 2 + x * y % 3).is_multiple_of(2),
+    }
+}
+
+/// Byte mode segment of `bytes` with terminator and padding, `capacity` codewords long
+fn data_codewords(bytes: &[u8], capacity: usize) -> Vec<u8> {
+    // Mode 0100 and 8 bit length, leaving every data byte split across two codewords
+    let mut codewords = vec![0x40 | (bytes.len() >> 4) as u8];
+    let mut carry = (bytes.len() as u8 & 0x0F) << 4;
+    for &byte in bytes {
+        codewords.push(carry | byte >> 4);
+        carry = (byte & 0x0F) << 4;
+    }
+    // Terminator fills the rest of the last half codeword
+    codewords.push(carry);
+
+    for pad in [0xEC, 0x11].into_iter().cycle() {
+        if codewords.len() >= capacity {
+            break;
+        }
+        codewords.push(pad);
+    }
+    codewords
+}
+
+/// Splits `data` into `blocks` equal blocks, appends `ecc` Reed–Solomon codewords to each and
+/// interleaves them codeword by codeword
+fn interleave(data: &[u8], ecc: usize, blocks: usize) -> Vec<u8> {
+    let length = data.len() / blocks;
+    let divisor = reed_solomon_divisor(ecc);
+    let blocks: Vec<(&[u8], Vec<u8>)> = data.chunks(length).map(|block| (block, reed_solomon_remainder(block, &divisor))).collect();
+
+    let mut codewords = Vec::with_capacity(data.len() + ecc * blocks.len());
+    for i in 0..length {
+        codewords.extend(blocks.iter().map(|(block, _)| block[i]));
+    }
+    for i in 0..ecc {
+        codewords.extend(blocks.iter().map(|(_, remainder)| remainder[i]));
+    }
+    codewords
+}
+
+/// Product of two elements of GF(2⁸) modulo the QR code polynomial x⁸ + x⁴ + x³ + x² + 1
+fn multiply(x: u8, y: u8) -> u8 {
+    let mut product: u16 = 0;
+    for i in (0..8).rev() {
+        product = (product << 1) ^ ((product >> 7) * 0x11D);
+        product ^= ((y >> i) & 1) as u16 * x as u16;
+    }
+    product as u8
+}
+
+/// Generator polynomial of given `degree`, coefficients from the highest power down, leading 1 left out
+fn reed_solomon_divisor(degree: usize) -> Vec<u8> {
+    let mut divisor = vec![0u8; degree];
+    divisor[degree - 1] = 1;
+    let mut root = 1u8;
+    for _ in 0..degree {
+        for j in 0..degree {
+            divisor[j] = multiply(divisor[j], root);
+            if j + 1 < degree {
+                divisor[j] ^= divisor[j + 1];
+            }
+        }
+        root = multiply(root, 0x02);
+    }
+    divisor
+}
+
+/// Error correction codewords of `data`, the remainder of its division by `divisor`
+fn reed_solomon_remainder(data: &[u8], divisor: &[u8]) -> Vec<u8> {
+    let mut remainder = vec![0u8; divisor.len()];
+    for &byte in data {
+        let factor = byte ^ remainder[0];
+        remainder.remove(0);
+        remainder.push(0);
+        for (coefficient, &term) in remainder.iter_mut().zip(divisor) {
+            *coefficient ^= multiply(term, factor);
+        }
+    }
+    remainder
+}
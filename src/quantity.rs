@@ -0,0 +1,62 @@
+//! Primary quantities of a shot, along with the names and units they go by in input and output.
+
+use crate::{MuzzleError, Units};
+use std::fmt;
+use std::str::FromStr;
+
+/// Primary quantities describing a shot, any two of which determine the third.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Quantity {
+    /// Mass of the projectile
+    MASS,
+    /// Speed of the projectile
+    SPEED,
+    /// Kinetic energy of the projectile
+    ENERGY,
+}
+
+impl Quantity {
+    /// Every quantity in canonical order, the one reports, columns and records list them in
+    pub const ALL: [Quantity; 3] = [Quantity::MASS, Quantity::SPEED, Quantity::ENERGY];
+
+    /// Lowercase name of the quantity as used in output and on the command line
+    pub fn name(self) -> &'static str {
+        match self {
+            Quantity::MASS => "mass",
+            Quantity::SPEED => "speed",
+            Quantity::ENERGY => "energy",
+        }
+    }
+
+    /// Quantity of given name, as returned by `name`, ignoring case
+    pub fn from_name(name: &str) -> Option<Quantity> {
+        Quantity::ALL.into_iter().find(|quantity| quantity.name().eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// Short unit symbol of the quantity in given measurment system, as tabular formats print it.
+    /// Energy is always in joules or foot-pounds.
+    pub fn unit(self, units: Units) -> &'static str {
+        match (self, units) {
+            (Quantity::MASS, Units::METRIC) => "g",
+            (Quantity::MASS, Units::IMPERIAL) => "gr",
+            (Quantity::SPEED, Units::METRIC) => "m/s",
+            (Quantity::SPEED, Units::IMPERIAL) => "ft/s",
+            (Quantity::ENERGY, Units::METRIC) => "J",
+            (Quantity::ENERGY, Units::IMPERIAL) => "ft·lbf",
+        }
+    }
+}
+
+impl fmt::Display for Quantity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Quantity {
+    type Err = MuzzleError;
+
+    fn from_str(name: &str) -> Result<Quantity, MuzzleError> {
+        Quantity::from_name(name).ok_or_else(|| MuzzleError::INVALID(format!("Unknown quantity `{}`, expected mass, speed or energy!", name.trim())))
+    }
+}
@@ -0,0 +1,536 @@
+//! Human readable report of shot parameters, as muzzle prints it by default.
+//!
+//! Everything the report shows beyond the parameters themselves is chosen by `ReportOptions`,
+//! so the report can be rendered (and tested) without the command line.
+
+use crate::settings::DEFAULT_DECIMALS;
+use crate::{
+    round_to_increment, ColorTheme, Division, DragModel, EnergyUnit, GameClass, Locale, Params, PowerFormula, Quantity, Reference, Units, INTUITION_REFERENCES,
+    NORMALIZATION_REFERENCE, RESET, STANDARD_TEMPERATURE,
+};
+use std::fmt::Write;
+
+/// Warning given when there was nothing to derive
+pub const BOGUS_WARNING: &str = "All shot parameters have been given. Nothing has been derived. Displaying as is.";
+
+/// Named bundle of rounding options, `--preset`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Preset {
+    /// As a chronograph session is written down: whole speed, mass to 0.1 grain (0.01 gram) and
+    /// energy to the nearest ten, hinting about inputs past the 4 digits chronographs read
+    CHRONO,
+    /// As reloading manuals print loads: whole speed and energy, mass to 0.1 grain (0.01 gram)
+    MANUAL,
+    /// Nothing rounded, 6 decimals shown, hinting only past 8 significant digits
+    PRECISE,
+}
+
+impl Preset {
+    /// Preset of given name: chrono, manual or precise
+    pub fn from_name(name: &str) -> Option<Preset> {
+        match name {
+            "chrono" => Some(Preset::CHRONO),
+            "manual" => Some(Preset::MANUAL),
+            "precise" => Some(Preset::PRECISE),
+            _ => None,
+        }
+    }
+
+    /// Rounding increment and decimals given quantity is printed with, if it is rounded at all
+    pub fn rounding(self, quantity: Quantity, units: Units) -> Option<(f64, usize)> {
+        match (self, quantity, units) {
+            (Preset::PRECISE, _, _) => None,
+            (_, Quantity::MASS, Units::IMPERIAL) => Some((0.1, 1)),
+            (_, Quantity::MASS, Units::METRIC) => Some((0.01, 2)),
+            (Preset::CHRONO, Quantity::ENERGY, _) => Some((10.0, 0)),
+            (_, Quantity::SPEED, _) | (_, Quantity::ENERGY, _) => Some((1.0, 0)),
+        }
+    }
+
+    /// Decimals of values that are not rounded, in place of `--decimals`
+    pub fn decimals(self) -> Option<usize> {
+        match self {
+            Preset::PRECISE => Some(6),
+            Preset::CHRONO | Preset::MANUAL => None,
+        }
+    }
+
+    /// Significant digits `--pedantic` hints past, in place of `--limit-sig-digits`
+    pub fn sig_digits_limit(self) -> Option<usize> {
+        match self {
+            Preset::CHRONO => Some(4),
+            Preset::PRECISE => Some(8),
+            Preset::MANUAL => None,
+        }
+    }
+}
+
+/// What the human report shows and how, the command line options it is rendered with.
+/// `ReportOptions::default()` is the report of `muzzle` given nothing but the parameters.
+#[derive(Clone)]
+pub struct ReportOptions<'a> {
+    /// Number and unit label conventions
+    pub locale: Locale,
+    /// Separate thousands the way the locale does
+    pub group_digits: bool,
+    /// Decimals mass, speed and energy are shown with unless rounded
+    pub decimals: usize,
+    /// Primary parameters in display order, those left out are not shown
+    pub order: Vec<Quantity>,
+    /// Rounding of every primary value, given ones included, as a manual or chronograph would print them
+    pub preset: Option<Preset>,
+    /// Increment derived values are rounded to, along with decimals it is written with
+    pub round_to: Option<(f64, usize)>,
+    /// Unit energy is shown in, kilogram-force meters are metric only
+    pub energy_unit: EnergyUnit,
+    /// Show mass in both grains and grams, as air rifle pellets are known by both
+    pub pellet: bool,
+    /// Road speed suffix speed was given with, `kmh` or `mph`, to show speed in as well
+    pub road_speed: Option<&'static str>,
+    /// Load whose momentum the result was derived to match
+    pub matched: Option<&'a Params>,
+    /// Downrange distance as it was given, for labelling downrange estimates
+    pub distance: Option<&'a str>,
+    /// Drag model the ballistic coefficient is given against
+    pub drag_model: DragModel,
+    /// Classify the projectile as subsonic, transonic or supersonic
+    pub sound_regime: bool,
+    /// Air temperature for the sound regime, standard temperature if not given. Classifies as well.
+    pub temperature: Option<f64>,
+    /// Stopping power formula the load was scored with along with its score
+    pub power_score: Option<(PowerFormula, f64)>,
+    /// Show indices relative to `NORMALIZATION_REFERENCE`
+    pub normalize: bool,
+    /// Reference to show energy relative to
+    pub relative_to: Option<&'a Reference>,
+    /// Name an everyday thing in motion with about the same energy
+    pub intuition: bool,
+    /// Practical shooting division to classify the load by
+    pub division: Option<&'a Division>,
+    /// Game class to check energy against
+    pub game: Option<&'a GameClass>,
+    /// Theme to highlight derived values in, plain text if none
+    pub color: Option<ColorTheme>,
+}
+
+impl Default for ReportOptions<'_> {
+    fn default() -> Self {
+        ReportOptions {
+            locale: Locale::EN,
+            group_digits: false,
+            decimals: DEFAULT_DECIMALS,
+            order: Quantity::ALL.to_vec(),
+            preset: None,
+            round_to: None,
+            energy_unit: EnergyUnit::JOULES,
+            pellet: false,
+            road_speed: None,
+            matched: None,
+            distance: None,
+            drag_model: DragModel::G1,
+            sound_regime: false,
+            temperature: None,
+            power_score: None,
+            normalize: false,
+            relative_to: None,
+            intuition: false,
+            division: None,
+            game: None,
+            color: None,
+        }
+    }
+}
+
+impl Params {
+    /// Human readable report of the shot parameters, ending in an empty line
+    pub fn to_human(&self, options: &ReportOptions) -> String {
+        let mut out = String::new();
+        // Writing to a string never fails
+        let _ = self.write_human(&mut out, options);
+        out
+    }
+
+    fn write_human(&self, out: &mut String, options: &ReportOptions) -> std::fmt::Result {
+        let (locale, decimals, energy_unit) = (options.locale, options.decimals, options.energy_unit);
+        let number = |value: f64, decimals: usize| {
+            let formatted = locale.format_number(value, decimals);
+            if options.group_digits { locale.group_digits(&formatted) } else { formatted }
+        };
+
+        let (mass_unit, speed_unit, mut energy_label) = locale.unit_labels(self.units);
+        if energy_unit == EnergyUnit::KGFM {
+            energy_label = "kgf·m";
+        }
+
+        // Values given by the user are displayed as is, derived ones are rounded if asked to.
+        // Rounding presets round everything, given values included, as a manual or chronograph would print them.
+        let show = |quantity: Quantity, value: f64, derived: bool| match (options.preset.and_then(|preset| preset.rounding(quantity, self.units)), options.round_to) {
+            (Some((increment, decimals)), _) => number(round_to_increment(value, increment), decimals),
+            (None, Some((increment, decimals))) if derived => number(round_to_increment(value, increment), decimals),
+            _ => number(value, decimals),
+        };
+        let highlight = |text: String, derived: bool| match options.color {
+            Some(theme) if derived => format!("{}{}{}", theme.highlight(), text, RESET),
+            _ => text,
+        };
+
+        if self.bogus {
+            writeln!(out, "WARNING: {}", BOGUS_WARNING)?;
+        }
+
+        writeln!(out, "Derived shot parameters are:")?;
+        let summary = self.summary();
+        for &(quantity, value, derived) in options.order.iter().filter_map(|quantity| summary.iter().find(|(field, _, _)| field == quantity)) {
+            let value = if quantity == Quantity::ENERGY { energy_unit.from_joules(value) } else { value };
+            let shown = highlight(show(quantity, value, derived), derived);
+            match quantity {
+                // Pellets are known by both, whichever system the rest is in
+                Quantity::MASS if options.pellet => {
+                    let other = self.units.other();
+                    let (other_value, (other_unit, _, _)) = (self.mass_in(other), locale.unit_labels(other));
+                    writeln!(out, "Projectile mass:\t{} {} ({} {})", shown, mass_unit, number(other_value, 3), other_unit)?
+                },
+                Quantity::MASS => writeln!(out, "Projectile mass:\t{} {}", shown, mass_unit)?,
+                Quantity::SPEED => match options.road_speed {
+                    Some("kmh") => writeln!(out, "Projectile speed:\t{} {} ({} km/h)", shown, speed_unit, number(self.speed_kmh(), 1))?,
+                    Some(_) => writeln!(out, "Projectile speed:\t{} {} ({} mph)", shown, speed_unit, number(self.speed_mph(), 1))?,
+                    None => writeln!(out, "Projectile speed:\t{} {}", shown, speed_unit)?,
+                },
+                Quantity::ENERGY => writeln!(out, "Projectile energy:\t{} {}", shown, energy_label)?,
+            }
+        }
+
+        if let Some(consistency) = self.overdetermined {
+            match self.expected_energy {
+                Some(expected) => writeln!(
+                    out,
+                    "Consistency:\t{} (energy {} % off the {} {} mass and speed give)",
+                    consistency,
+                    number((self.energy / expected - 1.0) * 100.0, 2),
+                    number(energy_unit.from_joules(expected), decimals),
+                    energy_label
+                )?,
+                None => writeln!(out, "Consistency:\toverdetermined, mass and speed give no energy to check against")?,
+            }
+        }
+
+        if let Some(reference) = options.matched.map(|reference| reference.in_units(self.units)) {
+            let momentum_unit = match self.units {
+                Units::METRIC => "kg·m/s",
+                Units::IMPERIAL => "lb·ft/s",
+            };
+            writeln!(
+                out,
+                "Matched momentum:\t{} {} (of {} {} at {} {})",
+                number(reference.momentum(), 3), momentum_unit,
+                number(reference.mass, 1), mass_unit,
+                number(reference.speed, 0), speed_unit
+            )?;
+        }
+
+        if let (Some(speed), Some(energy), Some(fraction)) = (self.retained_speed, self.retained_energy, self.retained_fraction) {
+            let at = format!("{} {}", options.distance.unwrap_or_default(), locale.distance_label(self.units));
+            write!(
+                out,
+                "Speed at {}:\t{} {}\nEnergy at {}:\t{} {}\nEnergy retained:\t{} %\n",
+                at, show(Quantity::SPEED, speed, true), speed_unit,
+                at, show(Quantity::ENERGY, energy_unit.from_joules(energy), true), energy_label,
+                number(fraction * 100.0, 1)
+            )?;
+            match self.time_of_flight {
+                Some(time) => writeln!(out, "Time of flight to {}:\t{} s", at, number(time, 3))?,
+                None => writeln!(out, "Time of flight to {}:\tnever, stopped by drag short of it", at)?,
+            }
+            if let (Some(drop), Some(moa), Some(mil)) = (self.drop, self.drop_moa, self.drop_mil) {
+                let unit = match self.units {
+                    Units::METRIC => "cm",
+                    Units::IMPERIAL => "in",
+                };
+                writeln!(
+                    out,
+                    "Drop at {}:\t{} {} ({} MOA, {} mil, from a level bore ignoring sight height and zero)",
+                    at, number(drop, 1), unit, number(moa, 1), number(mil, 2)
+                )?;
+            }
+            if let Some(drift) = self.wind_drift {
+                let unit = match self.units {
+                    Units::METRIC => "cm",
+                    Units::IMPERIAL => "in",
+                };
+                let side = match drift {
+                    d if d > 0.0 => " left",
+                    d if d < 0.0 => " right",
+                    _ => "",
+                };
+                writeln!(out, "Wind drift at {}:\t{} {}{} (lag time rule, steady wind)", at, number(drift.abs(), 1), unit, side)?;
+            }
+        }
+
+        if let Some(density) = self.sectional_density {
+            let unit = match self.units {
+                Units::METRIC => "g/mm²",
+                Units::IMPERIAL => "lb/in²",
+            };
+            writeln!(out, "Sectional density:\t{} {}", number(density, 3), unit)?;
+        }
+
+        if let Some(index) = self.penetration_index {
+            writeln!(out, "Penetration index:\t{} (rough approximation from sectional density and impact speed, ignores bullet construction)", number(index, 2))?;
+        }
+
+        if let Some(proxy) = self.pressure_proxy {
+            let unit = match self.units {
+                Units::METRIC => "MPa",
+                Units::IMPERIAL => "psi",
+            };
+            writeln!(out, "Pressure proxy:\t{} {} (average energy per bore volume, NOT chamber pressure)", number(proxy, 1), unit)?;
+        }
+
+        if let Some(charge) = self.estimated_charge {
+            let unit = match self.units {
+                Units::METRIC => "grams",
+                Units::IMPERIAL => "grains",
+            };
+            writeln!(
+                out,
+                "Estimated charge:\t{} {} (linear scaling from the reference load, not a pressure model, never exceed published maximums)",
+                number(charge, 2),
+                unit
+            )?;
+        }
+
+        if let (Some(velocity), Some(energy)) = (self.recoil_velocity, self.recoil_energy) {
+            writeln!(
+                out,
+                "Recoil:\t{} {}, {} {} (free recoil, powder gas ignored)",
+                number(velocity, 2), speed_unit,
+                number(energy_unit.from_joules(energy), 2), energy_label
+            )?;
+        }
+
+        if let Some(stability) = self.stability {
+            let verdict = match stability {
+                s if s < 1.0 => "unstable",
+                s if s < 1.5 => "marginal",
+                _ => "stable",
+            };
+            writeln!(out, "Gyroscopic stability:\t{} ({}, Miller rule)", number(stability, 2), verdict)?;
+        }
+
+        if let Some(bc) = self.estimated_bc {
+            let model = match options.drag_model {
+                DragModel::G1 => "G1",
+                DragModel::G7 => "G7",
+            };
+            writeln!(out, "Estimated BC:\t{} ({}, from chronograph readings)", number(bc, 3), model)?;
+        }
+
+        if options.sound_regime || options.temperature.is_some() {
+            let (temperature, scale) = match self.units {
+                Units::METRIC => (options.temperature.unwrap_or(STANDARD_TEMPERATURE), "°C"),
+                Units::IMPERIAL => (options.temperature.unwrap_or(Units::METRIC.convert_temperature(STANDARD_TEMPERATURE, Units::IMPERIAL)), "°F"),
+            };
+            writeln!(
+                out,
+                "Sound regime:\t{} (Mach {} at {} {})",
+                self.sound_regime(temperature),
+                number(self.mach(temperature), 2),
+                number(temperature, 1),
+                scale
+            )?;
+        }
+
+        if let Some((formula, score)) = options.power_score {
+            writeln!(out, "{}:\t{}", formula.label(), number(score, 2))?;
+        }
+
+        if options.normalize {
+            writeln!(out, "Indices relative to {}:", NORMALIZATION_REFERENCE.description)?;
+            for (name, ratio) in self.ratios(&NORMALIZATION_REFERENCE) {
+                writeln!(out, "  {}:\t{}", name, number(ratio, 3))?;
+            }
+        }
+
+        if let Some(reference) = options.relative_to {
+            writeln!(
+                out,
+                "Energy relative to {}:\t{} % of {} {} ({}, informational only)",
+                reference.name,
+                number(self.energy_ratio(reference) * 100.0, 1),
+                number(energy_unit.from_joules(reference.energy(self.units)), 1),
+                energy_label,
+                reference.description
+            )?;
+        }
+
+        if options.intuition {
+            if let Some(nearest) = self.nearest_reference(INTUITION_REFERENCES) {
+                writeln!(
+                    out,
+                    "Intuitively:\troughly equivalent to {} ({} {}, just for fun).",
+                    nearest.description,
+                    number(energy_unit.from_joules(nearest.energy(self.units)), 1),
+                    energy_label
+                )?;
+            }
+        }
+
+        if let Some(division) = options.division {
+            let floors = match division.major {
+                Some(major) => format!("major {}, minor {}", major, division.minor),
+                None => format!("minor {}, no major", division.minor),
+            };
+            writeln!(
+                out,
+                "Division {}:\t{} at power factor {} ({} floors: {}, informational only)",
+                division.name,
+                division.classify(self),
+                number(self.power_factor(), 1),
+                division.description,
+                floors
+            )?;
+        }
+
+        if let Some(class) = options.game {
+            writeln!(
+                out,
+                "Energy for {}:\t{}, minimum {} {} for {} (informational only, check local regulations)",
+                class.name,
+                if self.meets(class) { "PASS" } else { "FAIL" },
+                number(energy_unit.from_joules(class.threshold(self.units)), 0),
+                energy_label,
+                class.description
+            )?;
+        }
+
+        writeln!(out)
+    }
+}
+
+/// What two loads are compared by
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Criterion {
+    MASS,
+    SPEED,
+    ENERGY,
+    MOMENTUM,
+    PF,
+}
+
+impl Criterion {
+    /// Which way the metric is better
+    pub fn better(self) -> Better {
+        match self {
+            Criterion::SPEED | Criterion::ENERGY | Criterion::MOMENTUM => Better::HIGHER,
+            Criterion::MASS | Criterion::PF => Better::NEITHER,
+        }
+    }
+}
+
+/// Which way a compared metric is better, if either
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Better {
+    /// More of it is better, like energy and momentum
+    HIGHER,
+    /// Depends on what the load is for, like mass and power factor, so no winner is picked
+    NEITHER,
+}
+
+/// Which of two compared loads wins a metric
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Winner {
+    /// The load compared
+    FIRST,
+    /// The load it is compared to
+    SECOND,
+    /// Neither, both have the same value
+    EVEN,
+    /// The metric is not better either way
+    UNJUDGED,
+}
+
+/// One metric of two compared loads
+#[derive(Clone, Copy, Debug)]
+pub struct Compared {
+    pub criterion: Criterion,
+    pub first: f64,
+    pub second: f64,
+}
+
+impl Compared {
+    /// Load with the better value, if the metric has a better way
+    pub fn winner(&self) -> Winner {
+        match self.criterion.better() {
+            Better::NEITHER => Winner::UNJUDGED,
+            Better::HIGHER if self.first > self.second => Winner::FIRST,
+            Better::HIGHER if self.first < self.second => Winner::SECOND,
+            Better::HIGHER => Winner::EVEN,
+        }
+    }
+}
+
+impl Params {
+    /// Mass, speed, energy, momentum and power factor of this load next to those of `other`,
+    /// converted to the units of this one
+    pub fn compare(&self, other: &Params) -> [Compared; 5] {
+        let other = other.in_units(self.units);
+        let compared = |criterion, first, second| Compared { criterion, first, second };
+        [
+            compared(Criterion::MASS, self.mass, other.mass),
+            compared(Criterion::SPEED, self.speed, other.speed),
+            compared(Criterion::ENERGY, self.energy, other.energy),
+            compared(Criterion::MOMENTUM, self.momentum(), other.momentum()),
+            compared(Criterion::PF, self.power_factor(), other.power_factor()),
+        ]
+    }
+
+    /// Human readable comparison of this load and `other` metric by metric, naming the better
+    /// of each that has one and coloring its value if `options.color` is set, ending in an empty line
+    pub fn to_comparison(&self, other: &Params, options: &ReportOptions) -> String {
+        let (locale, decimals, energy_unit) = (options.locale, options.decimals, options.energy_unit);
+        let number = |value: f64| {
+            let formatted = locale.format_number(value, decimals);
+            if options.group_digits { locale.group_digits(&formatted) } else { formatted }
+        };
+
+        let (mass_unit, speed_unit, mut energy_label) = locale.unit_labels(self.units);
+        if energy_unit == EnergyUnit::KGFM {
+            energy_label = "kgf·m";
+        }
+        let momentum_unit = match self.units {
+            Units::METRIC => "kg·m/s",
+            Units::IMPERIAL => "lb·ft/s",
+        };
+
+        let mut out = String::from("Compared loads:\tfirst\tsecond\tbetter\n");
+        for compared in self.compare(other) {
+            let (label, scale) = match compared.criterion {
+                Criterion::MASS => (format!("Mass ({})", mass_unit), 1.0),
+                Criterion::SPEED => (format!("Speed ({})", speed_unit), 1.0),
+                Criterion::ENERGY => (format!("Energy ({})", energy_label), energy_unit.from_joules(1.0)),
+                Criterion::MOMENTUM => (format!("Momentum ({})", momentum_unit), 1.0),
+                Criterion::PF => ("Power factor".to_owned(), 1.0),
+            };
+            let winner = compared.winner();
+            let shown = |value: f64, wins: bool| match options.color {
+                Some(theme) if wins => format!("{}{}{}", theme.better(), number(value * scale), RESET),
+                _ => number(value * scale),
+            };
+            let verdict = match winner {
+                Winner::FIRST => "first",
+                Winner::SECOND => "second",
+                Winner::EVEN => "even",
+                Winner::UNJUDGED => "not judged",
+            };
+            out.push_str(&format!(
+                "{}:\t{}\t{}\t{}\n",
+                label,
+                shown(compared.first, winner == Winner::FIRST),
+                shown(compared.second, winner == Winner::SECOND),
+                verdict
+            ));
+        }
+        out.push('\n');
+        out
+    }
+}
@@ -0,0 +1,89 @@
+//! JSON Schema of the document `Params::to_json` writes, for consumers generating types from it.
+
+use crate::{Quantity, Units, SCHEMA_VERSION};
+use serde_json::json;
+
+/// Kind of value a field holds, units of numbers by measurment system
+// Variants are spelled like those of the public enums
+#[allow(clippy::upper_case_acronyms)]
+enum Kind {
+    /// Whole number
+    INTEGER,
+    /// One of given strings
+    NAME(&'static [&'static str]),
+    /// `true` or `false`
+    FLAG,
+    /// Number in the unit of a primary quantity
+    QUANTITY(Quantity),
+    /// Number in given metric and imperial units, empty for plain numbers
+    NUMBER(&'static str, &'static str),
+}
+
+/// Field of the document: name, kind, whether it may be `null` and what it holds
+const FIELDS: &[(&str, Kind, bool, &str)] = &[
+    ("schema_version", Kind::INTEGER, false, "version of the document shape, see SCHEMA_VERSION"),
+    ("units", Kind::NAME(&["metric", "imperial"]), false, "measurment system every number is in"),
+    ("mass", Kind::QUANTITY(Quantity::MASS), false, "mass of the projectile"),
+    ("speed", Kind::QUANTITY(Quantity::SPEED), false, "speed of the projectile"),
+    ("energy", Kind::QUANTITY(Quantity::ENERGY), false, "energy of the projectile"),
+    ("bogus", Kind::FLAG, false, "all three primary parameters were given and nothing was derived"),
+    ("derived", Kind::NAME(&["mass", "speed", "energy"]), true, "quantity computed from the other two"),
+    ("overdetermined", Kind::NAME(&["consistent", "inconsistent", "overdetermined"]), true, "agreement of the three primary parameters when all were given"),
+    ("expected_energy", Kind::QUANTITY(Quantity::ENERGY), true, "energy mass and speed give, that the given one was judged against"),
+    ("retained_speed", Kind::QUANTITY(Quantity::SPEED), true, "speed left at the downrange distance"),
+    ("retained_energy", Kind::QUANTITY(Quantity::ENERGY), true, "energy left at the downrange distance"),
+    ("retained_fraction", Kind::NUMBER("", ""), true, "downrange energy as a fraction of muzzle energy, 0 to 1"),
+    ("time_of_flight", Kind::NUMBER("s", "s"), true, "time of flight to the downrange distance"),
+    ("drop", Kind::NUMBER("cm", "in"), true, "drop below the line of a level bore at the downrange distance"),
+    ("drop_moa", Kind::NUMBER("MOA", "MOA"), true, "drop as an angle, true minutes of angle"),
+    ("drop_mil", Kind::NUMBER("mil", "mil"), true, "drop as an angle, milliradians"),
+    ("wind_drift", Kind::NUMBER("cm", "in"), true, "wind drift at the downrange distance, positive to the left"),
+    ("estimated_bc", Kind::NUMBER("lb/in²", "lb/in²"), true, "ballistic coefficient implied by chronograph readings"),
+    ("estimated_bc_iterations", Kind::INTEGER, true, "bisection steps the ballistic coefficient estimate took"),
+    ("estimated_bc_residual", Kind::QUANTITY(Quantity::SPEED), true, "far chronograph speed the estimated ballistic coefficient gives minus the one read"),
+    ("sectional_density", Kind::NUMBER("g/mm²", "lb/in²"), true, "sectional density"),
+    ("penetration_index", Kind::NUMBER("", ""), true, "crude penetration index, for comparing loads only"),
+    ("pressure_proxy", Kind::NUMBER("MPa", "psi"), true, "muzzle energy per bore volume, not chamber pressure"),
+    ("estimated_charge", Kind::QUANTITY(Quantity::MASS), true, "powder charge scaled from a reference load"),
+    ("recoil_velocity", Kind::QUANTITY(Quantity::SPEED), true, "free recoil speed of the firearm"),
+    ("recoil_energy", Kind::QUANTITY(Quantity::ENERGY), true, "free recoil energy of the firearm"),
+    ("stability", Kind::NUMBER("", ""), true, "Miller gyroscopic stability factor"),
+];
+
+/// JSON Schema (draft 2020-12) of the document `Params::to_json` writes for the current
+/// `SCHEMA_VERSION`. Every field is always present, optional ones are `null` when not estimated.
+/// Numbers carry their unit in each measurment system under a non-standard `x-unit` keyword.
+pub fn json_schema() -> serde_json::Value {
+    let mut properties = serde_json::Map::new();
+    for (name, kind, optional, description) in FIELDS {
+        let (kind, names, (metric, imperial)): (_, &[&str], _) = match kind {
+            Kind::INTEGER => ("integer", &[], ("", "")),
+            Kind::NAME(names) => ("string", names, ("", "")),
+            Kind::FLAG => ("boolean", &[], ("", "")),
+            Kind::QUANTITY(quantity) => ("number", &[], (quantity.unit(Units::METRIC), quantity.unit(Units::IMPERIAL))),
+            Kind::NUMBER(metric, imperial) => ("number", &[], (*metric, *imperial)),
+        };
+
+        let mut property = json!({ "type": if *optional { json!([kind, "null"]) } else { json!(kind) }, "description": description });
+        if !names.is_empty() {
+            let mut names: Vec<serde_json::Value> = names.iter().map(|name| json!(name)).collect();
+            if *optional {
+                names.push(serde_json::Value::Null);
+            }
+            property["enum"] = json!(names);
+        }
+        if !metric.is_empty() {
+            property["x-unit"] = json!({ "metric": metric, "imperial": imperial });
+        }
+        properties.insert(name.to_string(), property);
+    }
+
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "muzzle shot parameters",
+        "description": format!("Document written by Params::to_json, schema version {}", SCHEMA_VERSION),
+        "type": "object",
+        "properties": properties,
+        "required": FIELDS.iter().map(|(name, ..)| name).collect::<Vec<_>>(),
+    })
+}
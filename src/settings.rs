@@ -0,0 +1,126 @@
+//! User preferences kept in a TOML file, applied beneath command line flags.
+//!
+//! Every setting is layered the same way: a command line flag wins over the settings file, which
+//! wins over the built-in default. E.g. with `default_units = "imperial"` in the file, `muzzle`
+//! works in imperial units, `muzzle --metric` in metric ones and without the file it is metric.
+//! Likewise `default_format = "json"` is overridden by `--format` (or any other output flag),
+//! `decimals = 1` by `--decimals` and `color = "never"` or `color_theme = "light"` by `--color`
+//! and `--color-theme`.
+//!
+//! Reading the file takes the `settings` feature, on by default, as it is all `toml` is needed
+//! for. Built without it `load` finds no settings, while the layering stays the same for the
+//! library's users.
+
+use crate::{find_game_class, ColorChoice, ColorTheme, GameClass, MuzzleError, OutputFormat, Units, GAME_CLASSES};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::env;
+#[cfg(feature = "settings")]
+use std::fs;
+#[cfg(feature = "settings")]
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Decimals primary parameters are shown with in human output unless set otherwise
+pub const DEFAULT_DECIMALS: usize = 3;
+
+/// Preferences read from the settings file, `None` for anything not set there
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Settings {
+    /// Measurment system used unless `--imperial` or `--metric` is given
+    pub default_units: Option<Units>,
+    /// Output format used unless one is asked for on the command line
+    pub default_format: Option<OutputFormat>,
+    /// Decimals primary parameters are shown with in human output unless `--decimals` is given
+    pub decimals: Option<usize>,
+    /// When to color output unless `--color` is given
+    pub color: Option<ColorChoice>,
+    /// Colors to use unless `--color-theme` is given
+    pub color_theme: Option<ColorTheme>,
+    /// Minimum muzzle energies in joules replacing those of built-in game classes, by class name,
+    /// e.g. `deer = 1700` under a `[game_energy]` table
+    pub game_energy: BTreeMap<String, f64>,
+}
+
+impl Settings {
+    /// Units to work in: `flag` if one was given, else the settings file default, else metric
+    pub fn units(&self, flag: Option<Units>) -> Units {
+        flag.or(self.default_units).unwrap_or_default()
+    }
+
+    /// Output format to use: `flag` if one was given, else the settings file default, else human
+    pub fn format(&self, flag: Option<OutputFormat>) -> OutputFormat {
+        flag.or(self.default_format).unwrap_or_default()
+    }
+
+    /// Decimals to show: `flag` if one was given, else the settings file default, else `DEFAULT_DECIMALS`
+    pub fn decimals(&self, flag: Option<usize>) -> usize {
+        flag.or(self.decimals).unwrap_or(DEFAULT_DECIMALS)
+    }
+
+    /// When to color: `flag` if one was given, else the settings file default, else in terminals only
+    pub fn color(&self, flag: Option<ColorChoice>) -> ColorChoice {
+        flag.or(self.color).unwrap_or_default()
+    }
+
+    /// Colors to use: `flag` if one was given, else the settings file default, else those for a dark background
+    pub fn color_theme(&self, flag: Option<ColorTheme>) -> ColorTheme {
+        flag.or(self.color_theme).unwrap_or_default()
+    }
+
+    /// Built-in game class of given name with its minimum energy as set in the settings file, if it is
+    pub fn game_class(&self, name: &str) -> Option<GameClass> {
+        let class = find_game_class(GAME_CLASSES, name)?;
+        let min_energy = self
+            .game_energy
+            .iter()
+            .find(|(overridden, _)| overridden.eq_ignore_ascii_case(class.name))
+            .map_or(class.min_energy, |(_, &energy)| energy);
+
+        Some(GameClass { min_energy, ..*class })
+    }
+}
+
+/// Location of the settings file in the user's config directory:
+/// `$XDG_CONFIG_HOME/muzzle/config.toml`, falling back to `$HOME/.config/muzzle/config.toml`.
+/// Returns `None` if neither variable is set.
+pub fn default_path() -> Option<PathBuf> {
+    let config_dir = match env::var_os("XDG_CONFIG_HOME").filter(|dir| !dir.is_empty()) {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(env::var_os("HOME").filter(|dir| !dir.is_empty())?).join(".config"),
+    };
+
+    Some(config_dir.join("muzzle").join("config.toml"))
+}
+
+/// Reads settings from the file at `path`. Missing file is treated as no settings at all.
+#[cfg(feature = "settings")]
+pub fn load(path: &Path) -> Result<Settings, MuzzleError> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Settings::default()),
+        Err(error) => return Err(MuzzleError::IO(error)),
+    };
+
+    let settings: Settings = toml::from_str(&contents)
+        .map_err(|error| MuzzleError::INVALID(format!("Incorrect settings file `{}`. {}", path.display(), error.message())))?;
+
+    for (name, &energy) in &settings.game_energy {
+        if find_game_class(GAME_CLASSES, name).is_none() {
+            return Err(MuzzleError::INVALID(format!("Incorrect settings file `{}`. Unknown game class `{}`.", path.display(), name)));
+        }
+        let valid_energy = energy > 0.0 && energy.is_finite();
+        if !valid_energy {
+            return Err(MuzzleError::INVALID(format!("Incorrect settings file `{}`. Energy for `{}` must be positive.", path.display(), name)));
+        }
+    }
+
+    Ok(settings)
+}
+
+/// Built without the `settings` feature there is no settings file to read
+#[cfg(not(feature = "settings"))]
+pub fn load(_path: &Path) -> Result<Settings, MuzzleError> {
+    Ok(Settings::default())
+}
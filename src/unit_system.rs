@@ -0,0 +1,76 @@
+//! Conversion factors of measurment systems, for derivations that work the same in every one.
+//!
+//! Kinetic quantities follow the same formulas in every system and only differ by the factors
+//! here, so adding a system takes an implementation rather than another arm in each of them.
+//! Figures reported in units of their own (drop, sectional density and the like) still match
+//! on `Units`.
+
+use crate::{Units, GEE_FPS, GRAINS_IN_POUND, GRAMS_IN_KILO, METERS_IN_FOOT, METERS_IN_YARD};
+
+/// Factors relating units of a measurment system to the base units of kinetic formulas and to SI.
+pub trait UnitSystem {
+    /// Mass units (grams, grains) in one unit of the mass energy is reckoned in (kilogram, pound)
+    fn mass_scale(&self) -> f64;
+
+    /// Mass in those units times acceleration in speed units per second that makes one unit of
+    /// force energy is reckoned in: 1 for newtons, standard gravity for pounds-force
+    fn force_scale(&self) -> f64;
+
+    /// Meters in the length unit of speed (meter, foot)
+    fn meters_in_length(&self) -> f64;
+
+    /// Meters in the unit of downrange distance (meter, yard)
+    fn meters_in_distance(&self) -> f64;
+}
+
+/// Grams, meters per second and joules, distances in meters
+pub struct Metric;
+
+/// Grains, feet per second and foot-pounds, distances in yards
+pub struct Imperial;
+
+impl UnitSystem for Metric {
+    fn mass_scale(&self) -> f64 {
+        GRAMS_IN_KILO
+    }
+
+    fn force_scale(&self) -> f64 {
+        1.0
+    }
+
+    fn meters_in_length(&self) -> f64 {
+        1.0
+    }
+
+    fn meters_in_distance(&self) -> f64 {
+        1.0
+    }
+}
+
+impl UnitSystem for Imperial {
+    fn mass_scale(&self) -> f64 {
+        GRAINS_IN_POUND
+    }
+
+    fn force_scale(&self) -> f64 {
+        GEE_FPS
+    }
+
+    fn meters_in_length(&self) -> f64 {
+        METERS_IN_FOOT
+    }
+
+    fn meters_in_distance(&self) -> f64 {
+        METERS_IN_YARD
+    }
+}
+
+impl Units {
+    /// Conversion factors of the measurment system
+    pub fn system(self) -> &'static dyn UnitSystem {
+        match self {
+            Units::METRIC => &Metric,
+            Units::IMPERIAL => &Imperial,
+        }
+    }
+}
@@ -0,0 +1,30 @@
+//! Appending CSV records to an existing file with `--append`.
+
+mod common;
+
+use common::stdout;
+use std::env;
+use std::fs;
+
+/// Runs the binary writing CSV to `path`
+fn append_csv(path: &str, speed: &str) {
+    stdout(&["-m", "10", "-s", speed, "--csv-out", path, "--append"]);
+}
+
+#[test]
+fn header_is_written_once() {
+    let path = env::temp_dir().join(format!("muzzle-append-{}.csv", std::process::id()));
+    let _ = fs::remove_file(&path);
+    let path_str = path.to_str().unwrap();
+
+    append_csv(path_str, "800");
+    append_csv(path_str, "900");
+    let contents = fs::read_to_string(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    let lines: Vec<&str> = contents.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].starts_with("units,mass,speed,energy"));
+    assert!(lines[1].starts_with("metric,10,800,3200,"));
+    assert!(lines[2].starts_with("metric,10,900,4050,"));
+}
@@ -0,0 +1,33 @@
+//! Energy relative to body armor test threats, `--relative-to`.
+
+mod common;
+
+use common::muzzle;
+use muzzle::{find_reference, run, Config, Units, ARMOR_REFERENCES};
+
+#[test]
+fn ratio_against_known_baseline() {
+    let reference = find_reference(ARMOR_REFERENCES, "NIJ-IIIA").unwrap();
+    // 15.6 g at 436 m/s
+    let baseline = 0.5 * 0.0156 * 436.0f64.powi(2);
+    assert!((reference.energy(Units::METRIC) - baseline).abs() < 1e-9);
+
+    let params = run(Config { units: Units::METRIC, mass: Some("10".to_owned()), speed: Some("800".to_owned()), ..Config::default() }).unwrap();
+    assert!((params.energy_ratio(reference) - 3200.0 / baseline).abs() < 1e-12);
+
+    // The reference itself, in either system
+    for units in Units::ALL {
+        assert!((reference.params(units).energy_ratio(reference) - 1.0).abs() < 1e-12);
+    }
+    assert!(find_reference(ARMOR_REFERENCES, "nij-v").is_none());
+}
+
+#[test]
+fn cli_prints_percentage() {
+    let output = muzzle(&["-m", "10", "-s", "800", "--relative-to", "nij-iiia"]);
+    assert!(output.status.success());
+
+    let report = String::from_utf8(output.stdout).unwrap();
+    assert!(report.contains("Energy relative to nij-iiia:\t215.8 % of 1482.7 Joules"), "{}", report);
+    assert!(report.contains("informational only"));
+}
@@ -0,0 +1,38 @@
+//! Missing parameters filled in with assumed values, `--assume`.
+
+mod common;
+
+use common::muzzle;
+use serde_json::Value;
+use std::process::Output;
+
+fn json(output: &Output) -> Value {
+    assert!(output.status.success());
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+#[test]
+fn one_value_and_an_assumption_derive_the_third() {
+    let output = muzzle(&["-f", "json", "-s", "800", "--assume", "mass=10"]);
+    let document = json(&output);
+
+    assert_eq!(document["mass"], 10.0);
+    assert_eq!(document["energy"], 3200.0);
+    assert_eq!(document["derived"], "energy");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("NOTE: mass was not given, assuming 10."));
+}
+
+#[test]
+fn assumptions_only_fill_gaps() {
+    // Two values given already, the assumption is not needed and not noted
+    let output = muzzle(&["-f", "json", "-m", "12", "-s", "800", "--assume", "mass=10"]);
+    assert_eq!(json(&output)["mass"], 12.0);
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("NOTE"));
+
+    // Only as many as needed, in the order given
+    let document = json(&muzzle(&["-f", "json", "-s", "800", "--assume", "mass=10", "--assume", "energy=1"]));
+    assert_eq!(document["energy"], 3200.0);
+
+    assert!(!muzzle(&["-s", "800"]).status.success());
+    assert!(!muzzle(&["-s", "800", "--assume", "weight=10"]).status.success());
+}
@@ -0,0 +1,74 @@
+//! Ballistic coefficient estimate from two chronograph readings, its iteration cap and diagnostics.
+
+mod common;
+
+use common::muzzle;
+use muzzle::{run, Config, MuzzleError, Units, BC_ITERATIONS};
+
+fn chronograph(v2: &str, d2: &str, max_iterations: Option<&str>) -> Config {
+    Config {
+        units: Units::IMPERIAL,
+        mass: Some("150".to_owned()),
+        speed: Some("2800".to_owned()),
+        v1: Some("2800".to_owned()),
+        d1: Some("0".to_owned()),
+        v2: Some(v2.to_owned()),
+        d2: Some(d2.to_owned()),
+        max_iterations: max_iterations.map(str::to_owned),
+        ..Config::default()
+    }
+}
+
+#[test]
+fn reports_iterations_and_residual() {
+    let result = run(chronograph("2600", "100", None)).unwrap();
+    let iterations = result.estimated_bc_iterations.unwrap();
+    assert!(iterations > 0 && iterations <= BC_ITERATIONS, "{}", iterations);
+    assert!(result.estimated_bc_residual.unwrap().abs() < 0.01, "{:?}", result.estimated_bc_residual);
+
+    let json: serde_json::Value = serde_json::from_str(&result.to_json()).unwrap();
+    assert_eq!(json["estimated_bc_iterations"], iterations);
+    assert!((json["estimated_bc_residual"].as_f64().unwrap() - result.estimated_bc_residual.unwrap()).abs() < 1e-9);
+}
+
+#[test]
+fn too_few_iterations_fail_to_converge() {
+    match run(chronograph("2600", "100", Some("2"))) {
+        Err(MuzzleError::INVALID(message)) => assert!(message.contains("did not converge within 2 iterations"), "{}", message),
+        _ => panic!("a capped estimate must not pass for a result"),
+    }
+
+    let output = muzzle(&["--imperial", "-m", "150", "-s", "2800", "--v1", "2800", "--d1", "0", "--v2", "2600", "--d2", "100", "--max-iterations", "2"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("did not converge"));
+}
+
+#[test]
+fn unreachable_readings_and_bad_caps_are_rejected() {
+    // Losing nearly all speed within a yard takes a coefficient below any searched
+    assert!(matches!(run(chronograph("100", "1", None)), Err(MuzzleError::INVALID(_))));
+    for cap in ["0", "-3", "2.5", "many"] {
+        assert!(matches!(run(chronograph("2600", "100", Some(cap))), Err(MuzzleError::INVALID(_))), "{}", cap);
+    }
+}
+
+#[test]
+fn recovers_known_coefficient() {
+    for units in [Units::IMPERIAL, Units::METRIC] {
+        let shot = Config { units, mass: Some("10".to_owned()), speed: Some("850".to_owned()), bc: Some("0.4".to_owned()), distance: Some("100".to_owned()), ..Config::default() };
+        let far = run(shot).unwrap().retained_speed.unwrap();
+
+        let readings = Config {
+            units,
+            mass: Some("10".to_owned()),
+            speed: Some("850".to_owned()),
+            v1: Some("850".to_owned()),
+            d1: Some("0".to_owned()),
+            v2: Some(far.to_string()),
+            d2: Some("100".to_owned()),
+            ..Config::default()
+        };
+        let bc = run(readings).unwrap().estimated_bc.unwrap();
+        assert!((bc - 0.4).abs() < 1e-6, "{}", bc);
+    }
+}
@@ -0,0 +1,46 @@
+//! Packed binary records, `--binary`.
+
+mod common;
+
+use common::muzzle;
+use muzzle::{run, Config, Params, Units, PACKED_LEN};
+
+#[test]
+fn bytes_round_trip() {
+    let derived = run(Config { units: Units::IMPERIAL, mass: Some("150".to_owned()), speed: Some("2800".to_owned()), ..Config::default() }).unwrap();
+    let given = run(Config {
+        units: Units::METRIC,
+        mass: Some("10".to_owned()),
+        speed: Some("800".to_owned()),
+        energy: Some("3200".to_owned()),
+        ..Config::default()
+    })
+    .unwrap();
+
+    for params in [derived, given] {
+        let bytes = params.to_bytes();
+        assert_eq!(bytes.len(), PACKED_LEN);
+        let back = Params::from_bytes(&bytes).unwrap();
+        assert_eq!((back.units, back.mass, back.speed, back.energy, back.bogus), (params.units, params.mass, params.speed, params.energy, params.bogus));
+        assert_eq!(back.to_bytes(), bytes);
+    }
+}
+
+#[test]
+fn malformed_records_are_rejected() {
+    let bytes = run(Config { units: Units::METRIC, mass: Some("10".to_owned()), speed: Some("800".to_owned()), ..Config::default() }).unwrap().to_bytes();
+
+    assert!(Params::from_bytes(&bytes[..PACKED_LEN - 1]).is_err());
+    let mut units = bytes;
+    units[24] = 2;
+    assert!(Params::from_bytes(&units).is_err());
+}
+
+#[test]
+fn cli_writes_the_record() {
+    let output = muzzle(&["-b", "-m", "10", "-s", "800"]);
+    assert!(output.status.success());
+
+    let params = Params::from_bytes(&output.stdout).unwrap();
+    assert_eq!((params.units, params.mass, params.speed, params.energy), (Units::METRIC, 10.0, 800.0, 3200.0));
+}
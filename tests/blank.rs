@@ -0,0 +1,86 @@
+//! Blank option values, as scripts pass empty variables, taken as not given.
+
+mod common;
+
+use common::stdout;
+use muzzle::{run, Config, Quantity, Units};
+
+#[test]
+fn blank_config_value_is_unset() {
+    let config = Config {
+        units: Units::METRIC,
+        mass: Some(" ".to_owned()),
+        speed: Some("800".to_owned()),
+        energy: Some("3200".to_owned()),
+        bc: Some("".to_owned()),
+        ..Config::default()
+    };
+    let params = run(config).unwrap();
+
+    assert_eq!(params.derived, Some(Quantity::MASS));
+    assert_eq!(params.retained_speed, None);
+}
+
+/// Checks that `option` given blank reads as if it was left out of `args`
+fn omitted(option: &str, args: &[&str]) {
+    for blank in ["", " "] {
+        assert_eq!(stdout(&[&[option, blank][..], args].concat()), stdout(args), "{} {:?}", option, blank);
+    }
+}
+
+#[test]
+fn blank_iteration_cap_is_the_default() {
+    let config = Config {
+        units: Units::IMPERIAL,
+        mass: Some("150".to_owned()),
+        speed: Some("2800".to_owned()),
+        v1: Some("2800".to_owned()),
+        d1: Some("0".to_owned()),
+        v2: Some("2600".to_owned()),
+        d2: Some("100".to_owned()),
+        max_iterations: Some(" ".to_owned()),
+        ..Config::default()
+    };
+    let default = run(Config { max_iterations: None, ..config.clone() }).unwrap();
+    assert_eq!(run(config).unwrap().estimated_bc_iterations, default.estimated_bc_iterations);
+}
+
+#[test]
+fn blank_mass_flag_is_omitted() {
+    omitted("-m", &["-s", "800", "-e", "3200"]);
+}
+
+#[test]
+fn blank_temp_is_omitted() {
+    omitted("--temp", &["-m", "150", "-s", "2800"]);
+}
+
+#[test]
+fn blank_match_load_is_omitted() {
+    omitted("--match-load", &["-m", "150", "-s", "2800"]);
+}
+
+#[test]
+fn blank_max_iterations_is_omitted() {
+    omitted("--max-iterations", &["-i", "-m", "150", "-s", "2800", "--v1", "2800", "--d1", "0", "--v2", "2600", "--d2", "100"]);
+}
+
+#[test]
+fn blank_decimals_is_omitted() {
+    omitted("--decimals", &["-m", "10", "-s", "800"]);
+}
+
+#[test]
+fn blank_round_to_is_omitted() {
+    omitted("--round-to", &["-m", "10", "-e", "3300", "--preset", "chrono"]);
+}
+
+#[test]
+fn blank_limit_sig_digits_is_omitted() {
+    omitted("--limit-sig-digits", &["-p", "-m", "10.123456", "-s", "800"]);
+}
+
+#[test]
+fn blank_trajectory_samples_is_omitted() {
+    omitted("--trajectory-samples", &["-m", "10", "-s", "800", "--bc", "0.4", "--distance", "300"]);
+}
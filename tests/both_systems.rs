@@ -0,0 +1,35 @@
+//! Results reported in both measurement systems at once, `--both-systems`.
+
+mod common;
+
+use common::stdout;
+use muzzle::{run, Config, Units};
+use serde_json::Value;
+
+#[test]
+fn json_blocks_are_consistent_conversions() {
+    let params = run(Config { units: Units::METRIC, mass: Some("10".to_owned()), speed: Some("800".to_owned()), ..Config::default() }).unwrap();
+    let document: Value = serde_json::from_str(&params.to_json_both_systems(false)).unwrap();
+
+    let metric: Value = serde_json::from_str(&params.to_json()).unwrap();
+    assert_eq!(document["metric"], metric);
+
+    let imperial = &document["imperial"];
+    let field = |block: &Value, name: &str| block[name].as_f64().unwrap();
+    assert_eq!(imperial["units"], "imperial");
+    assert!((field(imperial, "mass") - 10.0 * 15.432358352941431).abs() < 1e-9);
+    assert!((field(imperial, "speed") - 800.0 / 0.3048).abs() < 1e-9);
+    assert!((field(imperial, "energy") - 3200.0 / 1.3558179483314004).abs() < 1e-9);
+}
+
+#[test]
+fn human_output_has_both_blocks() {
+    let report = stdout(&["-i", "-m", "150", "-s", "2800", "--both-systems"]);
+    let metric = report.find("Metric:").unwrap();
+    let imperial = report.find("Imperial:").unwrap();
+
+    assert!(report[metric..imperial].contains("Projectile mass:\t9.720 grams"));
+    assert!(report[metric..imperial].contains("Projectile speed:\t853.440 m/s"));
+    assert!(report[imperial..].contains("Projectile mass:\t150.000 grains"));
+    assert!(report[imperial..].contains("Projectile speed:\t2800.000 FPS"));
+}
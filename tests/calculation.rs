@@ -0,0 +1,25 @@
+//! Calculations keeping their inputs, derived quantity and warnings.
+
+use muzzle::{calculate, Config, Quantity, Units};
+
+#[test]
+fn records_derived_quantity() {
+    let config = Config { units: Units::METRIC, mass: Some("10".to_owned()), energy: Some("3200".to_owned()), ..Config::default() };
+    let calculation = calculate(config).unwrap();
+
+    assert_eq!(calculation.derived, Some(Quantity::SPEED));
+    assert_eq!(calculation.params.derived, Some(Quantity::SPEED));
+    assert_eq!(calculation.config.energy.as_deref(), Some("3200"));
+    assert!(calculation.warnings.is_empty());
+}
+
+#[test]
+fn carries_warnings() {
+    // Likely FPS given as metric
+    let config = Config { units: Units::METRIC, mass: Some("10".to_owned()), speed: Some("2800".to_owned()), ..Config::default() };
+    let calculation = calculate(config).unwrap();
+
+    assert_eq!(calculation.derived, Some(Quantity::ENERGY));
+    assert_eq!(calculation.warnings.len(), 1);
+    assert!(calculation.warnings[0].contains("2800"));
+}
@@ -0,0 +1,29 @@
+//! Informational speed ceilings and diameters of common cartridges, `--caliber`.
+
+mod common;
+
+use common::muzzle;
+use muzzle::{find_caliber, Units, CALIBERS};
+
+#[test]
+fn looks_up_cartridges() {
+    let caliber = find_caliber(CALIBERS, "308WIN").unwrap();
+    assert_eq!(caliber.description, ".308 Winchester");
+    assert!((caliber.diameter(Units::IMPERIAL) - 0.308).abs() < 0.001);
+    assert!(find_caliber(CALIBERS, "308").is_none());
+}
+
+#[test]
+fn warns_above_ceiling_only() {
+    let fast = muzzle(&["-i", "-m", "150", "-s", "3400", "--caliber", "308win"]);
+    assert!(fast.status.success());
+    assert!(String::from_utf8_lossy(&fast.stderr).contains("is above the typical top of 3215 FPS for .308 Winchester"));
+
+    let typical = muzzle(&["-i", "-m", "150", "-s", "2800", "--caliber", "308win"]);
+    assert!(typical.status.success());
+    assert!(typical.stderr.is_empty(), "{}", String::from_utf8_lossy(&typical.stderr));
+
+    let unknown = muzzle(&["-i", "-m", "150", "-s", "2800", "--caliber", "308"]);
+    assert_eq!(unknown.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&unknown.stderr).contains("unknown caliber `308`"));
+}
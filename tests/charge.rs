@@ -0,0 +1,37 @@
+//! Charge roughly scaled from a reference load, `ref_charge` with both velocities.
+
+use muzzle::{run_with_diagnostics, Config, MuzzleError, Units};
+
+fn charge(target: &str) -> (Result<Option<f64>, MuzzleError>, Vec<String>) {
+    let config = Config {
+        units: Units::IMPERIAL,
+        mass: Some("150".to_owned()),
+        speed: Some("2800".to_owned()),
+        ref_charge: Some("44.0".to_owned()),
+        ref_velocity: Some("2800".to_owned()),
+        target_velocity: Some(target.to_owned()),
+        ..Config::default()
+    };
+    let mut warnings = Vec::new();
+    (run_with_diagnostics(config, &mut warnings).map(|params| params.estimated_charge), warnings)
+}
+
+#[test]
+fn scales_linearly() {
+    let (lower, warnings) = charge("2700");
+    assert!((lower.unwrap().unwrap() - 44.0 * 2700.0 / 2800.0).abs() < 1e-9);
+    assert!(warnings.is_empty());
+
+    let (same, _) = charge("2800");
+    assert_eq!(same.unwrap(), Some(44.0));
+}
+
+#[test]
+fn warns_past_known_data() {
+    let (higher, warnings) = charge("2900");
+    assert!((higher.unwrap().unwrap() - 44.0 * 2900.0 / 2800.0).abs() < 1e-9);
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].contains("published maximum loads"));
+
+    assert!(matches!(charge("0").0, Err(MuzzleError::INVALID(_))));
+}
@@ -0,0 +1,25 @@
+//! Energy drop chart at the classic yardages.
+
+use muzzle::{energy_chart, run, Config, DragModel, Units, CHART_DISTANCES};
+
+#[test]
+fn chart_starts_at_muzzle_and_decreases() {
+    for (units, mass, speed) in [(Units::METRIC, "10", "800"), (Units::IMPERIAL, "150", "2800")] {
+        let params = run(Config { units, mass: Some(mass.to_owned()), speed: Some(speed.to_owned()), ..Config::default() }).unwrap();
+        let chart = energy_chart(&params, DragModel::G1, 0.4, &CHART_DISTANCES).unwrap();
+
+        let distances: Vec<f64> = chart.iter().map(|&(distance, _, _)| distance).collect();
+        assert_eq!(distances, CHART_DISTANCES);
+        assert_eq!(chart[0].1, params.speed);
+        assert_eq!(chart[0].2, params.energy);
+        assert!(chart.windows(2).all(|pair| pair[1].1 < pair[0].1 && pair[1].2 < pair[0].2));
+    }
+}
+
+#[test]
+fn chart_needs_positive_bc() {
+    let params = run(Config { units: Units::METRIC, mass: Some("10".to_owned()), speed: Some("800".to_owned()), ..Config::default() }).unwrap();
+
+    assert!(energy_chart(&params, DragModel::G1, 0.0, &CHART_DISTANCES).is_err());
+    assert!(energy_chart(&params, DragModel::G1, 0.4, &[-50.0]).is_err());
+}
@@ -0,0 +1,36 @@
+//! Magnitudes of inputs checked against the units they are given in, `--check-units`.
+
+mod common;
+
+use common::muzzle;
+use muzzle::{check_units, Config, Plausibility, Quantity, UnitCheck, Units};
+
+fn check(units: Units, mass: &str, speed: &str) -> Vec<UnitCheck> {
+    check_units(&Config { units, mass: Some(mass.to_owned()), speed: Some(speed.to_owned()), ..Config::default() })
+}
+
+#[test]
+fn flags_grains_entered_as_metric() {
+    let checks = check(Units::METRIC, "150", "800");
+    assert_eq!(checks, [
+        UnitCheck { quantity: Quantity::MASS, value: 150.0, verdict: Plausibility::MISMATCHED },
+        UnitCheck { quantity: Quantity::SPEED, value: 800.0, verdict: Plausibility::PLAUSIBLE },
+    ]);
+
+    // The same bullet in the right units, and values no system explains
+    assert!(check(Units::IMPERIAL, "150", "2800").iter().all(|check| check.verdict == Plausibility::PLAUSIBLE));
+    assert_eq!(check(Units::METRIC, "0.0001", "800")[0].verdict, Plausibility::LOW);
+    assert_eq!(check(Units::METRIC, "10", "100000")[1].verdict, Plausibility::HIGH);
+    assert_eq!(check(Units::METRIC, "ten", "800").len(), 1);
+}
+
+#[test]
+fn cli_warns_before_calculating() {
+    let lenient = muzzle(&["-m", "150", "-s", "800", "--check-units"]);
+    assert!(lenient.status.success());
+    let stderr = String::from_utf8_lossy(&lenient.stderr);
+    assert!(stderr.contains("  mass:\t150\tlikely imperial"), "{}", stderr);
+    assert!(stderr.contains("WARNING: Some values are implausible"));
+
+    assert_eq!(muzzle(&["-m", "150", "-s", "800", "--check-units", "--strict"]).status.code(), Some(1));
+}
@@ -0,0 +1,43 @@
+//! Colored output: derived values highlighted in the theme's color, and nothing colored once
+//! `NO_COLOR` is set or `--color never` is given.
+
+mod common;
+
+use common::stdout;
+use muzzle::{run, ColorChoice, ColorTheme, Config, ReportOptions, Units, RESET};
+
+#[test]
+fn names_are_parsed() {
+    assert_eq!(ColorChoice::from_name("Always"), Some(ColorChoice::ALWAYS));
+    assert_eq!(ColorChoice::from_name("never"), Some(ColorChoice::NEVER));
+    assert_eq!(ColorChoice::from_name("sometimes"), None);
+    assert_eq!(ColorTheme::from_name(" light "), Some(ColorTheme::LIGHT));
+    assert_eq!(ColorTheme::from_name("solarized"), None);
+    assert_ne!(ColorTheme::DARK.highlight(), ColorTheme::LIGHT.highlight());
+}
+
+#[test]
+fn only_derived_values_are_highlighted() {
+    let params = run(Config { units: Units::METRIC, mass: Some("10".to_owned()), speed: Some("800".to_owned()), ..Config::default() }).unwrap();
+    let report = params.to_human(&ReportOptions { color: Some(ColorTheme::LIGHT), ..ReportOptions::default() });
+
+    assert!(report.contains(&format!("Projectile energy:\t{}3200.000{} Joules", ColorTheme::LIGHT.highlight(), RESET)), "{}", report);
+    assert!(report.contains("Projectile mass:\t10.000 grams"), "{}", report);
+    assert_eq!(report.matches('\x1b').count(), 2);
+    assert!(!params.to_human(&ReportOptions::default()).contains('\x1b'));
+}
+
+#[test]
+fn cli_colors_only_when_allowed() {
+    let args = ["-m", "10", "-s", "800", "--color", "always", "--color-theme", "light"];
+    assert!(stdout(&args).contains(ColorTheme::LIGHT.highlight()));
+    assert!(!stdout(&args[..4]).contains('\x1b'));
+    assert!(!stdout(&["-m", "10", "-s", "800", "--color", "never"]).contains('\x1b'));
+
+    let output = common::command().args(args).env("NO_COLOR", "1").output().unwrap();
+    assert!(output.status.success());
+    assert!(!output.stdout.contains(&b'\x1b') && !output.stderr.contains(&b'\x1b'));
+
+    assert_eq!(common::muzzle(&["-m", "10", "-s", "800", "--color", "sometimes"]).status.code(), Some(1));
+    assert_eq!(common::muzzle(&["-m", "10", "-s", "800", "--color-theme", "solarized"]).status.code(), Some(1));
+}
@@ -0,0 +1,42 @@
+//! Running the `muzzle` binary from integration tests, isolated from the settings file and
+//! calculation history of whoever runs them.
+
+// Every test crate includes this module and uses only some of it
+#![allow(dead_code)]
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+
+/// Directory that is never created, for HOME and the XDG directories to point at
+pub fn nowhere() -> PathBuf {
+    std::env::temp_dir().join("muzzle-tests-nonexistent")
+}
+
+/// Command running the binary with no settings file or history to pick up, directories may
+/// still be pointed elsewhere with `env`
+pub fn command() -> Command {
+    let nowhere = nowhere();
+    let mut command = Command::new(env!("CARGO_BIN_EXE_muzzle"));
+    command.env("HOME", &nowhere).env("XDG_CONFIG_HOME", &nowhere).env("XDG_DATA_HOME", &nowhere);
+    command
+}
+
+/// Runs the binary with given arguments, whether it succeeds or not
+pub fn muzzle(args: &[&str]) -> Output {
+    command().args(args).output().unwrap()
+}
+
+/// Runs the binary with given arguments and `stdin` piped in, whether it succeeds or not
+pub fn muzzle_with_stdin(args: &[&str], stdin: &str) -> Output {
+    let mut child = command().args(args).stdin(Stdio::piped()).stdout(Stdio::piped()).stderr(Stdio::piped()).spawn().unwrap();
+    child.stdin.take().unwrap().write_all(stdin.as_bytes()).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+/// Stdout of a run with given arguments, which must succeed
+pub fn stdout(args: &[&str]) -> String {
+    let output = muzzle(args);
+    assert!(output.status.success(), "muzzle {:?} failed with: {}", args, String::from_utf8_lossy(&output.stderr));
+    String::from_utf8(output.stdout).unwrap()
+}
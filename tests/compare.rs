@@ -0,0 +1,55 @@
+//! Two loads compared metric by metric, the better value colored where a metric has a better way.
+
+mod common;
+
+use common::stdout;
+use muzzle::{run, ColorTheme, Config, Criterion, Params, ReportOptions, Units, Winner, RESET};
+
+fn load(units: Units, mass: &str, speed: &str) -> Params {
+    run(Config { units, mass: Some(mass.to_owned()), speed: Some(speed.to_owned()), ..Config::default() }).unwrap()
+}
+
+#[test]
+fn higher_energy_and_momentum_win() {
+    // Lighter and faster against heavier and slower, more energy but less momentum
+    let compared = load(Units::METRIC, "10", "800").compare(&load(Units::METRIC, "12", "700"));
+    let winner = |criterion| compared.iter().find(|compared| compared.criterion == criterion).unwrap().winner();
+
+    assert_eq!(winner(Criterion::SPEED), Winner::FIRST);
+    assert_eq!(winner(Criterion::ENERGY), Winner::FIRST);
+    assert_eq!(winner(Criterion::MOMENTUM), Winner::SECOND);
+    assert_eq!(winner(Criterion::MASS), Winner::UNJUDGED);
+    assert_eq!(winner(Criterion::PF), Winner::UNJUDGED);
+
+    let even = load(Units::METRIC, "10", "800").compare(&load(Units::METRIC, "10", "800"));
+    assert_eq!(even[2].winner(), Winner::EVEN);
+}
+
+#[test]
+fn other_load_is_converted() {
+    let compared = load(Units::IMPERIAL, "150", "2800").compare(&load(Units::METRIC, "9.7198365", "853.44"));
+    assert!(compared.iter().all(|compared| (compared.first - compared.second).abs() < 1e-5 * compared.first), "{:?}", compared);
+}
+
+#[test]
+fn better_values_are_colored() {
+    let (first, second) = (load(Units::METRIC, "10", "800"), load(Units::METRIC, "12", "700"));
+    let theme = ColorTheme::DARK;
+    let report = first.to_comparison(&second, &ReportOptions { color: Some(theme), ..ReportOptions::default() });
+
+    assert!(report.contains(&format!("Energy (Joules):\t{}3200.000{}\t2940.000\tfirst\n", theme.better(), RESET)), "{}", report);
+    assert!(report.contains(&format!("Momentum (kg·m/s):\t8.000\t{}8.400{}\tsecond\n", theme.better(), RESET)), "{}", report);
+    assert!(report.contains("Power factor:\t405.049\t425.301\tnot judged\n"), "{}", report);
+    assert!(!first.to_comparison(&second, &ReportOptions::default()).contains('\x1b'));
+}
+
+#[test]
+fn cli_compares_to_a_load() {
+    let args = ["-m", "10", "-s", "800", "--compare", "12@700"];
+    assert!(stdout(&args).contains("Energy (Joules):\t3200.000\t2940.000\tfirst\n"));
+    assert!(stdout(&[&args[..], &["--color", "always"]].concat()).contains(ColorTheme::DARK.better()));
+    assert!(!stdout(&[&args[..], &["--color", "never"]].concat()).contains('\x1b'));
+
+    assert_eq!(common::muzzle(&["-m", "10", "-s", "800", "--compare", "12"]).status.code(), Some(1));
+    assert_eq!(common::muzzle(&[&args[..], &["-f", "json"]].concat()).status.code(), Some(1));
+}
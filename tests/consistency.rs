@@ -0,0 +1,46 @@
+//! Verdict on input that gives all three primary parameters, nothing left to derive.
+
+use muzzle::{run, Config, Consistency, Units};
+
+fn config(units: Units, mass: &str, speed: &str, energy: &str) -> Config {
+    Config {
+        units,
+        mass: Some(mass.to_owned()),
+        speed: Some(speed.to_owned()),
+        energy: Some(energy.to_owned()),
+        ..Config::default()
+    }
+}
+
+#[test]
+fn matching_triple_is_consistent() {
+    let params = run(config(Units::IMPERIAL, "150", "2800", "2611")).unwrap();
+    assert_eq!(params.overdetermined, Some(Consistency::CONSISTENT));
+    assert_eq!(params.bogus, params.overdetermined.is_some());
+}
+
+#[test]
+fn mismatched_triple_is_inconsistent() {
+    let params = run(config(Units::METRIC, "10", "800", "2000")).unwrap();
+    assert_eq!(params.overdetermined, Some(Consistency::INCONSISTENT));
+    assert_eq!(Consistency::INCONSISTENT.to_string(), "inconsistent");
+    // Judged against the 3200 J that 10 g at 800 m/s carry, in whichever units it is reported
+    assert!((params.expected_energy.unwrap() - 3200.0).abs() < 1e-9, "{:?}", params.expected_energy);
+    assert!((params.in_units(Units::IMPERIAL).expected_energy.unwrap() - 3200.0 / 1.3558179483314004).abs() < 1e-6);
+}
+
+#[test]
+fn zero_mass_has_nothing_to_check_against() {
+    let params = run(config(Units::METRIC, "0", "800", "100")).unwrap();
+    assert_eq!(params.overdetermined, Some(Consistency::OVERDETERMINED));
+    assert_eq!(params.expected_energy, None);
+}
+
+#[test]
+fn derived_input_has_no_verdict() {
+    let mut config = config(Units::METRIC, "10", "800", "");
+    config.energy = None;
+    let params = run(config).unwrap();
+    assert_eq!(params.overdetermined, None);
+    assert_eq!(params.expected_energy, None);
+}
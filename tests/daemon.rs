@@ -0,0 +1,33 @@
+//! Round-trip of calculation requests over a Unix socket, `cargo test --features daemon`.
+#![cfg(all(unix, feature = "daemon"))]
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::Shutdown;
+use std::os::unix::net::UnixStream;
+use std::thread;
+
+#[test]
+fn round_trip() {
+    let (client, server) = UnixStream::pair().unwrap();
+    let handler = thread::spawn(move || muzzle::daemon::handle(server));
+
+    let mut writer = client.try_clone().unwrap();
+    let mut responses = BufReader::new(client).lines();
+    let mut request = |line: &str| -> serde_json::Value {
+        writeln!(writer, "{}", line).unwrap();
+        serde_json::from_str(&responses.next().unwrap().unwrap()).unwrap()
+    };
+
+    let response = request(r#"{"mass": "8", "speed": "800", "units": "metric"}"#);
+    assert_eq!(response["energy"], 2560.0);
+
+    let response = request(r#"{"mass": "8"}"#);
+    assert!(response["error"].is_string());
+
+    let response = request("not json");
+    assert!(response["error"].as_str().unwrap().starts_with("Expected a JSON config"));
+
+    // Hanging up ends the connection cleanly
+    writer.shutdown(Shutdown::Write).unwrap();
+    handler.join().unwrap().unwrap();
+}
@@ -0,0 +1,39 @@
+//! Units defaulted by the settings file and overridden per run, `default_units` and `--metric`.
+
+mod common;
+
+use muzzle::settings::Settings;
+use muzzle::Units;
+#[cfg(feature = "settings")]
+use {muzzle::settings, std::fs};
+
+#[test]
+fn flag_beats_file_beats_metric() {
+    assert_eq!(Settings::default().units(None), Units::METRIC);
+    let imperial = Settings { default_units: Some(Units::IMPERIAL), ..Settings::default() };
+    assert_eq!(imperial.units(None), Units::IMPERIAL);
+    assert_eq!(imperial.units(Some(Units::METRIC)), Units::METRIC);
+}
+
+#[test]
+#[cfg(feature = "settings")]
+fn cli_reads_default_from_file() {
+    let home = std::env::temp_dir().join(format!("muzzle-default-units-{}", std::process::id()));
+    fs::create_dir_all(home.join("muzzle")).unwrap();
+    fs::write(home.join("muzzle").join("config.toml"), "default_units = \"imperial\"\n").unwrap();
+    assert_eq!(settings::load(&home.join("muzzle").join("config.toml")).unwrap().default_units, Some(Units::IMPERIAL));
+
+    let muzzle = |args: &[&str]| common::command().args(["-m", "150", "-s", "2800"]).args(args).env("XDG_CONFIG_HOME", &home).output().unwrap();
+    let energy = |args: &[&str]| {
+        let output = muzzle(args);
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        stdout.lines().find(|line| line.starts_with("Projectile energy:")).unwrap().to_owned()
+    };
+    let (default, metric, conflicting) = (energy(&[]), energy(&["--metric"]), muzzle(&["-i", "--metric"]));
+    fs::remove_dir_all(&home).unwrap();
+
+    assert!(default.ends_with("FPE"), "{}", default);
+    assert!(metric.ends_with("Joules"), "{}", metric);
+    assert_eq!(conflicting.status.code(), Some(1));
+}
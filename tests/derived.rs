@@ -0,0 +1,36 @@
+//! The one quantity computed from the other two, `Params::derived_value`.
+
+use muzzle::{run, Config, Quantity, Units};
+
+fn config(mass: Option<&str>, speed: Option<&str>, energy: Option<&str>) -> Config {
+    Config {
+        units: Units::METRIC,
+        mass: mass.map(str::to_owned),
+        speed: speed.map(str::to_owned),
+        energy: energy.map(str::to_owned),
+        ..Config::default()
+    }
+}
+
+#[test]
+fn names_and_values_the_derived_quantity() {
+    let energy = run(config(Some("10"), Some("800"), None)).unwrap();
+    assert_eq!(energy.derived_value(), Some(("energy".to_owned(), 3200.0)));
+    assert_eq!(energy.derived, Some(Quantity::ENERGY));
+
+    let speed = run(config(Some("10"), None, Some("3200"))).unwrap();
+    assert_eq!(speed.derived_value(), Some(("speed".to_owned(), 800.0)));
+
+    let mass = run(config(None, Some("800"), Some("3200"))).unwrap();
+    let (name, value) = mass.derived_value().unwrap();
+    assert_eq!(name, "mass");
+    assert!((value - 10.0).abs() < 1e-12);
+    assert_eq!(value, mass.value(Quantity::MASS));
+}
+
+#[test]
+fn nothing_derived_when_all_given() {
+    let params = run(config(Some("10"), Some("800"), Some("3200"))).unwrap();
+    assert_eq!(params.derived_value(), None);
+    assert_eq!(params.derived, None);
+}
@@ -0,0 +1,28 @@
+//! Several output formats written in one run, `--human-out`, `--json-out` and `--csv-out`.
+
+mod common;
+
+use muzzle::{run, Config, Units, CSV_HEADER};
+use std::env;
+use std::fs;
+
+#[test]
+fn each_format_goes_to_its_file() {
+    let dir = env::temp_dir().join(format!("muzzle-destinations-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let [json, csv] = ["result.json", "result.csv"].map(|name| dir.join(name));
+
+    let output = common::command()
+        .args(["-m", "10", "-s", "800", "--human-out", "-"])
+        .args(["--json-out".as_ref(), json.as_os_str(), "--csv-out".as_ref(), csv.as_os_str()])
+        .output()
+        .unwrap();
+    let (json, csv) = (fs::read_to_string(&json).unwrap(), fs::read_to_string(&csv).unwrap());
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert!(output.status.success());
+    let params = run(Config { units: Units::METRIC, mass: Some("10".to_owned()), speed: Some("800".to_owned()), ..Config::default() }).unwrap();
+    assert!(String::from_utf8(output.stdout).unwrap().contains("Projectile energy:\t3200.000 Joules"));
+    assert_eq!(json.trim_end(), params.to_json());
+    assert_eq!(csv, format!("{}\n{}\n", CSV_HEADER, params.to_csv()));
+}
@@ -0,0 +1,53 @@
+//! Warnings handed to a sink of the caller's choosing, `run_with_diagnostics`.
+
+use muzzle::{calculate, run, run_with_diagnostics, Config, Diagnostics, Units};
+
+/// Sink counting warnings by whether they mention units
+#[derive(Default)]
+struct Counter {
+    about_units: usize,
+    other: usize,
+}
+
+impl Diagnostics for Counter {
+    fn warning(&mut self, message: String) {
+        if message.contains("units") { self.about_units += 1 } else { self.other += 1 }
+    }
+}
+
+/// Speed given in FPS to a metric calculation, with a charge estimate past the reference load
+fn questionable() -> Config {
+    Config {
+        units: Units::METRIC,
+        mass: Some("10".to_owned()),
+        speed: Some("2800".to_owned()),
+        ref_charge: Some("3".to_owned()),
+        ref_velocity: Some("800".to_owned()),
+        target_velocity: Some("850".to_owned()),
+        ..Config::default()
+    }
+}
+
+#[test]
+fn collects_warnings_in_order() {
+    let mut warnings = Vec::new();
+    let params = run_with_diagnostics(questionable(), &mut warnings).unwrap();
+    assert_eq!(warnings.len(), 2);
+    assert!(warnings[0].starts_with("Speed of 2800 m/s is beyond any firearm"), "{}", warnings[0]);
+    assert!(warnings[1].starts_with("Target velocity is above the reference load"), "{}", warnings[1]);
+
+    // Same result as without a sink, and as `calculate` keeps
+    assert_eq!(params.to_json(), run(questionable()).unwrap().to_json());
+    assert_eq!(calculate(questionable()).unwrap().warnings, warnings);
+}
+
+#[test]
+fn takes_custom_sinks() {
+    let mut counter = Counter::default();
+    run_with_diagnostics(questionable(), &mut counter).unwrap();
+    assert_eq!((counter.about_units, counter.other), (1, 1));
+
+    let mut quiet = Counter::default();
+    run_with_diagnostics(Config { units: Units::METRIC, mass: Some("10".to_owned()), speed: Some("800".to_owned()), ..Config::default() }, &mut quiet).unwrap();
+    assert_eq!((quiet.about_units, quiet.other), (0, 0));
+}
@@ -0,0 +1,26 @@
+//! Rounding of human output to decimals, `Locale::format_number`.
+
+mod common;
+
+use common::stdout;
+use muzzle::Locale;
+
+#[test]
+fn rounds_to_nearest_shown_decimal() {
+    assert_eq!(Locale::EN.format_number(149.9997, 3), "150.000");
+    assert_eq!(Locale::EN.format_number(149.9994, 3), "149.999");
+    // 2.675 is stored a hair below, yet halfway as written
+    assert_eq!(Locale::EN.format_number(2.675, 2), "2.68");
+    assert_eq!(Locale::EN.format_number(-0.0004, 3), "0.000");
+    assert_eq!(Locale::DE.format_number(149.9997, 3), "150,000");
+    assert_eq!(Locale::EN.format_number(f64::MAX, 2).len(), 312);
+}
+
+#[test]
+fn grams_from_grains_land_on_whole_values() {
+    assert!(stdout(&["-i", "-m", "149.9997", "-s", "2800"]).contains("Projectile mass:\t150.000 grains"));
+
+    // 9.72 g converted to grains and back shows as entered
+    let grains = 9.72 / 0.06479891;
+    assert_eq!(Locale::EN.format_number(grains * 0.06479891, 3), "9.720");
+}
@@ -0,0 +1,35 @@
+//! Major and minor power factor floors of practical shooting divisions, `--division`.
+
+mod common;
+
+use common::muzzle;
+use muzzle::{find_division, run, Config, Params, PowerClass, Units, DIVISIONS};
+
+fn load(mass: &str, speed: &str) -> Params {
+    run(Config { units: Units::IMPERIAL, mass: Some(mass.to_owned()), speed: Some(speed.to_owned()), ..Config::default() }).unwrap()
+}
+
+fn class(division: &str, load: &Params) -> PowerClass {
+    find_division(DIVISIONS, division).unwrap().classify(load)
+}
+
+#[test]
+fn same_load_scores_differently_by_division() {
+    // 180 gr at 925 FPS makes a power factor of 166.5
+    let load = load("180", "925");
+    assert_eq!(class("generic", &load), PowerClass::MAJOR);
+    assert_eq!(class("ipsc-open", &load), PowerClass::MAJOR);
+    assert_eq!(class("IPSC-Standard", &load), PowerClass::MINOR);
+    assert_eq!(class("uspsa-production", &load), PowerClass::MINOR);
+}
+
+#[test]
+fn floors_are_inclusive() {
+    assert_eq!(class("generic", &load("165", "1000")), PowerClass::MAJOR);
+    assert_eq!(class("generic", &load("125", "1000")), PowerClass::MINOR);
+    assert_eq!(class("generic", &load("124", "1000")), PowerClass::BELOW);
+    assert!(find_division(DIVISIONS, "idpa").is_none());
+
+    let output = muzzle(&["-i", "-m", "180", "-s", "925", "--division", "idpa"]);
+    assert_eq!(output.status.code(), Some(1));
+}
@@ -0,0 +1,38 @@
+//! Reference drag functions downrange estimates are made against, `--drag-model`.
+
+mod common;
+
+use common::{muzzle, stdout};
+use muzzle::{run, Config, DragModel, Units};
+
+fn retained_speed(drag_model: DragModel, speed: &str, distance: &str) -> f64 {
+    let config = Config {
+        units: Units::IMPERIAL,
+        mass: Some("175".to_owned()),
+        speed: Some(speed.to_owned()),
+        bc: Some("0.25".to_owned()),
+        drag_model,
+        distance: Some(distance.to_owned()),
+        ..Config::default()
+    };
+    run(config).unwrap().retained_speed.unwrap()
+}
+
+#[test]
+fn g7_retains_more_than_g1_at_same_bc() {
+    // Supersonic and subsonic alike, G7 projectiles are the lower drag reference
+    for (speed, distance) in [("2600", "500"), ("1050", "100"), ("3000", "1000")] {
+        let g1 = retained_speed(DragModel::G1, speed, distance);
+        let g7 = retained_speed(DragModel::G7, speed, distance);
+        assert!(g7 > g1, "{} FPS at {} yd: G7 {} vs G1 {}", speed, distance, g7, g1);
+    }
+    assert!(matches!(DragModel::default(), DragModel::G1));
+}
+
+#[test]
+fn cli_takes_model_by_name() {
+    let args = |model| ["-i", "-m", "175", "-s", "2600", "--bc", "0.25", "--distance", "500", "--drag-model", model];
+
+    assert_ne!(stdout(&args("g1")), stdout(&args("G7")));
+    assert!(!muzzle(&args("g5")).status.success());
+}
@@ -0,0 +1,23 @@
+//! Shot and ball weights in avoirdupois drams, `1.125dr`.
+
+use muzzle::{run, Config, MuzzleError, Units};
+
+fn mass(units: Units, mass: &str) -> Result<f64, MuzzleError> {
+    run(Config { units, mass: Some(mass.to_owned()), speed: Some("1200".to_owned()), ..Config::default() }).map(|params| params.mass)
+}
+
+#[test]
+fn converts_drams_to_grains_or_grams() {
+    // A sixteenth of an ounce: 1 1/8 oz of shot is 18 drams
+    assert_eq!(mass(Units::IMPERIAL, "1dr").unwrap(), 27.34375);
+    assert_eq!(mass(Units::IMPERIAL, "18dr").unwrap(), 492.1875);
+    assert_eq!(mass(Units::IMPERIAL, " 1.125 DR ").unwrap(), 1.125 * 27.34375);
+    assert!((mass(Units::METRIC, "1dr").unwrap() - 1.7718451953125).abs() < 1e-12);
+}
+
+#[test]
+fn rejects_malformed_drams() {
+    for input in ["dr", "1.1.2dr", "onedr"] {
+        assert!(mass(Units::IMPERIAL, input).is_err(), "{}", input);
+    }
+}
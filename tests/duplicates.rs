@@ -0,0 +1,25 @@
+//! Options repeated on the command line, `-m 150 --mass 160`.
+
+mod common;
+
+use common::muzzle;
+
+#[test]
+fn conflicting_duplicates_are_rejected() {
+    for args in [&["-m", "150", "--mass", "160", "-s", "800"][..], &["-m", "10", "-s", "800", "-s", "900"], &["-m", "10", "-e", "1", "--energy", "2"]] {
+        let output = muzzle(args);
+        assert!(!output.status.success(), "{:?}", args);
+        assert!(output.stdout.is_empty());
+        assert!(String::from_utf8_lossy(&output.stderr).contains("given more than once with conflicting values"));
+    }
+}
+
+#[test]
+fn identical_duplicates_are_allowed() {
+    let once = muzzle(&["-m", "10", "-s", "800"]);
+    assert!(once.status.success());
+    let twice = muzzle(&["-m", "10", "--mass", "10", "-s", "800", "-s", " 800 "]);
+
+    assert!(twice.status.success());
+    assert_eq!(twice.stdout, once.stdout);
+}
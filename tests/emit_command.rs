@@ -0,0 +1,31 @@
+//! Command lines reproducing a result, `--emit-command`.
+
+mod common;
+
+use common::stdout;
+use muzzle::{run, Config, Units};
+use serde_json::Value;
+
+#[test]
+fn emitted_command_reproduces_result() {
+    for args in [&["-i", "-m", "150", "-s", "2800"][..], &["-m", "10", "-e", "3000"], &["-s", "2800.5", "-e", "3100.25"]] {
+        let derived: Value = serde_json::from_str(&stdout(&[args, &["-f", "json"]].concat())).unwrap();
+
+        let command = stdout(&[args, &["--emit-command"]].concat());
+        let words: Vec<&str> = command.split_whitespace().collect();
+        assert_eq!(words[0], "muzzle");
+        let rerun: Value = serde_json::from_str(&stdout(&[&words[1..], &["-f", "json"]].concat())).unwrap();
+
+        // All three given this time, so nothing is derived and the values come back as they were
+        assert_eq!(rerun["bogus"], true);
+        for field in ["units", "mass", "speed", "energy"] {
+            assert_eq!(rerun[field], derived[field], "{} of {:?}", field, args);
+        }
+    }
+}
+
+#[test]
+fn command_gives_all_three() {
+    let params = run(Config { units: Units::METRIC, mass: Some("10".to_owned()), speed: Some("800".to_owned()), ..Config::default() }).unwrap();
+    assert_eq!(params.to_command(), "muzzle --mass 10 --speed 800 --energy 3200");
+}
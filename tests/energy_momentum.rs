@@ -0,0 +1,35 @@
+//! Speed and mass of a bullet of unknown mass from its energy and momentum, `v = 2E/p`.
+
+use muzzle::{run, Config, MuzzleError, Units};
+
+fn solve(units: Units, energy: &str, momentum: &str) -> Result<(f64, f64), MuzzleError> {
+    let config = Config { units, energy: Some(energy.to_owned()), momentum: Some(momentum.to_owned()), ..Config::default() };
+    run(config).map(|params| (params.mass, params.speed))
+}
+
+#[test]
+fn recovers_mass_and_speed() {
+    // 10 g at 800 m/s carries 3200 J and 8 kg·m/s
+    let (mass, speed) = solve(Units::METRIC, "3200", "8").unwrap();
+    assert!((speed - 800.0).abs() < 1e-9);
+    assert!((mass - 10.0).abs() < 1e-9);
+
+    // 150 gr at 2800 FPS carries 60 lb·ft/s
+    let (mass, speed) = solve(Units::IMPERIAL, "2610.8037545844472", "60").unwrap();
+    assert!((speed - 2800.0).abs() < 1e-9);
+    assert!((mass - 150.0).abs() < 1e-9);
+}
+
+#[test]
+fn rejects_non_positive_momentum() {
+    for momentum in ["0", "-8"] {
+        assert!(matches!(solve(Units::METRIC, "3200", momentum), Err(MuzzleError::INVALID(_))), "{}", momentum);
+    }
+}
+
+#[test]
+fn rejects_non_positive_energy() {
+    for energy in ["0", "-100"] {
+        assert!(matches!(solve(Units::METRIC, energy, "2"), Err(MuzzleError::INVALID(_))), "{}", energy);
+    }
+}
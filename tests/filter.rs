@@ -0,0 +1,34 @@
+//! JSON array of configs in, JSON array of results out, `--from-json-stdin`.
+
+mod common;
+
+use common::muzzle_with_stdin;
+use muzzle::{filter_json, run, Config, Units};
+use serde_json::Value;
+
+const INPUT: &str = r#"[{"mass": 10, "speed": 800}, {"mass": 10}, {"units": "imperial", "mass": "150", "speed": "2800"}]"#;
+
+#[test]
+fn maps_every_item_in_order() {
+    let output = filter_json(INPUT).unwrap();
+    let items = output.as_array().unwrap();
+    assert_eq!(items.len(), 3);
+
+    let metric = run(Config { units: Units::METRIC, mass: Some("10".to_owned()), speed: Some("800".to_owned()), ..Config::default() }).unwrap();
+    assert_eq!(items[0], serde_json::from_str::<Value>(&metric.to_json()).unwrap());
+    // A bad item is reported in its place and does not stop the rest
+    assert!(items[1]["error"].as_str().unwrap().starts_with("Incorrect parameters set."));
+    assert_eq!(items[2]["units"], "imperial");
+    assert_eq!(items[2]["derived"], "energy");
+
+    assert_eq!(filter_json("[]").unwrap(), Value::Array(Vec::new()));
+    assert!(filter_json(r#"{"mass": 10}"#).is_err());
+}
+
+#[test]
+fn cli_writes_only_the_array() {
+    let output = muzzle_with_stdin(&["--from-json-stdin", "--to-json-stdout"], INPUT);
+
+    assert!(output.status.success());
+    assert_eq!(serde_json::from_slice::<Value>(&output.stdout).unwrap(), filter_json(INPUT).unwrap());
+}
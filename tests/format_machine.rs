@@ -0,0 +1,22 @@
+//! Locale independent numbers for machine readable output, `format_machine`.
+
+use muzzle::{format_machine, Locale};
+
+#[test]
+fn writes_plain_shortest_digits() {
+    assert_eq!(format_machine(3200.0), "3200");
+    assert_eq!(format_machine(0.1 + 0.2), "0.30000000000000004");
+    assert_eq!(format_machine(1234567.5), "1234567.5");
+    assert_eq!(format_machine(1e21), "1000000000000000000000");
+    assert_eq!(format_machine(-0.0), "0");
+    assert_eq!(format_machine(-2.5), "-2.5");
+}
+
+#[test]
+fn round_trips_whatever_the_locale() {
+    for value in [0.06479891, 2610.8037545844472, 1.0 / 3.0, 5e-324, f64::MAX] {
+        assert_eq!(format_machine(value).parse::<f64>().unwrap(), value);
+    }
+    // German human output writes a comma, machine output never does
+    assert_eq!(Locale::from_tag("de").unwrap().normalize_number("1234,5"), format_machine(1234.5));
+}
@@ -0,0 +1,38 @@
+//! Energy checked against minimums for game classes, `--game`.
+
+mod common;
+
+use common::muzzle;
+use muzzle::settings::Settings;
+use muzzle::{find_game_class, run, Config, Params, Units, GAME_CLASSES};
+
+fn params(units: Units, mass: &str, energy: &str) -> Params {
+    run(Config { units, mass: Some(mass.to_owned()), energy: Some(energy.to_owned()), ..Config::default() }).unwrap()
+}
+
+#[test]
+fn passes_from_the_minimum_up() {
+    let deer = find_game_class(GAME_CLASSES, "Deer").unwrap();
+    assert!((deer.threshold(Units::IMPERIAL) - 1000.0).abs() < 1e-9);
+    assert!(params(Units::IMPERIAL, "150", "1000.001").meets(deer));
+    assert!(!params(Units::IMPERIAL, "150", "999.999").meets(deer));
+    assert!(params(Units::METRIC, "10", "1356").meets(deer));
+    assert!(!params(Units::METRIC, "10", "1355").meets(deer));
+
+    // Settings file minimums replace the built-in ones
+    let settings = Settings { game_energy: [("deer".to_owned(), 1200.0)].into(), ..Settings::default() };
+    let lowered = settings.game_class("deer").unwrap();
+    assert!(params(Units::METRIC, "10", "1250").meets(&lowered));
+}
+
+#[test]
+fn cli_reports_pass_or_fail() {
+    let verdict = |class: &str| {
+        let output = muzzle(&["-i", "-m", "55", "-s", "3000", "--game", class]);
+        assert!(output.status.success());
+        String::from_utf8(output.stdout).unwrap().lines().find(|line| line.starts_with("Energy for")).unwrap().to_owned()
+    };
+
+    assert!(verdict("deer").starts_with("Energy for deer:\tPASS, minimum 1000 FPE"), "{}", verdict("deer"));
+    assert!(verdict("elk").starts_with("Energy for elk:\tFAIL, minimum 1500 FPE"), "{}", verdict("elk"));
+}
@@ -0,0 +1,95 @@
+//! Golden output tests: representative shots rendered through every output format and compared
+//! against the output committed under `tests/golden`, so formats never change by accident.
+//!
+//! After an intentional format change, regenerate the files with
+//! `UPDATE_GOLDEN=1 cargo test --test golden` and review their diff before committing it.
+
+mod common;
+
+use common::stdout;
+use muzzle::{run, Config, Locale, Params, Preset, ReportOptions, Units};
+use std::env;
+use std::fs;
+use std::path::Path;
+
+/// Compares `actual` to the golden file `name`, or overwrites the file with it when updating
+fn check(name: &str, actual: &str) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("golden").join(format!("{}.txt", name));
+    if env::var_os("UPDATE_GOLDEN").is_some() {
+        fs::write(&path, actual).unwrap();
+        return;
+    }
+
+    let expected = fs::read_to_string(&path).unwrap_or_else(|_| panic!("missing {}, run with UPDATE_GOLDEN=1 to create it", path.display()));
+    assert!(actual == expected, "output of `{}` no longer matches {}:\n--- expected\n{}\n--- actual\n{}", name, path.display(), expected, actual);
+}
+
+/// Checks every library formatter of given shot against golden files prefixed with `name`
+fn check_formats(name: &str, params: &Params) {
+    check(&format!("{}.json", name), &params.to_json());
+    check(&format!("{}.pretty", name), &params.to_json_pretty());
+    check(&format!("{}.csv", name), &params.to_csv());
+    check(&format!("{}.md", name), &params.to_markdown());
+    check(&format!("{}.tex", name), &params.to_latex());
+    check(&format!("{}.command", name), &params.to_command());
+}
+
+#[test]
+fn metric_energy() {
+    let config = Config { units: Units::METRIC, mass: Some("10".to_owned()), speed: Some("800".to_owned()), ..Config::default() };
+    check_formats("metric_energy", &run(config).unwrap());
+}
+
+#[test]
+fn imperial_downrange() {
+    let config = Config {
+        units: Units::IMPERIAL,
+        mass: Some("150".to_owned()),
+        energy: Some("2611".to_owned()),
+        bc: Some("0.4".to_owned()),
+        distance: Some("300".to_owned()),
+        diameter: Some("0.308".to_owned()),
+        ..Config::default()
+    };
+    check_formats("imperial_downrange", &run(config).unwrap());
+}
+
+/// Shot of given units and parameters, each of mass, speed and energy given as written
+fn shot(units: Units, mass: &str, speed: Option<&str>, energy: Option<&str>) -> Params {
+    let config = Config { units, mass: Some(mass.to_owned()), speed: speed.map(str::to_owned), energy: energy.map(str::to_owned), ..Config::default() };
+    run(config).unwrap()
+}
+
+#[test]
+fn human_report_library() {
+    let default = ReportOptions::default();
+    check("human_metric", &shot(Units::METRIC, "10", Some("800"), None).to_human(&default));
+    check("human_bogus", &shot(Units::METRIC, "10", Some("800"), Some("3200")).to_human(&default));
+
+    let config = Config {
+        units: Units::IMPERIAL,
+        mass: Some("150".to_owned()),
+        energy: Some("2611".to_owned()),
+        bc: Some("0.4".to_owned()),
+        distance: Some("300".to_owned()),
+        diameter: Some("0.308".to_owned()),
+        ..Config::default()
+    };
+    check("human_imperial_downrange", &run(config).unwrap().to_human(&ReportOptions { distance: Some("300"), ..ReportOptions::default() }));
+
+    let chrono = ReportOptions { preset: Some(Preset::CHRONO), ..ReportOptions::default() };
+    check("human_preset_chrono", &shot(Units::IMPERIAL, "150.04", None, Some("2611.4")).to_human(&chrono));
+
+    let german = ReportOptions { locale: Locale::DE, sound_regime: true, ..ReportOptions::default() };
+    check("human_locale_de", &shot(Units::METRIC, "10.5", Some("800"), None).to_human(&german));
+}
+
+/// The binary prints the report of the library as is
+#[test]
+fn human_reports() {
+    check("human_metric", &stdout(&["-m", "10", "-s", "800"]));
+    check("human_imperial_downrange", &stdout(&["-i", "-m", "150", "-e", "2611", "--bc", "0.4", "-d", "300", "--diameter", "0.308"]));
+    check("human_bogus", &stdout(&["-m", "10", "-s", "800", "-e", "3200"]));
+    check("human_preset_chrono", &stdout(&["-i", "--preset", "chrono", "-m", "150.04", "-e", "2611.4"]));
+    check("human_locale_de", &stdout(&["--locale", "de", "-m", "10,5", "-s", "800", "--sound-regime"]));
+}
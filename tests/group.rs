@@ -0,0 +1,26 @@
+//! Thousands separators in human output, `--group-digits`.
+
+mod common;
+
+use common::stdout;
+use muzzle::Locale;
+
+#[test]
+fn groups_whole_part_by_locale() {
+    assert_eq!(Locale::EN.group_digits("12345.678"), "12,345.678");
+    assert_eq!(Locale::EN.group_digits("-1234567"), "-1,234,567");
+    assert_eq!(Locale::EN.group_digits("999.5"), "999.5");
+    assert_eq!(Locale::DE.group_digits("12345,678"), "12.345,678");
+    assert_eq!(Locale::EN.group_digits("inf"), "inf");
+}
+
+#[test]
+fn only_human_output_is_grouped() {
+    let shot = ["-m", "50", "-s", "900", "--group-digits"];
+    let with = |extra: &[&str]| stdout(&[&shot[..], extra].concat());
+
+    assert!(with(&[]).contains("Projectile energy:\t20,250.000 Joules"));
+    assert!(with(&["-f", "json"]).contains("\"energy\":20250"));
+    assert!(with(&["-f", "csv"]).contains(",20250,"));
+    assert!(with(&["--emit-command"]).contains("--energy 20250"));
+}
@@ -0,0 +1,28 @@
+//! Help on a single topic given after `--help`.
+
+mod common;
+
+fn muzzle(args: &[&str]) -> (bool, String) {
+    let output = common::muzzle(args);
+    (output.status.success(), String::from_utf8(output.stdout).unwrap())
+}
+
+#[test]
+fn energy_topic_prints_formula() {
+    let (success, help) = muzzle(&["--help", "energy"]);
+
+    assert!(success);
+    assert!(help.contains("energy = mass × speed² / 2"));
+    assert!(help.contains("-e, --energy NUMBER"));
+    assert!(!help.contains("USAGE"));
+}
+
+#[test]
+fn option_topics_and_plain_help() {
+    let (success, help) = muzzle(&["-h", "kgfm"]);
+    assert!(success);
+    assert!(help.contains("--kgfm") && !help.contains("--imperial"));
+
+    assert!(!muzzle(&["--help", "bogus"]).0);
+    assert!(muzzle(&["--help"]).1.starts_with("USAGE"));
+}
@@ -0,0 +1,52 @@
+//! Persistent calculation history, `--history` and `--show-history`.
+
+mod common;
+
+use muzzle::{history, run, Config, Units};
+use serde_json::Value;
+use std::env;
+use std::fs;
+
+fn params(speed: &str) -> muzzle::Params {
+    run(Config { units: Units::METRIC, mass: Some("10".to_owned()), speed: Some(speed.to_owned()), ..Config::default() }).unwrap()
+}
+
+#[test]
+fn appends_and_reads_back() {
+    let dir = env::temp_dir().join(format!("muzzle-history-{}", std::process::id()));
+    let path = dir.join("nested").join("history.jsonl");
+    let _ = fs::remove_dir_all(&dir);
+
+    assert_eq!(history::last_entries(&path, 5).unwrap(), (Vec::new(), 0));
+    for speed in ["700", "800", "900"] {
+        history::append(&path, &params(speed)).unwrap();
+    }
+    // A line broken by hand in between
+    fs::write(&path, fs::read_to_string(&path).unwrap().replacen('\n', "\n{\"mass\": 1\n", 1)).unwrap();
+
+    let (entries, skipped) = history::last_entries(&path, 2).unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(skipped, 1);
+    assert_eq!(entries, [params("800").to_json(), params("900").to_json()]);
+}
+
+#[test]
+fn cli_records_in_data_directory() {
+    let dir = env::temp_dir().join(format!("muzzle-history-cli-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    let muzzle = |args: &[&str]| {
+        let output = common::command().args(args).env("HOME", &dir).env("XDG_CONFIG_HOME", &dir).env("XDG_DATA_HOME", &dir).output().unwrap();
+        assert!(output.status.success());
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    muzzle(&["--history", "-m", "10", "-s", "800"]);
+    muzzle(&["--history", "-m", "10", "-s", "900"]);
+    let shown = muzzle(&["--show-history", "1"]);
+    let recorded = fs::read_to_string(dir.join("muzzle").join("history.jsonl")).unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(recorded.lines().count(), 2);
+    let entry: Value = serde_json::from_str(shown.trim()).unwrap();
+    assert_eq!(entry["speed"], 900.0);
+}
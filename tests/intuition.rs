@@ -0,0 +1,31 @@
+//! Everyday things of about the same energy, `--intuition`.
+
+mod common;
+
+use common::stdout;
+use muzzle::{run, Config, Params, Units, INTUITION_REFERENCES};
+
+fn nearest(params: &Params) -> &'static str {
+    params.nearest_reference(INTUITION_REFERENCES).unwrap().name
+}
+
+#[test]
+fn picks_reference_nearest_in_energy() {
+    // 3200 J is 3.3 times the walking car and a quarter of the parking one
+    let params = run(Config { units: Units::METRIC, mass: Some("10".to_owned()), speed: Some("800".to_owned()), ..Config::default() }).unwrap();
+    assert_eq!(nearest(&params), "car-walking");
+
+    // About 100 J either way
+    assert_eq!(nearest(&params.with_energy(110.0).unwrap()), "baseball");
+    assert_eq!(nearest(&params.with_energy(1.0).unwrap()), "apple");
+    assert_eq!(nearest(&params.in_units(Units::IMPERIAL).with_energy(9.0).unwrap()), "paintball");
+    assert!(params.nearest_reference(&[]).is_none());
+}
+
+#[test]
+fn off_by_default() {
+    let report = |extra: &[&str]| stdout(&[&["-m", "10", "-s", "800"][..], extra].concat());
+
+    assert!(!report(&[]).contains("roughly equivalent"));
+    assert!(report(&["--intuition"]).contains("roughly equivalent to a small car rolling at walking pace"));
+}
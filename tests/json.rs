@@ -0,0 +1,30 @@
+//! JSON documents of shot parameters and their schema version.
+
+mod common;
+
+use common::muzzle;
+use muzzle::{run, Config, Units, SCHEMA_VERSION};
+use serde_json::Value;
+
+#[test]
+fn documents_carry_schema_version() {
+    let params = run(Config { units: Units::IMPERIAL, mass: Some("150".to_owned()), speed: Some("2800".to_owned()), ..Config::default() }).unwrap();
+
+    for json in [params.to_json(), params.to_json_pretty()] {
+        let document: Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(document["schema_version"], SCHEMA_VERSION);
+        assert_eq!(document.as_object().unwrap().keys().next().map(String::as_str), Some("schema_version"));
+        assert_eq!(document["units"], "imperial");
+        assert_eq!(document["mass"], 150.0);
+    }
+}
+
+#[test]
+fn cli_prints_the_same_document() {
+    let output = muzzle(&["-f", "json", "-m", "10", "-s", "800"]);
+    assert!(output.status.success());
+
+    let document: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(document["schema_version"], SCHEMA_VERSION);
+    assert_eq!(document["energy"], 3200.0);
+}
@@ -0,0 +1,38 @@
+//! Keys bucketing loads by rounded mass, speed and energy, `Params::key`.
+
+use muzzle::{run, Config, MuzzleError, Params, Units};
+
+fn params(units: Units, mass: &str, speed: &str) -> Params {
+    run(Config { units, mass: Some(mass.to_owned()), speed: Some(speed.to_owned()), ..Config::default() }).unwrap()
+}
+
+#[test]
+fn buckets_by_precision() {
+    let load = params(Units::METRIC, "10", "800");
+    // All three given, so energy is as close as mass and speed
+    let close = run(Config {
+        units: Units::METRIC,
+        mass: Some("10.001".to_owned()),
+        speed: Some("800.001".to_owned()),
+        energy: Some("3200.001".to_owned()),
+        ..Config::default()
+    })
+    .unwrap();
+    let far = params(Units::METRIC, "10.1", "800");
+
+    assert_eq!(load.key(0.01).unwrap(), close.key(0.01).unwrap());
+    assert_ne!(load.key(0.0001).unwrap(), close.key(0.0001).unwrap());
+    assert_ne!(load.key(0.01).unwrap(), far.key(0.01).unwrap());
+    assert_eq!(load.key(100.0).unwrap(), far.key(100.0).unwrap());
+
+    // Same numbers in other units are another load
+    assert_ne!(load.key(0.01).unwrap(), params(Units::IMPERIAL, "10", "800").key(0.01).unwrap());
+}
+
+#[test]
+fn rejects_unusable_precision() {
+    let load = params(Units::METRIC, "10", "800");
+    for precision in [0.0, -0.01, f64::NAN, f64::INFINITY] {
+        assert!(matches!(load.key(precision), Err(MuzzleError::INVALID(_))), "{}", precision);
+    }
+}
@@ -0,0 +1,34 @@
+//! Metric energy in kilogram-force meters, `--kgfm`.
+
+mod common;
+
+use common::muzzle;
+use muzzle::{run, Config, EnergyUnit, Units};
+
+#[test]
+fn converts_by_standard_gravity() {
+    assert_eq!(EnergyUnit::KGFM.to_joules(1.0), 9.80665);
+    assert_eq!(EnergyUnit::KGFM.from_joules(9.80665), 1.0);
+    assert_eq!(EnergyUnit::JOULES.to_joules(42.0), 42.0);
+    assert!((EnergyUnit::KGFM.from_joules(EnergyUnit::KGFM.to_joules(2.5)) - 2.5).abs() < 1e-15);
+}
+
+#[test]
+fn energy_is_entered_in_kgfm_and_calculated_in_joules() {
+    let config = |units: Units| Config { units, mass: Some("10".to_owned()), energy: Some("100".to_owned()), energy_unit: EnergyUnit::KGFM, ..Config::default() };
+    let params = run(config(Units::METRIC)).unwrap();
+
+    assert!((params.energy - 980.665).abs() < 1e-9);
+    assert!((params.speed - (2.0 * 980.665 / 0.01f64).sqrt()).abs() < 1e-9);
+    // FPE is the only imperial energy unit
+    assert!(run(config(Units::IMPERIAL)).is_err());
+}
+
+#[test]
+fn cli_displays_kgfm() {
+    let output = muzzle(&["--kgfm", "-m", "10", "-s", "800"]);
+    assert!(output.status.success());
+
+    let report = String::from_utf8(output.stdout).unwrap();
+    assert!(report.contains(&format!("Projectile energy:\t{:.3} kgf·m", 3200.0 / 9.80665)), "{}", report);
+}
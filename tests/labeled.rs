@@ -0,0 +1,29 @@
+//! Labeled quantities of a shot, `for (label, value, unit) in &params`.
+
+use muzzle::{run, Config, Units};
+
+#[test]
+fn iterates_primary_then_optional() {
+    let config = Config { units: Units::IMPERIAL, mass: Some("150".to_owned()), speed: Some("2800".to_owned()), diameter: Some("0.308".to_owned()), ..Config::default() };
+    let params = run(config).unwrap();
+
+    let labels: Vec<String> = (&params).into_iter().map(|(label, ..)| label).collect();
+    assert_eq!(labels[..4], ["mass", "speed", "energy (derived)", "sectional density"]);
+
+    let mut rows = (&params).into_iter();
+    assert_eq!(rows.next(), Some(("mass".to_owned(), 150.0, "gr")));
+    assert_eq!(rows.next(), Some(("speed".to_owned(), 2800.0, "ft/s")));
+    let (_, energy, unit) = rows.next().unwrap();
+    assert_eq!((energy, unit), (params.energy, "ft·lbf"));
+}
+
+#[test]
+fn leaves_out_estimates_not_made() {
+    let params = run(Config { units: Units::METRIC, mass: Some("10".to_owned()), speed: Some("800".to_owned()), ..Config::default() }).unwrap();
+    let mut count = 0;
+    for (_, value, _) in &params {
+        assert!(value.is_finite());
+        count += 1;
+    }
+    assert_eq!(count, 3);
+}
@@ -0,0 +1,34 @@
+//! LaTeX rendering of a calculation, `--format latex`.
+
+mod common;
+
+use common::muzzle;
+use muzzle::{run, Config, Units};
+
+#[test]
+fn energy_case_shows_its_formula() {
+    let params = run(Config { units: Units::METRIC, mass: Some("10".to_owned()), speed: Some("800".to_owned()), ..Config::default() }).unwrap();
+    let latex = params.to_latex();
+
+    assert!(latex.starts_with("\\begin{align*}\n") && latex.ends_with("\n\\end{align*}"));
+    assert!(latex.contains(r"E &= \frac{1}{2} \cdot \frac{m}{1000} \cdot v^2 = 3200.000\,\mathrm{J}"));
+    assert!(latex.contains(r"m &= 10.000\,\mathrm{g} \\"));
+    // Only the derived line has a formula
+    assert_eq!(latex.matches(r"\frac").count(), 2);
+}
+
+#[test]
+fn imperial_shows_gravity_constant() {
+    let params = run(Config { units: Units::IMPERIAL, mass: Some("150".to_owned()), energy: Some("2611".to_owned()), ..Config::default() }).unwrap();
+    let latex = params.to_latex();
+
+    assert!(latex.contains(r"v &= \sqrt{\frac{2 g E}{m / 7000}} = "));
+    assert!(latex.contains(r"g &= 32.174\,\mathrm{ft/s^2}"));
+}
+
+#[test]
+fn cli_prints_latex() {
+    let output = muzzle(&["-f", "latex", "-m", "10", "-s", "800"]);
+    assert!(output.status.success());
+    assert!(String::from_utf8(output.stdout).unwrap().contains(r"\begin{align*}"));
+}
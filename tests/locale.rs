@@ -0,0 +1,27 @@
+//! Decimal separators and unit labels of human input and output, `--locale`.
+
+mod common;
+
+use common::stdout;
+use muzzle::{Locale, Units};
+
+#[test]
+fn german_round_trips_decimal_comma() {
+    let locale = Locale::from_tag("de_DE").unwrap();
+    let normalized = locale.normalize_number("9,81");
+
+    assert_eq!(normalized, "9.81");
+    assert_eq!(locale.format_number(normalized.parse().unwrap(), 2), "9,81");
+    assert_eq!(Locale::EN.normalize_number("9,81"), "9,81");
+    assert_eq!(locale.unit_labels(Units::METRIC), ("Gramm", "m/s", "Joule"));
+    assert!(Locale::from_tag("fr").is_err());
+}
+
+#[test]
+fn cli_reads_and_writes_decimal_comma() {
+    let report = stdout(&["--locale", "de", "-m", "9,81", "-s", "800"]);
+
+    assert!(report.contains("Projectile mass:\t9,810 Gramm"));
+    assert!(report.contains("Projectile energy:\t3139,200 Joule"));
+    assert_eq!(stdout(&["-m", "9.81", "-s", "800"]).replace(" grams", " Gramm").replace(" Joules", " Joule").replace('.', ","), report);
+}
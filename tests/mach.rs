@@ -0,0 +1,35 @@
+//! Speed given as a Mach number, e.g. `-s 2.5mach`, at adjustable temperature.
+
+use muzzle::{run, Config, Units};
+
+fn speed(units: Units, speed: &str, temperature: Option<&str>) -> f64 {
+    let config = Config {
+        units,
+        mass: Some("10".to_owned()),
+        speed: Some(speed.to_owned()),
+        temperature: temperature.map(str::to_owned),
+        ..Config::default()
+    };
+    run(config).unwrap().speed
+}
+
+#[test]
+fn mach_one_is_about_343_meters_per_second() {
+    // 340.3 m/s at the 15 °C standard, the 343 m/s usually quoted is that of 20 °C air
+    let standard = speed(Units::METRIC, "1mach", None);
+    assert!((standard - 340.3).abs() < 0.1);
+    assert!((standard / 343.0 - 1.0).abs() < 0.01);
+    assert!((speed(Units::METRIC, "1mach", Some("20")) - 343.2).abs() < 0.1);
+}
+
+#[test]
+fn suffix_scales_and_converts() {
+    let standard = speed(Units::METRIC, "1mach", None);
+    assert!((speed(Units::METRIC, "2.5 MACH", None) - 2.5 * standard).abs() < 1e-9);
+    // 59 °F is the same standard temperature
+    assert!((speed(Units::IMPERIAL, "1mach", None) - standard / 0.3048).abs() < 1e-9);
+    assert!((speed(Units::IMPERIAL, "1mach", Some("59")) - standard / 0.3048).abs() < 1e-9);
+
+    let malformed = Config { units: Units::METRIC, mass: Some("10".to_owned()), speed: Some("fastmach".to_owned()), ..Config::default() };
+    assert!(run(malformed).is_err());
+}
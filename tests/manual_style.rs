@@ -0,0 +1,26 @@
+//! Rounding like reloading manuals do, `--manual-style`.
+
+mod common;
+
+use common::{muzzle, stdout};
+
+#[test]
+fn rounds_like_manuals() {
+    let imperial = stdout(&["-i", "-m", "150.04", "-s", "2803.4", "--manual-style"]);
+    assert!(imperial.contains("Projectile mass:\t150.0 grains"), "{}", imperial);
+    assert!(imperial.contains("Projectile speed:\t2803 FPS"), "{}", imperial);
+    assert!(imperial.contains("Projectile energy:\t2618 FPE"), "{}", imperial);
+
+    let metric = stdout(&["-m", "9.7234", "-s", "823.6", "--manual-style"]);
+    assert!(metric.contains("Projectile mass:\t9.72 grams"), "{}", metric);
+    assert!(metric.contains("Projectile speed:\t824 m/s"), "{}", metric);
+}
+
+#[test]
+fn conflicts_with_other_rounding() {
+    for other in [&["--round-to", "5"][..], &["--preset", "chrono"]] {
+        let output = muzzle(&[&["-m", "10", "-s", "800", "--manual-style"], other].concat());
+        assert_eq!(output.status.code(), Some(1));
+        assert!(String::from_utf8_lossy(&output.stderr).contains("only one of --round-to, --manual-style and --preset"));
+    }
+}
@@ -0,0 +1,28 @@
+//! Markdown table output, `--format markdown-table`.
+
+mod common;
+
+use common::muzzle;
+use muzzle::{run, Config, Units};
+
+#[test]
+fn renders_aligned_table() {
+    let params = run(Config { units: Units::METRIC, mass: Some("10".to_owned()), speed: Some("800".to_owned()), ..Config::default() }).unwrap();
+    let table = params.to_markdown();
+    let lines: Vec<&str> = table.lines().collect();
+
+    assert_eq!(lines[0], "| Quantity         |    Value | Unit |");
+    assert_eq!(lines[1], "| ---------------- | -------: | ---- |");
+    assert_eq!(lines[2], "| mass             |   10.000 | g    |");
+    assert_eq!(lines[4], "| energy (derived) | 3200.000 | J    |");
+    assert_eq!(lines.len(), 5);
+    assert!(lines.iter().all(|line| line.chars().count() == lines[0].chars().count()));
+}
+
+#[test]
+fn cli_adds_optional_estimates() {
+    let output = muzzle(&["-m", "10", "-s", "800", "--diameter", "7.82", "-f", "markdown-table"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("| sectional density |    0.164 | g/mm² |"), "{}", stdout);
+}
@@ -0,0 +1,41 @@
+//! Loads of one energy across a range of masses, `--mass-range`.
+
+mod common;
+
+use common::muzzle;
+use muzzle::{energy_sweep, Units};
+
+#[test]
+fn momentum_grows_with_mass_at_fixed_energy() {
+    let table = energy_sweep(Units::IMPERIAL, 2600.0, 110.0, 180.0, 10.0).unwrap();
+
+    assert_eq!(table.len(), 8);
+    assert_eq!((table[0].mass, table[7].mass), (110.0, 180.0));
+    for params in &table {
+        assert!((params.energy - 2600.0).abs() < 1e-9);
+    }
+    for pair in table.windows(2) {
+        assert!(pair[1].momentum() > pair[0].momentum());
+        assert!(pair[1].power_factor() > pair[0].power_factor());
+        assert!(pair[1].speed < pair[0].speed);
+    }
+}
+
+#[test]
+fn incorrect_range_is_rejected() {
+    assert!(energy_sweep(Units::METRIC, 3200.0, 0.0, 10.0, 1.0).is_err());
+    assert!(energy_sweep(Units::METRIC, 3200.0, 10.0, 5.0, 1.0).is_err());
+    assert!(energy_sweep(Units::METRIC, 3200.0, 5.0, 10.0, 0.0).is_err());
+}
+
+#[test]
+fn cli_prints_a_row_per_mass() {
+    let output = muzzle(&["-e", "3200", "--mass-range", "8:12:1"]);
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 6);
+    assert!(lines[0].starts_with("Mass (grams)"));
+    assert!(lines[3].starts_with("10.000\t800.000\t"));
+}
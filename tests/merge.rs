@@ -0,0 +1,45 @@
+//! Partial configs layered on top of each other, `Config::merge`.
+
+use muzzle::{run, Config, DragModel, Units};
+
+#[test]
+fn overrides_fill_and_replace() {
+    let profile = Config {
+        units: Units::METRIC,
+        mass: Some("10".to_owned()),
+        speed: Some("700".to_owned()),
+        bc: Some("0.4".to_owned()),
+        ..Config::default()
+    };
+    let overrides = Config {
+        units: Units::IMPERIAL,
+        speed: Some("2800".to_owned()),
+        distance: Some("100".to_owned()),
+        drag_model: DragModel::G7,
+        ..Config::default()
+    };
+    let merged = profile.merge(overrides);
+
+    // Kept from the profile, replaced by and filled in from the overrides
+    assert_eq!(merged.mass.as_deref(), Some("10"));
+    assert_eq!(merged.bc.as_deref(), Some("0.4"));
+    assert_eq!(merged.speed.as_deref(), Some("2800"));
+    assert_eq!(merged.distance.as_deref(), Some("100"));
+    assert_eq!(merged.energy, None);
+    assert_eq!(merged.units, Units::IMPERIAL);
+    assert!(matches!(merged.drag_model, DragModel::G7));
+
+    let params = run(merged).unwrap();
+    assert_eq!((params.mass, params.speed), (10.0, 2800.0));
+    assert!(params.retained_speed.is_some());
+}
+
+#[test]
+fn empty_overrides_keep_everything_but_units() {
+    let profile = Config { units: Units::IMPERIAL, mass: Some("150".to_owned()), speed: Some("2800".to_owned()), ..Config::default() };
+    let merged = profile.merge(Config::default());
+
+    assert_eq!(merged.mass.as_deref(), Some("150"));
+    assert_eq!(merged.speed.as_deref(), Some("2800"));
+    assert_eq!(merged.units, Units::METRIC);
+}
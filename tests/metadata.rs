@@ -0,0 +1,33 @@
+//! Version, timestamp and input of a calculation in JSON output, `--metadata`.
+
+mod common;
+
+use common::muzzle;
+use muzzle::{Config, Metadata, Units, VERSION};
+use serde_json::{json, Value};
+
+#[test]
+fn records_version_time_and_input() {
+    let input = Config { units: Units::IMPERIAL, mass: Some("150".to_owned()), speed: Some("2800".to_owned()), ..Config::default() };
+    let metadata = Metadata { version: VERSION, timestamp: 951_825_600, input };
+    let value = metadata.to_json_value();
+
+    assert_eq!(value["version"], env!("CARGO_PKG_VERSION"));
+    // Leap day of a year divisible by 400
+    assert_eq!(value["timestamp"], "2000-02-29T12:00:00Z");
+    assert_eq!(value["input"]["mass"], "150");
+    assert_eq!(value["input"]["units"], "imperial");
+    assert!(value["input"].get("energy").is_none());
+}
+
+#[test]
+fn cli_adds_meta_key() {
+    let output = muzzle(&["-m", "10", "-s", "800", "-f", "json", "--metadata"]);
+    assert!(output.status.success());
+
+    let document: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(document["meta"]["version"], VERSION);
+    assert_eq!(document["meta"]["input"]["speed"], json!("800"));
+    let timestamp = document["meta"]["timestamp"].as_str().unwrap();
+    assert!(timestamp.len() == 20 && timestamp.ends_with('Z'), "{}", timestamp);
+}
@@ -0,0 +1,33 @@
+//! Speed matching the momentum of another load, `--match-load`.
+
+mod common;
+
+use common::muzzle;
+use muzzle::{run, Config, Quantity, Units};
+
+fn load(units: Units, mass: &str, speed: Option<&str>, momentum: Option<String>) -> Config {
+    Config { units, mass: Some(mass.to_owned()), speed: speed.map(str::to_owned), momentum, ..Config::default() }
+}
+
+#[test]
+fn derived_load_has_target_momentum() {
+    for (units, reference_mass, reference_speed, mass) in [(Units::IMPERIAL, "124", "1100", "147"), (Units::METRIC, "8", "350", "10.2")] {
+        let reference = run(load(units, reference_mass, Some(reference_speed), None)).unwrap();
+        let target = reference.momentum();
+
+        let matched = run(load(units, mass, None, Some(muzzle::format_machine(target)))).unwrap();
+        assert_eq!(matched.derived, Some(Quantity::SPEED));
+        assert!((matched.momentum() - target).abs() <= 1e-12 * target);
+        assert!(matched.speed < reference.speed);
+    }
+}
+
+#[test]
+fn match_load_flag_reports_matched_momentum() {
+    let output = muzzle(&["-i", "-m", "147", "--match-load", "124@1100"]);
+    let report = String::from_utf8(output.stdout).unwrap();
+
+    assert!(output.status.success());
+    assert!(report.contains("Projectile speed:\t927.891 FPS"));
+    assert!(report.contains("Matched momentum:\t19.486 lb·ft/s"));
+}
@@ -0,0 +1,27 @@
+//! Reproducibility of Monte Carlo sampling, `cargo test --features monte-carlo`.
+#![cfg(feature = "monte-carlo")]
+
+use muzzle::monte_carlo::sample_energy;
+use muzzle::{run, Config, Units};
+
+#[test]
+fn fixed_seed_is_deterministic() {
+    let params = run(Config { units: Units::METRIC, mass: Some("10".to_owned()), speed: Some("800".to_owned()), ..Config::default() }).unwrap();
+
+    let first = sample_energy(&params, 0.05, 10.0, 2000, 42).unwrap();
+    assert_eq!(first, sample_energy(&params, 0.05, 10.0, 2000, 42).unwrap());
+    assert_ne!(first, sample_energy(&params, 0.05, 10.0, 2000, 43).unwrap());
+
+    assert!(first.p5 < first.p50 && first.p50 < first.p95);
+    assert!((first.p50 - params.energy).abs() < 0.01 * params.energy);
+}
+
+#[test]
+fn no_spread_is_the_shot_itself() {
+    let params = run(Config { units: Units::IMPERIAL, mass: Some("150".to_owned()), speed: Some("2800".to_owned()), ..Config::default() }).unwrap();
+    let distribution = sample_energy(&params, 0.0, 0.0, 10, 1).unwrap();
+
+    for value in [distribution.mean, distribution.p5, distribution.p50, distribution.p95] {
+        assert!((value - params.energy).abs() < 1e-9 * params.energy);
+    }
+}
@@ -0,0 +1,27 @@
+//! One gate on ANSI escape sequences, honouring `--color`, `NO_COLOR` and output that is not a terminal.
+
+use muzzle::{colors, escapes_allowed, ColorChoice, ColorTheme};
+use std::env;
+
+// NO_COLOR is process wide, so this stays the only test of the file
+#[test]
+fn honours_no_color_and_terminals() {
+    env::remove_var("NO_COLOR");
+    assert!(escapes_allowed(ColorChoice::AUTO, true));
+    assert!(!escapes_allowed(ColorChoice::AUTO, false));
+    assert!(escapes_allowed(ColorChoice::ALWAYS, false));
+    assert!(!escapes_allowed(ColorChoice::NEVER, true));
+    assert_eq!(colors(ColorChoice::AUTO, ColorTheme::LIGHT, true), Some(ColorTheme::LIGHT));
+
+    // Whatever was chosen
+    env::set_var("NO_COLOR", "1");
+    for choice in [ColorChoice::AUTO, ColorChoice::ALWAYS, ColorChoice::NEVER] {
+        assert!(!escapes_allowed(choice, true), "{:?}", choice);
+    }
+    assert_eq!(colors(ColorChoice::ALWAYS, ColorTheme::DARK, true), None);
+
+    // Set but empty does not count, as no-color.org has it
+    env::set_var("NO_COLOR", "");
+    assert!(escapes_allowed(ColorChoice::AUTO, true));
+    env::remove_var("NO_COLOR");
+}
@@ -0,0 +1,34 @@
+//! Inputs validated and converted without deriving anything, `--no-derive`.
+
+mod common;
+
+use common::muzzle;
+use muzzle::{normalize, run, Config, MuzzleError, Quantity, Units};
+use serde_json::Value;
+
+#[test]
+fn single_parameter_passes_through() {
+    let config = Config { units: Units::METRIC, mass: Some("10".to_owned()), ..Config::default() };
+    assert!(matches!(run(config.clone()), Err(MuzzleError::INSUFFICIENT)));
+    assert_eq!(normalize(&config).unwrap(), vec![(Quantity::MASS, 10.0)]);
+
+    let output = muzzle(&["--no-derive", "-m", "10", "-f", "json"]);
+    assert!(output.status.success());
+    let document: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(document["units"], "metric");
+    assert_eq!(document["mass"], 10.0);
+    assert!(document["speed"].is_null() && document["energy"].is_null());
+}
+
+#[test]
+fn inputs_are_converted_and_checked() {
+    let config = Config { units: Units::METRIC, mass: Some("1dr".to_owned()), energy: Some("2".to_owned()), ..Config::default() };
+    let given = normalize(&config).unwrap();
+    assert_eq!(given.iter().map(|&(quantity, _)| quantity).collect::<Vec<_>>(), [Quantity::MASS, Quantity::ENERGY]);
+    assert!((given[0].1 - 27.34375 / 15.432358352941431).abs() < 1e-12);
+
+    let negative = Config { units: Units::METRIC, speed: Some("-5".to_owned()), ..Config::default() };
+    assert!(matches!(normalize(&negative), Err(MuzzleError::INVALID(_))));
+    assert!(matches!(normalize(&Config::default()), Err(MuzzleError::EMPTY)));
+    assert!(!muzzle(&["--no-derive", "-s", "-5"]).status.success());
+}
@@ -0,0 +1,29 @@
+//! Indices against a 100 gr at 1000 FPS load, `--normalize`.
+
+use muzzle::{run, Config, Units, NORMALIZATION_REFERENCE};
+
+fn ratios(units: Units, mass: &str, speed: &str) -> [(&'static str, f64); 5] {
+    run(Config { units, mass: Some(mass.to_owned()), speed: Some(speed.to_owned()), ..Config::default() }).unwrap().ratios(&NORMALIZATION_REFERENCE)
+}
+
+#[test]
+fn reference_itself_is_one() {
+    let imperial = ratios(Units::IMPERIAL, "100", "1000");
+    let metric = ratios(Units::METRIC, &(100.0 * 0.06479891).to_string(), &(1000.0 * 0.3048).to_string());
+    for ((name, imperial), (_, metric)) in imperial.into_iter().zip(metric) {
+        assert!((imperial - 1.0).abs() < 1e-9, "{}: {}", name, imperial);
+        assert!((metric - 1.0).abs() < 1e-6, "{}: {}", name, metric);
+    }
+}
+
+#[test]
+fn scales_like_the_quantities() {
+    // Twice the speed of the reference: momentum doubles, energy quadruples
+    let indices = ratios(Units::IMPERIAL, "100", "2000");
+    let names: Vec<&str> = indices.iter().map(|(name, _)| *name).collect();
+    assert_eq!(names, ["mass", "speed", "energy", "momentum", "power factor"]);
+    let values: Vec<f64> = indices.iter().map(|(_, ratio)| *ratio).collect();
+    for (value, expected) in values.into_iter().zip([1.0, 2.0, 4.0, 2.0, 2.0]) {
+        assert!((value - expected).abs() < 1e-9, "{} {}", value, expected);
+    }
+}
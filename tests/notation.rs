@@ -0,0 +1,33 @@
+//! Per-quantity notation of structured output, switching to scientific past its threshold.
+
+use muzzle::{run, Config, FormatPolicy, Notation, Params, Units, ENERGY_SCIENTIFIC_FROM};
+
+fn shot(mass: &str, energy: &str) -> Params {
+    run(Config { units: Units::METRIC, mass: Some(mass.to_owned()), energy: Some(energy.to_owned()), ..Config::default() }).unwrap()
+}
+
+#[test]
+fn energy_switches_at_threshold() {
+    let below = shot("10", &(ENERGY_SCIENTIFIC_FROM / 2.0).to_string());
+    let above = shot("10", &(ENERGY_SCIENTIFIC_FROM * 2.0).to_string());
+
+    assert!(below.to_json().contains(r#""energy":500000.0,"#));
+    assert!(above.to_json().contains(r#""energy":2e6,"#));
+    assert!(above.to_csv().contains(",2e6,"));
+
+    // Still a number, and the same one
+    let document: serde_json::Value = serde_json::from_str(&above.to_json_pretty()).unwrap();
+    assert_eq!(document["energy"].as_f64(), Some(2e6));
+}
+
+#[test]
+fn fixed_mass_has_no_exponent() {
+    let params = shot("0.000001", "1");
+
+    // `serde_json` alone would write 1e-6
+    assert!(params.to_json().contains(r#""mass":0.000001,"#));
+
+    let policy = FormatPolicy { mass: Notation::SCIENTIFIC(0.0), ..FormatPolicy::default() };
+    assert!(params.to_json_with_policy(&policy, false).contains(r#""mass":1e-6,"#));
+    assert!(params.to_csv_with_policy(&policy).starts_with("metric,1e-6,"));
+}
@@ -0,0 +1,35 @@
+//! Field order of the human report, `--order`.
+
+mod common;
+
+use std::process::Output;
+
+fn muzzle(args: &[&str]) -> Output {
+    common::muzzle(&[&["-m", "10", "-s", "800"][..], args].concat())
+}
+
+fn fields(output: &Output) -> Vec<String> {
+    assert!(output.status.success());
+    String::from_utf8_lossy(&output.stdout).lines().filter_map(|line| line.strip_prefix("Projectile ")).map(|line| line.split(':').next().unwrap().to_owned()).collect()
+}
+
+#[test]
+fn custom_order_renders_in_that_order() {
+    assert_eq!(fields(&muzzle(&[])), ["mass", "speed", "energy"]);
+    assert_eq!(fields(&muzzle(&["--order", "energy,mass,speed"])), ["energy", "mass", "speed"]);
+    assert_eq!(fields(&muzzle(&["--order", " speed , energy"])), ["speed", "energy"]);
+
+    let output = muzzle(&["--order", "energy,mass"]);
+    assert!(output.status.success());
+    let report = String::from_utf8(output.stdout).unwrap();
+    assert!(report.contains("Projectile energy:\t3200.000 Joules\nProjectile mass:\t10.000 grams\n"));
+}
+
+#[test]
+fn unknown_and_repeated_fields_are_rejected() {
+    for order in ["energy,mass,mass", "energy,weight", ""] {
+        let output = muzzle(&["--order", order]);
+        assert!(!output.status.success(), "--order {:?}", order);
+        assert!(String::from_utf8_lossy(&output.stderr).contains("--order"));
+    }
+}
@@ -0,0 +1,29 @@
+//! Loads ordered by energy, `Params::cmp_by_energy`.
+
+use muzzle::{run, Config, Params, Units};
+use std::cmp::Ordering;
+
+fn load(mass: &str, speed: &str) -> Params {
+    run(Config { units: Units::IMPERIAL, mass: Some(mass.to_owned()), speed: Some(speed.to_owned()), ..Config::default() }).unwrap()
+}
+
+#[test]
+fn sorts_by_energy_descending() {
+    let mut loads = [load("55", "3200"), load("180", "2700"), load("150", "2800"), load("40", "1200")];
+    loads.sort_by(|a, b| b.cmp_by_energy(a));
+
+    let masses: Vec<f64> = loads.iter().map(|params| params.mass).collect();
+    assert_eq!(masses, [180.0, 150.0, 55.0, 40.0]);
+    assert!(loads.windows(2).all(|pair| pair[0].energy >= pair[1].energy));
+}
+
+#[test]
+fn ties_are_broken_by_momentum() {
+    // Same energy, the heavier and slower one carries more momentum
+    let light = load("100", "2000");
+    let heavy = light.with_mass(400.0).with_energy(light.energy).unwrap();
+
+    assert!((heavy.energy - light.energy).abs() < 1e-9 * light.energy);
+    assert_eq!(heavy.cmp_by_energy(&light), Ordering::Greater);
+    assert_eq!(light.cmp_by_energy(&light), Ordering::Equal);
+}
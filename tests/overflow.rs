@@ -0,0 +1,38 @@
+//! Huge inputs overflowing to infinity, reported as errors rather than printed as `inf`.
+
+mod common;
+
+use common::muzzle;
+use muzzle::{run, Config, MuzzleError, Units};
+
+fn energy_of(mass: &str, speed: &str) -> Result<f64, MuzzleError> {
+    run(Config { units: Units::METRIC, mass: Some(mass.to_owned()), speed: Some(speed.to_owned()), ..Config::default() }).map(|params| params.energy)
+}
+
+#[test]
+fn overflowing_result_is_domain_error() {
+    // Squared, twice the root of the largest float is past it
+    let speed = (2.0 * f64::MAX.sqrt()).to_string();
+    assert!(matches!(energy_of("1000", &speed), Err(MuzzleError::DOMAIN(name)) if name == "energy"));
+
+    // Just below the root the square still fits
+    let speed = (0.9 * f64::MAX.sqrt()).to_string();
+    assert!(energy_of("1000", &speed).unwrap().is_finite());
+}
+
+#[test]
+fn non_finite_input_is_parse_error() {
+    for speed in ["1e400", "inf", "NaN"] {
+        assert!(matches!(energy_of("10", speed), Err(MuzzleError::PARSE(_))), "{}", speed);
+    }
+}
+
+#[test]
+fn cli_prints_no_inf() {
+    let speed = (2.0 * f64::MAX.sqrt()).to_string();
+    let output = muzzle(&["-m", "1000", "-s", &speed]);
+
+    assert!(!output.status.success());
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("inf"));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Calculated energy is not a finite number"));
+}
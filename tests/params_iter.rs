@@ -0,0 +1,40 @@
+//! Shot specs read and run one line at a time, `ParamsIter`.
+
+use muzzle::{MuzzleError, ParamsIter, Quantity, Units};
+use std::io::Cursor;
+
+#[test]
+fn iterates_over_multi_line_reader() {
+    let input = "# mass,speed,energy\n150,2800,\n\n,2800,2611\n150,,2611\n150,2800,2611\n";
+    let results: Vec<_> = ParamsIter::new(Cursor::new(input), Units::IMPERIAL).collect::<Result<_, _>>().unwrap();
+
+    assert_eq!(results.len(), 4);
+    let derived: Vec<Option<Quantity>> = results.iter().map(|params| params.derived).collect();
+    assert_eq!(derived, [Some(Quantity::ENERGY), Some(Quantity::MASS), Some(Quantity::SPEED), None]);
+    assert!(results.iter().all(|params| params.units == Units::IMPERIAL));
+    assert!((results[0].energy - 2610.8037545844472).abs() < 1e-9);
+}
+
+#[test]
+fn malformed_lines_do_not_stop_iteration() {
+    let input = "10,800,\n10,800\n10,,\nten,800,\n10,900,\n";
+    let results: Vec<_> = ParamsIter::new(Cursor::new(input), Units::METRIC).collect();
+
+    assert_eq!(results.len(), 5);
+    assert_eq!(results[0].as_ref().unwrap().energy, 3200.0);
+    assert!(matches!(results[1], Err(MuzzleError::INVALID(_))));
+    assert!(matches!(results[2], Err(MuzzleError::INSUFFICIENT)));
+    assert!(matches!(results[3], Err(MuzzleError::PARSE(_))));
+    assert_eq!(results[4].as_ref().unwrap().energy, 4050.0);
+}
+
+#[test]
+fn reads_spreadsheet_exports() {
+    let input = "\u{feff}150,2800,\r\n\r\n # padded \r\n 150 , 2800 , \r\n150,,2610.80";
+    let results: Vec<_> = ParamsIter::new(Cursor::new(input), Units::IMPERIAL).collect::<Result<_, _>>().unwrap();
+
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0].energy, results[1].energy);
+    assert_eq!(results[1].mass, 150.0);
+    assert!((results[2].speed - 2800.0).abs() < 0.01);
+}
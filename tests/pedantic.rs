@@ -0,0 +1,38 @@
+//! Hints about false precision in input values, `--pedantic`.
+
+mod common;
+
+use common::muzzle;
+use muzzle::significant_digits;
+
+#[test]
+fn counts_digits_as_written() {
+    assert_eq!(significant_digits("2800"), 2);
+    assert_eq!(significant_digits("2800."), 4);
+    assert_eq!(significant_digits("0.0150"), 3);
+    assert_eq!(significant_digits("2834.7281937"), 11);
+    assert_eq!(significant_digits("1.5e3"), 2);
+}
+
+#[test]
+fn hints_for_over_precise_input_only() {
+    let precise = muzzle(&["-p", "-m", "10", "-s", "2834.7281937"]);
+    assert!(precise.status.success());
+    assert!(String::from_utf8_lossy(&precise.stderr).contains("speed `2834.7281937` has 11 significant digits"));
+
+    let reasonable = muzzle(&["-p", "-m", "10", "-s", "800"]);
+    assert!(reasonable.status.success());
+    assert!(!String::from_utf8_lossy(&reasonable.stderr).contains("significant digits"));
+
+    // The value is used as given either way
+    let plain = muzzle(&["-m", "10", "-s", "2834.7281937"]);
+    assert!(plain.status.success());
+    assert_eq!(precise.stdout, plain.stdout);
+}
+
+#[test]
+fn limit_is_adjustable() {
+    let limited = muzzle(&["-p", "--limit-sig-digits", "2", "-m", "10", "-s", "825"]);
+    assert!(limited.status.success());
+    assert!(String::from_utf8_lossy(&limited.stderr).contains("speed `825` has 3 significant digits"));
+}
@@ -0,0 +1,24 @@
+//! Metric results for masses in grains, as air rifle pellets are sold by, `--pellet`.
+
+mod common;
+
+use common::muzzle;
+use muzzle::{run, Config, Units};
+
+#[test]
+fn converts_grains_to_grams_first() {
+    let pellet = run(Config { units: Units::METRIC, mass: Some("8.44".to_owned()), mass_in_grains: true, speed: Some("270".to_owned()), ..Config::default() }).unwrap();
+    assert!((pellet.mass - 8.44 * 0.06479891).abs() < 1e-12);
+    assert!((pellet.energy - 19.935).abs() < 0.001, "{}", pellet.energy);
+
+    // Speed derived from energy lands on the same mass
+    let derived = run(Config { units: Units::METRIC, mass: Some("8.44".to_owned()), mass_in_grains: true, energy: Some(pellet.energy.to_string()), ..Config::default() }).unwrap();
+    assert!((derived.speed - 270.0).abs() < 1e-9);
+}
+
+#[test]
+fn cli_shows_both_masses() {
+    let output = muzzle(&["-m", "8.44", "-s", "270", "--pellet"]);
+    assert!(output.status.success());
+    assert!(String::from_utf8(output.stdout).unwrap().contains("Projectile mass:\t0.547 grams (8.440 grains)"));
+}
@@ -0,0 +1,39 @@
+//! Shot parameters with one quantity changed, `Params::with_speed`, `with_mass` and `with_energy`.
+
+use muzzle::{run, Config, MuzzleError, Params, Quantity, Units};
+
+fn load(units: Units, mass: &str, speed: &str) -> Params {
+    run(Config { units, mass: Some(mass.to_owned()), speed: Some(speed.to_owned()), ..Config::default() }).unwrap()
+}
+
+/// Whether energy agrees with mass and speed as `run` derives it
+fn consistent(params: &Params) -> bool {
+    let expected = load(params.units, &params.mass.to_string(), &params.speed.to_string()).energy;
+    (params.energy - expected).abs() <= 1e-12 * expected
+}
+
+#[test]
+fn results_stay_consistent() {
+    for units in Units::ALL {
+        let params = load(units, "150", "2800");
+
+        let faster = params.with_speed(3000.0);
+        assert_eq!((faster.mass, faster.speed, faster.derived), (params.mass, 3000.0, Some(Quantity::ENERGY)));
+        assert!(consistent(&faster));
+
+        let heavier = params.with_mass(180.0);
+        assert_eq!((heavier.mass, heavier.speed, heavier.derived), (180.0, params.speed, Some(Quantity::ENERGY)));
+        assert!(consistent(&heavier));
+
+        let stronger = params.with_energy(params.energy * 4.0).unwrap();
+        assert_eq!((stronger.mass, stronger.energy, stronger.derived), (params.mass, params.energy * 4.0, Some(Quantity::SPEED)));
+        assert!((stronger.speed - 2.0 * params.speed).abs() < 1e-9);
+        assert!(consistent(&stronger));
+    }
+}
+
+#[test]
+fn negative_energy_has_no_speed() {
+    let params = load(Units::METRIC, "10", "800");
+    assert!(matches!(params.with_energy(-1.0), Err(MuzzleError::DOMAIN(_))));
+}
@@ -0,0 +1,32 @@
+//! JSON config piped to stdin when no options are given.
+
+mod common;
+
+use common::muzzle_with_stdin;
+use muzzle::{run, Config};
+
+#[test]
+fn piped_json_is_run() {
+    let output = muzzle_with_stdin(&[], r#"{"units":"imperial","mass":"150","speed":2800}"#);
+    assert!(output.status.success());
+
+    let report = String::from_utf8(output.stdout).unwrap();
+    assert!(report.contains("Projectile energy:\t2610.804 FPE"), "{}", report);
+
+    let config: Config = serde_json::from_str(r#"{"units":"imperial","mass":"150","speed":2800}"#).unwrap();
+    assert_eq!(run(config).unwrap().energy, 2610.8037545844472);
+}
+
+#[test]
+fn invalid_or_ignored_input_falls_back() {
+    // Not JSON, warned about and treated as no input at all: usage, as without any options
+    let output = muzzle_with_stdin(&[], "150,2800,");
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Ignoring piped input as it is not a valid JSON config"));
+    assert!(String::from_utf8_lossy(&output.stdout).starts_with("USAGE"));
+
+    // Options given, stdin is not looked at
+    let output = muzzle_with_stdin(&["-m", "10", "-s", "800"], r#"{"mass":"20","speed":"900"}"#);
+    assert!(output.status.success());
+    assert!(String::from_utf8(output.stdout).unwrap().contains("Projectile mass:\t10.000 grams"));
+}
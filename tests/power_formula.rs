@@ -0,0 +1,33 @@
+//! Stopping power formulas, `PowerFormula`.
+
+use muzzle::{run, Config, Params, PowerFormula, Units};
+
+fn params(units: Units, mass: &str, speed: &str) -> Params {
+    run(Config { units, mass: Some(mass.to_owned()), speed: Some(speed.to_owned()), ..Config::default() }).unwrap()
+}
+
+#[test]
+fn scores_known_loads() {
+    // .458 Win Mag, 500 gr at 2100 FPS: Taylor KO 68.7
+    let elephant = params(Units::IMPERIAL, "500", "2100");
+    let taylor = PowerFormula::TAYLOR.score(&elephant, Some(0.458)).unwrap();
+    assert!((taylor - 500.0 * 2100.0 * 0.458 / 7000.0).abs() < 1e-9);
+    assert!((taylor - 68.7).abs() < 0.05);
+    assert_eq!(PowerFormula::TAYLOR.score(&elephant, None), None);
+
+    let deer = params(Units::IMPERIAL, "150", "2800");
+    let ogw = PowerFormula::OGW.score(&deer, None).unwrap();
+    assert!((ogw - 2800f64.powi(3) * 150f64.powi(2) * 1.5e-12).abs() < 1e-9);
+    assert!((PowerFormula::PF.score(&deer, None).unwrap() - 420.0).abs() < 1e-9);
+}
+
+#[test]
+fn metric_scores_like_imperial() {
+    let imperial = params(Units::IMPERIAL, "500", "2100");
+    let metric = params(Units::METRIC, &(500.0 * 0.06479891).to_string(), &(2100.0 * 0.3048).to_string());
+    for formula in PowerFormula::ALL {
+        let (a, b) = (formula.score(&imperial, Some(0.458)).unwrap(), formula.score(&metric, Some(0.458 * 25.4)).unwrap());
+        assert!((a / b - 1.0).abs() < 1e-6, "{}: {} {}", formula.name(), a, b);
+    }
+    assert_eq!(PowerFormula::from_name(" OGW "), Some(PowerFormula::OGW));
+}
@@ -0,0 +1,33 @@
+//! Energy per bore volume, `bore_length` with `diameter`.
+
+use muzzle::{run, Config, MuzzleError, Units};
+
+fn proxy(units: Units, energy: &str, diameter: &str, bore_length: Option<&str>) -> Result<Option<f64>, MuzzleError> {
+    let config = Config {
+        units,
+        mass: Some("10".to_owned()),
+        energy: Some(energy.to_owned()),
+        diameter: Some(diameter.to_owned()),
+        bore_length: bore_length.map(str::to_owned),
+        ..Config::default()
+    };
+    run(config).map(|params| params.pressure_proxy)
+}
+
+#[test]
+fn divides_energy_by_bore_volume() {
+    // 3200 J over a 10 mm bore 500 mm long, π × 5² × 500 mm³
+    let metric = proxy(Units::METRIC, "3200", "10", Some("500")).unwrap().unwrap();
+    assert!((metric - 3200.0 / (std::f64::consts::PI * 25.0 * 500.0) * 1000.0).abs() < 1e-9);
+    assert!((metric - 81.487).abs() < 0.001, "{}", metric);
+
+    // 2600 FPE over a .308 bore 24 inches long, in inch-pounds per cubic inch
+    let imperial = proxy(Units::IMPERIAL, "2600", "0.308", Some("24")).unwrap().unwrap();
+    assert!((imperial - 2600.0 * 12.0 / (std::f64::consts::PI * 0.308f64.powi(2) / 4.0 * 24.0)).abs() < 1e-6);
+}
+
+#[test]
+fn needs_bore_length() {
+    assert_eq!(proxy(Units::METRIC, "3200", "10", None).unwrap(), None);
+    assert!(matches!(proxy(Units::METRIC, "3200", "10", Some("0")), Err(MuzzleError::INVALID(_))));
+}
@@ -0,0 +1,28 @@
+//! Indented and single line JSON output, `--pretty` and `--compact`.
+
+mod common;
+
+use common::muzzle;
+use serde_json::Value;
+
+#[test]
+fn both_forms_hold_the_same_document() {
+    let [default, compact, pretty] = [&[][..], &["--compact"], &["--pretty"]].map(|form| {
+        let output = muzzle(&[&["-f", "json", "-m", "10", "-s", "800"], form].concat());
+        assert!(output.status.success());
+        String::from_utf8(output.stdout).unwrap()
+    });
+
+    assert_eq!(default, compact);
+    assert_eq!(compact.lines().count(), 1);
+    assert!(pretty.lines().count() > 10);
+    assert!(pretty.contains("\n  \"mass\": 10.0"), "{}", pretty);
+    assert_eq!(serde_json::from_str::<Value>(&compact).unwrap(), serde_json::from_str::<Value>(&pretty).unwrap());
+}
+
+#[test]
+fn forms_conflict() {
+    let output = muzzle(&["-f", "json", "-m", "10", "-s", "800", "--pretty", "--compact"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+}
@@ -0,0 +1,46 @@
+//! Progress of long tables reported on stderr, `--quiet` to suppress it.
+
+mod common;
+
+use common::muzzle_with_stdin;
+use muzzle::{energy_sweep_iter, filter_json_with_progress, Units};
+
+#[test]
+fn batch_reports_each_config_done() {
+    let mut reports = Vec::new();
+    let results = filter_json_with_progress(r#"[{"mass": 10, "speed": 800}, {"mass": 10}, {"speed": 800, "energy": 3200}]"#, &mut |done, total| reports.push((done, total))).unwrap();
+
+    assert_eq!(reports, [(1, 3), (2, 3), (3, 3)]);
+    assert_eq!(results.as_array().unwrap().len(), 3);
+}
+
+#[test]
+fn sweep_rows_are_calculated_lazily() {
+    let mut rows = energy_sweep_iter(Units::METRIC, 3200.0, 1.0, 5000.0, 1.0).unwrap();
+
+    assert_eq!(rows.size_hint().0, 5000);
+    assert_eq!(rows.next().unwrap().unwrap().mass, 1.0);
+    assert_eq!(rows.next().unwrap().unwrap().mass, 2.0);
+    assert!(energy_sweep_iter(Units::METRIC, 3200.0, 0.0, 10.0, 1.0).is_err());
+}
+
+#[test]
+fn suppressed_by_quiet_and_when_stderr_is_piped() {
+    let muzzle = |args: &[&str], stdin: &str| {
+        let output = muzzle_with_stdin(args, stdin);
+        assert!(output.status.success());
+        output
+    };
+
+    // Past the progress interval, so a counter would have been printed at least once
+    let configs = format!("[{}]", vec![r#"{"mass": 10, "speed": 800}"#; 2500].join(","));
+    for quiet in [&[][..], &["--quiet"]] {
+        let sweep = muzzle(&[&["-e", "3200", "--mass-range", "1:2500:1"][..], quiet].concat(), "");
+        assert_eq!(String::from_utf8(sweep.stdout).unwrap().lines().count(), 2501);
+        assert!(sweep.stderr.is_empty());
+
+        let batch = muzzle(&[&["--from-json-stdin"][..], quiet].concat(), &configs);
+        assert!(batch.stdout.starts_with(b"[{"));
+        assert!(batch.stderr.is_empty());
+    }
+}
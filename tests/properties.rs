@@ -0,0 +1,114 @@
+//! Property tests of derivations against each other: whichever two of mass, speed and energy a
+//! shot is given by, deriving the third and then deriving back from any other pair recovers the
+//! values it started from, in both measurment systems.
+//!
+//! Values are compared to a relative tolerance of 1e-12. Every derivation is a handful of
+//! multiplications, divisions and at most one square root, each off by at most half an ulp
+//! (about 1.1e-16 relative), and values pass between runs as shortest round-tripping decimal
+//! strings, which loses nothing. A round trip thus stays within some 1e-15, the rest of the
+//! margin keeps the test from tripping over that estimate while still catching any unit
+//! constant that is off, even in its last printed digit.
+
+use muzzle::{run, Config, Params, Units};
+use proptest::prelude::*;
+
+/// Meters in an international foot
+const METERS_IN_FOOT: f64 = 0.3048;
+
+/// Relative tolerance of round trips, see above for why
+const TOLERANCE: f64 = 1e-12;
+
+/// Runs a shot given by exactly two of mass, speed and energy
+fn shot(units: Units, mass: Option<f64>, speed: Option<f64>, energy: Option<f64>) -> Params {
+    run(Config {
+        units,
+        mass: mass.map(|value| value.to_string()),
+        speed: speed.map(|value| value.to_string()),
+        energy: energy.map(|value| value.to_string()),
+        ..Config::default()
+    })
+    .unwrap()
+}
+
+/// Relative tolerance of energies compared across measurment systems. Imperial energy is
+/// derived with the 32.174 ft/s² of gravity reloading manuals use, while standard gravity is
+/// 32.17405 ft/s², so imperial and metric energies of the same shot differ by some 1.5e-6.
+const CROSS_UNITS_ENERGY_TOLERANCE: f64 = 1e-5;
+
+/// Asserts `actual` equals `expected` within `TOLERANCE`
+fn assert_close(actual: f64, expected: f64) -> Result<(), TestCaseError> {
+    assert_within(actual, expected, TOLERANCE)
+}
+
+/// Asserts `actual` equals `expected` within relative `tolerance`
+fn assert_within(actual: f64, expected: f64, tolerance: f64) -> Result<(), TestCaseError> {
+    prop_assert!(
+        ((actual - expected) / expected).abs() <= tolerance,
+        "{} differs from {} by more than {} relative",
+        actual,
+        expected,
+        tolerance
+    );
+    Ok(())
+}
+
+/// Either measurment system
+fn units() -> impl Strategy<Value = Units> {
+    prop_oneof![Just(Units::METRIC), Just(Units::IMPERIAL)]
+}
+
+proptest! {
+    #[test]
+    fn energy_from_mass_and_speed(units in units(), mass in 0.01..1e4f64, speed in 1.0..5e3f64) {
+        let energy = shot(units, Some(mass), Some(speed), None).energy;
+
+        assert_close(shot(units, Some(mass), None, Some(energy)).speed, speed)?;
+        assert_close(shot(units, None, Some(speed), Some(energy)).mass, mass)?;
+    }
+
+    #[test]
+    fn speed_from_mass_and_energy(units in units(), mass in 0.01..1e4f64, energy in 0.01..1e6f64) {
+        let speed = shot(units, Some(mass), None, Some(energy)).speed;
+
+        assert_close(shot(units, Some(mass), Some(speed), None).energy, energy)?;
+        assert_close(shot(units, None, Some(speed), Some(energy)).mass, mass)?;
+    }
+
+    #[test]
+    fn si_tuple_is_independent_of_units(grams in 0.01..1e3f64, speed in 1.0..2e3f64) {
+        let metric = shot(Units::METRIC, Some(grams), Some(speed), None);
+        let imperial = shot(Units::IMPERIAL, Some(metric.mass_in(Units::IMPERIAL)), Some(speed / METERS_IN_FOOT), None);
+
+        let (mass, speed, energy) = metric.as_si_tuple();
+        let (imperial_mass, imperial_speed, imperial_energy) = imperial.as_si_tuple();
+        assert_close(imperial_mass, mass)?;
+        assert_close(imperial_speed, speed)?;
+        assert_within(imperial_energy, energy, CROSS_UNITS_ENERGY_TOLERANCE)?;
+    }
+
+    #[test]
+    fn penetration_index_grows_with_sectional_density_and_speed(
+        units in units(),
+        mass in 1.0..500.0f64,
+        speed in 100.0..3e3f64,
+        more in 1.01..2.0f64,
+    ) {
+        let index = |mass: f64, speed: f64| {
+            run(Config { units, mass: Some(mass.to_string()), speed: Some(speed.to_string()), diameter: Some("0.3".to_owned()), ..Config::default() })
+                .unwrap()
+                .penetration_index
+                .unwrap()
+        };
+
+        prop_assert!(index(mass * more, speed) > index(mass, speed));
+        prop_assert!(index(mass, speed * more) > index(mass, speed));
+    }
+
+    #[test]
+    fn mass_from_speed_and_energy(units in units(), speed in 1.0..5e3f64, energy in 0.01..1e6f64) {
+        let mass = shot(units, None, Some(speed), Some(energy)).mass;
+
+        assert_close(shot(units, Some(mass), Some(speed), None).energy, energy)?;
+        assert_close(shot(units, Some(mass), None, Some(energy)).speed, speed)?;
+    }
+}
@@ -0,0 +1,103 @@
+//! QR codes of results read back module by module, `cargo test --features qr`.
+#![cfg(feature = "qr")]
+
+mod common;
+
+use muzzle::qr::QrCode;
+use muzzle::{run, Config, Units};
+
+/// Total codewords, error correction codewords per block and blocks of versions 1 to 6 at level L
+const VERSIONS: [(usize, usize, usize); 6] = [(26, 7, 1), (44, 10, 1), (70, 15, 1), (100, 20, 1), (134, 26, 1), (172, 18, 2)];
+
+/// Reads the byte mode text back out of `code`, trusting it carries no errors to correct
+fn decode(code: &QrCode) -> String {
+    let size = code.size();
+    let version = (size - 17) / 4;
+    let (total, ecc, blocks) = VERSIONS[version - 1];
+
+    // First copy of format information, most significant bit next to the left edge
+    let positions = (0..6).map(|y| (8, y)).chain([(8, 7), (8, 8), (7, 8)]).chain((9..15).map(|i| (14 - i, 8)));
+    let format = positions.enumerate().fold(0, |bits, (i, (x, y))| bits | (code.is_dark(x, y) as u32) << i) ^ 0x5412;
+    assert_eq!(format >> 13, 1, "error correction level is not L");
+    let mask = (format >> 10) & 7;
+
+    let alignment = |x: usize, y: usize| version > 1 && x.abs_diff(size - 7) <= 2 && y.abs_diff(size - 7) <= 2;
+    // Finders with their separators and format information take the corners but the bottom right one
+    let corner = |x: usize, y: usize| (x < 9 && (y < 9 || y >= size - 8)) || (x >= size - 8 && y < 9);
+    let function = |x: usize, y: usize| corner(x, y) || x == 6 || y == 6 || alignment(x, y);
+    let masked = |x: usize, y: usize| match mask {
+        0 => (x + y).is_multiple_of(2),
+        1 => y.is_multiple_of(2),
+        2 => x.is_multiple_of(3),
+        3 => (x + y).is_multiple_of(3),
+        4 => (x / 3 + y / 2).is_multiple_of(2),
+        5 => x * y % 2 + x * y % 3 == 0,
+        6 => (x * y % 2 + x * y % 3).is_multiple_of(2),
+        _ => ((x + y) % 2 + x * y % 3).is_multiple_of(2),
+    };
+
+    let mut bits = Vec::new();
+    let mut right = size - 1;
+    loop {
+        if right == 6 {
+            right = 5;
+        }
+        for vertical in 0..size {
+            for x in [right, right - 1] {
+                let y = if (right + 1) & 2 == 0 { size - 1 - vertical } else { vertical };
+                if !function(x, y) {
+                    bits.push(code.is_dark(x, y) ^ masked(x, y));
+                }
+            }
+        }
+        if right < 2 {
+            break;
+        }
+        right -= 2;
+    }
+    let codewords: Vec<u8> = bits.chunks(8).take(total).map(|byte| byte.iter().fold(0, |value, &bit| value << 1 | bit as u8)).collect();
+
+    // Blocks are interleaved codeword by codeword
+    let length = (total - ecc * blocks) / blocks;
+    let data: Vec<u8> = (0..blocks).flat_map(|block| (0..length).map(move |i| (block, i))).map(|(block, i)| codewords[i * blocks + block]).collect();
+
+    let bit = |i: usize| (data[i / 8] >> (7 - i % 8)) & 1;
+    let read = |from: usize, count: usize| (from..from + count).fold(0usize, |value, i| value << 1 | bit(i) as usize);
+    assert_eq!(read(0, 4), 0b0100, "not byte mode");
+    let bytes: Vec<u8> = (0..read(4, 8)).map(|i| read(12 + 8 * i, 8) as u8).collect();
+    String::from_utf8(bytes).unwrap()
+}
+
+#[test]
+fn encoded_payload_round_trips() {
+    let params = run(Config { units: Units::IMPERIAL, mass: Some("55".to_owned()), speed: Some("3240.5".to_owned()), ..Config::default() }).unwrap();
+    let command = params.to_command();
+
+    let code = QrCode::encode(&command).unwrap();
+    assert_eq!(decode(&code), command);
+
+    // Long enough for the two interleaved blocks of the largest version
+    let long = "muzzle --mass 0.123456789 --speed 0.123456789 ".repeat(3);
+    let code = QrCode::encode(&long[..130]).unwrap();
+    assert_eq!(code.size(), 41);
+    assert_eq!(decode(&code), long[..130]);
+}
+
+#[test]
+fn too_long_text_is_refused() {
+    assert!(QrCode::encode(&"m".repeat(134)).is_ok());
+    assert!(QrCode::encode(&"m".repeat(135)).is_err());
+}
+
+#[test]
+fn plain_rendering_has_no_escapes() {
+    let code = QrCode::encode("muzzle --mass 10 --speed 800").unwrap();
+    assert!(code.to_ansi().contains('\x1b'));
+    assert!(!code.to_text().contains('\x1b'));
+    assert_eq!(code.to_text(), code.to_ansi().replace("\x1b[30;47m", "").replace("\x1b[0m", ""));
+
+    let output = common::command().args(["-m", "10", "-s", "800", "--qr"]).env("NO_COLOR", "1").output().unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains('▀') && !stdout.contains('\x1b'), "{}", stdout);
+}
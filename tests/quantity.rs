@@ -0,0 +1,19 @@
+//! Names and units of the primary quantities, `Quantity`.
+
+use muzzle::{MuzzleError, Quantity, Units};
+
+#[test]
+fn names_round_trip() {
+    for quantity in Quantity::ALL {
+        assert_eq!(quantity.to_string().parse::<Quantity>().unwrap(), quantity);
+        assert_eq!(Quantity::from_name(&format!(" {} ", quantity.name().to_uppercase())), Some(quantity));
+    }
+    assert_eq!(Quantity::ALL.map(Quantity::name), ["mass", "speed", "energy"]);
+    assert!(matches!("momentum".parse::<Quantity>(), Err(MuzzleError::INVALID(_))));
+}
+
+#[test]
+fn units_by_system() {
+    assert_eq!(Quantity::ALL.map(|quantity| quantity.unit(Units::METRIC)), ["g", "m/s", "J"]);
+    assert_eq!(Quantity::ALL.map(|quantity| quantity.unit(Units::IMPERIAL)), ["gr", "ft/s", "ft·lbf"]);
+}
@@ -0,0 +1,31 @@
+//! Free recoil velocity and energy of the firearm, `gun_mass`.
+
+use muzzle::{run, Config, MuzzleError, Units};
+
+fn recoil(units: Units, mass: &str, speed: &str, gun_mass: &str) -> Result<(f64, f64), MuzzleError> {
+    let config = Config { units, mass: Some(mass.to_owned()), speed: Some(speed.to_owned()), gun_mass: Some(gun_mass.to_owned()), ..Config::default() };
+    run(config).map(|params| (params.recoil_velocity.unwrap(), params.recoil_energy.unwrap()))
+}
+
+#[test]
+fn conserves_momentum() {
+    // 10 g at 800 m/s out of a 4 kg rifle
+    let (velocity, energy) = recoil(Units::METRIC, "10", "800", "4").unwrap();
+    assert!((velocity - 2.0).abs() < 1e-12);
+    assert!((energy - 8.0).abs() < 1e-12);
+
+    // 150 gr at 2800 FPS out of an 8 lb rifle
+    let (velocity, energy) = recoil(Units::IMPERIAL, "150", "2800", "8").unwrap();
+    assert!((velocity - 7.5).abs() < 1e-12);
+    assert!((energy - 8.0 * 7.5f64.powi(2) / (2.0 * 32.174)).abs() < 1e-9);
+    assert!((energy - 6.99).abs() < 0.01);
+}
+
+#[test]
+fn halves_with_twice_the_gun() {
+    let (light, light_energy) = recoil(Units::IMPERIAL, "150", "2800", "8").unwrap();
+    let (heavy, heavy_energy) = recoil(Units::IMPERIAL, "150", "2800", "16").unwrap();
+    assert!((light / heavy - 2.0).abs() < 1e-12);
+    assert!((light_energy / heavy_energy - 2.0).abs() < 1e-12);
+    assert!(matches!(recoil(Units::IMPERIAL, "150", "2800", "0"), Err(MuzzleError::INVALID(_))));
+}
@@ -0,0 +1,41 @@
+//! Energy retained downrange as a fraction of muzzle energy.
+
+use muzzle::{run, Config, Params, Units};
+
+fn downrange(distance: &str) -> Params {
+    let config = Config {
+        units: Units::IMPERIAL,
+        mass: Some("150".to_owned()),
+        speed: Some("2800".to_owned()),
+        bc: Some("0.4".to_owned()),
+        distance: Some(distance.to_owned()),
+        ..Config::default()
+    };
+    run(config).unwrap()
+}
+
+#[test]
+fn fraction_is_between_zero_and_one() {
+    let mut last = 1.0;
+    for distance in ["100", "300", "600", "1000"] {
+        let params = downrange(distance);
+        let fraction = params.retained_fraction.unwrap();
+        assert!(fraction > 0.0 && fraction < 1.0);
+        assert!(fraction < last);
+        assert!((fraction - params.retained_energy.unwrap() / params.energy).abs() < 1e-12);
+        last = fraction;
+    }
+}
+
+#[test]
+fn nothing_is_lost_at_the_muzzle() {
+    let params = downrange("0");
+    assert_eq!(params.retained_speed, Some(params.speed));
+    assert!((params.retained_fraction.unwrap() - 1.0).abs() < 1e-12);
+}
+
+#[test]
+fn only_estimated_with_drag_inputs() {
+    let params = run(Config { units: Units::IMPERIAL, mass: Some("150".to_owned()), speed: Some("2800".to_owned()), ..Config::default() }).unwrap();
+    assert_eq!(params.retained_fraction, None);
+}
@@ -0,0 +1,29 @@
+//! Derived values rounded to an increment, `--round-to`.
+
+mod common;
+
+use common::muzzle;
+use muzzle::round_to_increment;
+
+#[test]
+fn rounds_to_nearest_increment() {
+    assert_eq!(round_to_increment(2803.0, 5.0), 2805.0);
+    assert_eq!(round_to_increment(2802.4, 5.0), 2800.0);
+    assert_eq!(round_to_increment(2802.5, 5.0), 2805.0);
+    assert_eq!(round_to_increment(-2802.5, 5.0), -2805.0);
+    assert!((round_to_increment(150.04, 0.1) - 150.0).abs() < 1e-9);
+    assert_eq!(round_to_increment(2803.0, 0.0), 2803.0);
+    assert_eq!(round_to_increment(2803.0, -5.0), 2803.0);
+}
+
+#[test]
+fn cli_rounds_derived_value_only() {
+    // 150 gr at 2611 FPE is 2800.1 FPS, given 2620 FPE it is 2804.9
+    let output = muzzle(&["-i", "-m", "150", "-e", "2620", "--round-to", "5"]);
+    assert!(output.status.success());
+
+    let report = String::from_utf8(output.stdout).unwrap();
+    assert!(report.contains("Projectile speed:\t2805 FPS"), "{}", report);
+    assert!(report.contains("Projectile mass:\t150.000 grains"));
+    assert!(report.contains("Projectile energy:\t2620.000 FPE"));
+}
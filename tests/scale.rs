@@ -0,0 +1,38 @@
+//! Shot parameters scaled by a factor, `Params::scale_speed` and `Params::scale_mass`.
+
+use muzzle::{run, Config, Params, Quantity, Units};
+
+fn load() -> Params {
+    let config = Config {
+        units: Units::IMPERIAL,
+        mass: Some("150".to_owned()),
+        speed: Some("2800".to_owned()),
+        bc: Some("0.4".to_owned()),
+        distance: Some("200".to_owned()),
+        ..Config::default()
+    };
+    run(config).unwrap()
+}
+
+#[test]
+fn five_percent_more_speed_is_ten_percent_more_energy() {
+    let params = load();
+    let faster = params.scale_speed(1.05);
+
+    assert!((faster.speed - 2940.0).abs() < 1e-9);
+    assert_eq!(faster.mass, params.mass);
+    assert!((faster.energy / params.energy - 1.1025).abs() < 1e-12);
+    assert!((faster.momentum() / params.momentum() - 1.05).abs() < 1e-12);
+    assert_eq!(faster.derived, Some(Quantity::ENERGY));
+    assert_eq!(faster.retained_speed, None);
+}
+
+#[test]
+fn mass_scales_energy_linearly() {
+    let params = load();
+    let heavier = params.scale_mass(1.2);
+
+    assert!((heavier.mass - 180.0).abs() < 1e-9);
+    assert_eq!(heavier.speed, params.speed);
+    assert!((heavier.energy / params.energy - 1.2).abs() < 1e-12);
+}
@@ -0,0 +1,29 @@
+//! JSON Schema of `Params::to_json` documents.
+
+use muzzle::{json_schema, run, Config, Units};
+
+#[test]
+fn lists_core_fields_with_optionality() {
+    let schema = json_schema();
+    let properties = &schema["properties"];
+
+    for name in ["mass", "speed", "energy"] {
+        assert_eq!(properties[name]["type"], "number");
+        assert!(properties[name]["x-unit"]["metric"].is_string());
+    }
+    assert_eq!(properties["retained_speed"]["type"], serde_json::json!(["number", "null"]));
+    assert_eq!(properties["derived"]["enum"], serde_json::json!(["mass", "speed", "energy", null]));
+    assert_eq!(properties["units"]["type"], "string");
+}
+
+#[test]
+fn matches_documents() {
+    let params = run(Config { units: Units::METRIC, mass: Some("10".to_owned()), speed: Some("800".to_owned()), ..Config::default() }).unwrap();
+    let document: serde_json::Value = serde_json::from_str(&params.to_json()).unwrap();
+    let schema = json_schema();
+
+    let keys: Vec<&String> = document.as_object().unwrap().keys().collect();
+    let described: Vec<&String> = schema["properties"].as_object().unwrap().keys().collect();
+    assert_eq!(keys, described);
+    assert_eq!(schema["required"].as_array().unwrap().len(), keys.len());
+}
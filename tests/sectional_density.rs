@@ -0,0 +1,22 @@
+//! Sectional density from `diameter`, in lb/in² or g/mm².
+
+use muzzle::{run, Config, Units};
+
+fn density(units: Units, mass: &str, diameter: &str) -> f64 {
+    let config = Config { units, mass: Some(mass.to_owned()), speed: Some("800".to_owned()), diameter: Some(diameter.to_owned()), ..Config::default() };
+    run(config).unwrap().sectional_density.unwrap()
+}
+
+#[test]
+fn matches_published_figure() {
+    // 150 gr .308 bullet, 0.226 in the loading manuals
+    assert!((density(Units::IMPERIAL, "150", "0.308") - 0.226).abs() < 0.0005);
+}
+
+#[test]
+fn converts_between_systems() {
+    let imperial = density(Units::IMPERIAL, "150", "0.308");
+    // The same bullet in grams and millimeters
+    let metric = density(Units::METRIC, &(150.0 * 0.06479891).to_string(), &(0.308 * 25.4).to_string());
+    assert!((metric / imperial - 0.70307).abs() < 1e-4, "{} {}", metric, imperial);
+}
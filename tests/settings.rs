@@ -0,0 +1,64 @@
+//! Defaults for format, decimals and colors kept in the settings file, beneath command line flags.
+
+mod common;
+
+use muzzle::settings::{Settings, DEFAULT_DECIMALS};
+use muzzle::{ColorChoice, ColorTheme, OutputFormat};
+#[cfg(feature = "settings")]
+use {muzzle::settings, muzzle::MuzzleError, std::fs, std::process::Output};
+
+#[test]
+fn flag_beats_file_beats_default() {
+    let defaults = Settings::default();
+    assert_eq!(defaults.format(None), OutputFormat::HUMAN);
+    assert_eq!(defaults.decimals(None), DEFAULT_DECIMALS);
+
+    let file = Settings { default_format: Some(OutputFormat::JSON), decimals: Some(1), ..Settings::default() };
+    assert_eq!(file.format(None), OutputFormat::JSON);
+    assert_eq!(file.format(Some(OutputFormat::CSV)), OutputFormat::CSV);
+    assert_eq!(file.decimals(None), 1);
+    assert_eq!(file.decimals(Some(0)), 0);
+
+    assert_eq!(defaults.color(None), ColorChoice::AUTO);
+    assert_eq!(defaults.color_theme(None), ColorTheme::DARK);
+    let colored = Settings { color: Some(ColorChoice::NEVER), color_theme: Some(ColorTheme::LIGHT), ..Settings::default() };
+    assert_eq!(colored.color(None), ColorChoice::NEVER);
+    assert_eq!(colored.color(Some(ColorChoice::ALWAYS)), ColorChoice::ALWAYS);
+    assert_eq!(colored.color_theme(None), ColorTheme::LIGHT);
+    assert_eq!(colored.color_theme(Some(ColorTheme::DARK)), ColorTheme::DARK);
+}
+
+#[test]
+#[cfg(feature = "settings")]
+fn cli_applies_file_under_flags() {
+    let home = std::env::temp_dir().join(format!("muzzle-settings-{}", std::process::id()));
+    let path = home.join("muzzle").join("config.toml");
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    let muzzle = |args: &[&str]| -> Output { common::command().args(["-m", "10", "-s", "800"]).args(args).env("XDG_CONFIG_HOME", &home).output().unwrap() };
+
+    fs::write(&path, "default_format = \"json\"\ndecimals = 1\n").unwrap();
+    let from_file = muzzle(&[]);
+    let human = muzzle(&["-f", "human"]);
+    let decimals = muzzle(&["-f", "human", "--decimals", "2"]);
+
+    fs::write(&path, "color = \"always\"\ncolor_theme = \"light\"\n").unwrap();
+    let colored = muzzle(&[]);
+    let plain = muzzle(&["--color", "never"]);
+    let loaded_color = settings::load(&path);
+
+    fs::write(&path, "default_format = \"yaml\"\n").unwrap();
+    let broken = muzzle(&[]);
+    let loaded = settings::load(&path);
+    fs::remove_dir_all(&home).unwrap();
+
+    assert!(from_file.status.success() && human.status.success() && decimals.status.success() && colored.status.success());
+    assert!(from_file.stdout.starts_with(b"{\"schema_version\""));
+    assert!(String::from_utf8(human.stdout).unwrap().contains("Projectile energy:\t3200.0 Joules"));
+    assert!(String::from_utf8(decimals.stdout).unwrap().contains("Projectile energy:\t3200.00 Joules"));
+    assert!(String::from_utf8(colored.stdout).unwrap().contains(ColorTheme::LIGHT.highlight()));
+    assert!(plain.status.success() && !plain.stdout.contains(&b'\x1b'));
+    assert_eq!(loaded_color.unwrap().color, Some(ColorChoice::ALWAYS));
+    assert_eq!(broken.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&broken.stderr).starts_with("Failed to read settings with: Incorrect settings file"));
+    assert!(matches!(loaded, Err(MuzzleError::INVALID(_))));
+}
@@ -0,0 +1,22 @@
+//! Core parameters in SI units whatever the input units, `run_si`.
+
+use muzzle::{run_si, Config, Units};
+
+fn si(units: Units, mass: &str, speed: &str) -> muzzle::ParamsSi {
+    run_si(Config { units, mass: Some(mass.to_owned()), speed: Some(speed.to_owned()), ..Config::default() }).unwrap()
+}
+
+#[test]
+fn equivalent_inputs_agree() {
+    let metric = si(Units::METRIC, "10", "800");
+    assert!((metric.mass - 0.01).abs() < 1e-12);
+    assert!((metric.speed - 800.0).abs() < 1e-9);
+    assert!((metric.energy - 3200.0).abs() < 1e-6);
+
+    // 10 g and 800 m/s in grains and FPS
+    let imperial = si(Units::IMPERIAL, &(10.0 / 0.06479891).to_string(), &(800.0 / 0.3048).to_string());
+    // Imperial energy goes through g rounded to 32.174 ft/s², a few parts per million off
+    for (imperial, metric) in [(imperial.mass, metric.mass), (imperial.speed, metric.speed), (imperial.energy, metric.energy)] {
+        assert!((imperial / metric - 1.0).abs() < 1e-5, "{} {}", imperial, metric);
+    }
+}
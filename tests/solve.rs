@@ -0,0 +1,27 @@
+//! Declarative solving for one quantity from a list of knowns, `solve`.
+
+use muzzle::{solve, MuzzleError, Quantity, Units};
+
+#[test]
+fn solves_for_each_quantity() {
+    let close = |actual: f64, expected: f64| (actual - expected).abs() <= 1e-9 * expected;
+
+    assert!(close(solve(Quantity::ENERGY, &[(Quantity::MASS, 10.0), (Quantity::SPEED, 800.0)], Units::METRIC).unwrap(), 3200.0));
+    assert!(close(solve(Quantity::MASS, &[(Quantity::ENERGY, 3200.0), (Quantity::SPEED, 800.0)], Units::METRIC).unwrap(), 10.0));
+    assert!(close(solve(Quantity::SPEED, &[(Quantity::MASS, 10.0), (Quantity::ENERGY, 3200.0)], Units::METRIC).unwrap(), 800.0));
+    assert!(close(solve(Quantity::ENERGY, &[(Quantity::SPEED, 2800.0), (Quantity::MASS, 150.0)], Units::IMPERIAL).unwrap(), 2610.8037545844472));
+}
+
+#[test]
+fn under_determined_and_malformed_knowns_are_errors() {
+    assert!(matches!(solve(Quantity::ENERGY, &[(Quantity::MASS, 10.0)], Units::METRIC), Err(MuzzleError::INSUFFICIENT)));
+    assert!(matches!(solve(Quantity::SPEED, &[], Units::METRIC), Err(MuzzleError::INSUFFICIENT)));
+    assert!(matches!(
+        solve(Quantity::ENERGY, &[(Quantity::MASS, 10.0), (Quantity::ENERGY, 3200.0)], Units::METRIC),
+        Err(MuzzleError::INVALID(_))
+    ));
+    assert!(matches!(
+        solve(Quantity::ENERGY, &[(Quantity::MASS, 10.0), (Quantity::MASS, 12.0)], Units::METRIC),
+        Err(MuzzleError::INVALID(_))
+    ));
+}
@@ -0,0 +1,35 @@
+//! Projectile speed against the speed of sound, `--sound-regime` and `--temp`.
+
+mod common;
+
+use common::stdout;
+use muzzle::{run, speed_of_sound, Config, Params, SoundRegime, Units, STANDARD_TEMPERATURE};
+
+fn imperial(speed: &str) -> Params {
+    run(Config { units: Units::IMPERIAL, mass: Some("150".to_owned()), speed: Some(speed.to_owned()), ..Config::default() }).unwrap()
+}
+
+#[test]
+fn classifies_at_standard_conditions() {
+    // 59 °F, the imperial standard temperature, where sound travels at about 1116 FPS
+    assert!((speed_of_sound(STANDARD_TEMPERATURE) - 340.3).abs() < 0.1);
+    assert_eq!(imperial("1050").sound_regime(59.0), SoundRegime::SUBSONIC);
+    assert_eq!(imperial("1200").sound_regime(59.0), SoundRegime::TRANSONIC);
+    assert_eq!(imperial("2800").sound_regime(59.0), SoundRegime::SUPERSONIC);
+}
+
+#[test]
+fn cold_air_lowers_the_speed_of_sound() {
+    let params = imperial("1050");
+    assert!(params.mach(-40.0) > params.mach(59.0));
+    assert_eq!(params.sound_regime(-40.0), SoundRegime::TRANSONIC);
+}
+
+#[test]
+fn cli_prints_regime() {
+    let report = |extra: &[&str]| stdout(&[&["-i", "-m", "150", "-s", "1050"][..], extra].concat());
+
+    assert!(report(&["--sound-regime"]).contains("Sound regime:\tsubsonic"));
+    assert!(report(&["--temp", "-40"]).contains("Sound regime:\ttransonic"));
+    assert!(!report(&[]).contains("Sound regime"));
+}
@@ -0,0 +1,19 @@
+//! Energy per unit of projectile mass, `Params::specific_energy`.
+
+use muzzle::{run, Config, Units};
+
+fn specific_energy(units: Units, mass: &str, speed: &str) -> f64 {
+    run(Config { units, mass: Some(mass.to_owned()), speed: Some(speed.to_owned()), ..Config::default() }).unwrap().specific_energy()
+}
+
+#[test]
+fn depends_on_speed_only() {
+    // Half the square of speed in SI, J/kg
+    assert!((specific_energy(Units::METRIC, "10", "800") - 320_000.0).abs() < 1e-6);
+    assert!((specific_energy(Units::METRIC, "3", "800") - 320_000.0).abs() < 1e-6);
+
+    // Square of speed over twice g in ft·lbf/lb
+    let imperial = specific_energy(Units::IMPERIAL, "150", "2800");
+    assert!((imperial - 2800f64.powi(2) / (2.0 * 32.174)).abs() < 1e-6, "{}", imperial);
+    assert!((specific_energy(Units::IMPERIAL, "55", "2800") - imperial).abs() < 1e-6);
+}
@@ -0,0 +1,36 @@
+//! Speed derived from mass and energy, the one derivation that takes a square root.
+
+use muzzle::{run, solve, Config, MuzzleError, Quantity, Units};
+
+fn config(mass: &str, energy: &str) -> Config {
+    Config {
+        units: Units::METRIC,
+        mass: Some(mass.to_owned()),
+        energy: Some(energy.to_owned()),
+        ..Config::default()
+    }
+}
+
+#[test]
+fn derived_speed_is_exact_square_root() {
+    let known = [(Quantity::MASS, 10.0), (Quantity::ENERGY, 3200.0)];
+    let speed = solve(Quantity::SPEED, &known, Units::METRIC).unwrap();
+    assert_eq!(speed, (2.0 * 3200.0 / 0.01_f64).sqrt());
+    assert_eq!(speed, 800.0);
+
+    let params = run(config("10", "3200")).unwrap();
+    assert_eq!(params.speed, speed);
+    assert_eq!(params.with_energy(3200.0).unwrap().speed, speed);
+}
+
+#[test]
+fn negative_speed_squared_is_domain_error() {
+    let is_speed_domain = |result: Result<f64, MuzzleError>| matches!(result, Err(MuzzleError::DOMAIN(name)) if name == "speed");
+
+    assert!(is_speed_domain(run(config("10", "-3200")).map(|params| params.speed)));
+    assert!(is_speed_domain(run(config("-10", "3200")).map(|params| params.speed)));
+    assert!(is_speed_domain(solve(Quantity::SPEED, &[(Quantity::MASS, 10.0), (Quantity::ENERGY, -3200.0)], Units::METRIC)));
+
+    let params = run(config("10", "3200")).unwrap();
+    assert!(is_speed_domain(params.with_energy(-3200.0).map(|params| params.speed)));
+}
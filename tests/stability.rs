@@ -0,0 +1,38 @@
+//! Miller gyroscopic stability, `length` and `twist` with `diameter`.
+
+use muzzle::{run, Config, MuzzleError, Units};
+
+fn stability(units: Units, [mass, speed, diameter, length, twist]: [&str; 5], temperature: Option<&str>) -> Result<Option<f64>, MuzzleError> {
+    let config = Config {
+        units,
+        mass: Some(mass.to_owned()),
+        speed: Some(speed.to_owned()),
+        diameter: Some(diameter.to_owned()),
+        length: Some(length.to_owned()),
+        twist: Some(twist.to_owned()),
+        temperature: temperature.map(str::to_owned),
+        ..Config::default()
+    };
+    run(config).map(|params| params.stability)
+}
+
+#[test]
+fn matches_miller_example() {
+    // 168 gr .308 Sierra MatchKing, 1.215" long, from a 1:12" barrel at 2650 FPS: about 1.71
+    let sierra = stability(Units::IMPERIAL, ["168", "2650", "0.308", "1.215", "12"], None).unwrap().unwrap();
+    assert!((sierra - 1.708).abs() < 0.001, "{}", sierra);
+
+    // The same in metric units
+    let metric = stability(Units::METRIC, ["10.886", "807.72", "7.8232", "30.861", "304.8"], None).unwrap().unwrap();
+    assert!((metric / sierra - 1.0).abs() < 1e-4, "{} {}", metric, sierra);
+
+    // Faster twist and warmer air both stabilize more
+    assert!(stability(Units::IMPERIAL, ["168", "2650", "0.308", "1.215", "10"], None).unwrap().unwrap() > sierra);
+    assert!(stability(Units::IMPERIAL, ["168", "2650", "0.308", "1.215", "12"], Some("100")).unwrap().unwrap() > sierra);
+}
+
+#[test]
+fn needs_all_dimensions() {
+    let config = Config { units: Units::IMPERIAL, mass: Some("168".to_owned()), speed: Some("2650".to_owned()), twist: Some("12".to_owned()), ..Config::default() };
+    assert!(matches!(run(config), Err(MuzzleError::INVALID(_))));
+}
@@ -0,0 +1,25 @@
+//! Warnings and hints turned into failures, `--strict`.
+
+mod common;
+
+use common::muzzle;
+
+#[test]
+fn clean_input_passes() {
+    let output = muzzle(&["--strict", "-m", "10", "-s", "800"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(output.stderr.is_empty(), "{}", String::from_utf8_lossy(&output.stderr));
+}
+
+#[test]
+fn warnings_and_hints_fail() {
+    for args in [&["-m", "10", "-s", "800", "-e", "3200"][..], &["-p", "-m", "10", "-s", "2834.7281937"]] {
+        let lenient = muzzle(args);
+        assert_eq!(lenient.status.code(), Some(0));
+
+        let strict = muzzle(&[&["--strict"], args].concat());
+        assert_eq!(strict.status.code(), Some(1), "{:?}", args);
+        assert!(strict.stdout.is_empty(), "{}", String::from_utf8_lossy(&strict.stdout));
+        assert!(String::from_utf8_lossy(&strict.stderr).contains("Failed because of --strict with:"));
+    }
+}
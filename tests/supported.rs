@@ -0,0 +1,24 @@
+//! Lists of what calculations can be made with, `supported_quantities` and `supported_units`.
+
+use muzzle::{run, supported_quantities, supported_units, Config, Quantity, Units};
+
+#[test]
+fn lists_in_stable_order() {
+    assert_eq!(supported_quantities(), [Quantity::MASS, Quantity::SPEED, Quantity::ENERGY]);
+    assert_eq!(supported_units(), [Units::METRIC, Units::IMPERIAL]);
+}
+
+#[test]
+fn every_listed_combination_runs() {
+    for &units in supported_units() {
+        for &derived in supported_quantities() {
+            let mut config = Config { units, mass: Some("10".to_owned()), speed: Some("800".to_owned()), energy: Some("3200".to_owned()), ..Config::default() };
+            match derived {
+                Quantity::MASS => config.mass = None,
+                Quantity::SPEED => config.speed = None,
+                Quantity::ENERGY => config.energy = None,
+            }
+            assert_eq!(run(config).unwrap().derived, Some(derived));
+        }
+    }
+}
@@ -0,0 +1,36 @@
+//! Runs of a config over a range of values of one quantity, `sweep`.
+
+use muzzle::{energy_sweep_iter, sweep, Config, MuzzleError, Quantity, Units, MAX_SWEEP_ROWS};
+
+fn base() -> Config {
+    Config { units: Units::METRIC, energy: Some("3200".to_owned()), ..Config::default() }
+}
+
+#[test]
+fn counts_rows_and_hits_both_ends() {
+    let rows: Vec<_> = sweep(base(), Quantity::MASS, 5.0, 10.0, 0.5).collect::<Result<_, _>>().unwrap();
+    assert_eq!(rows.len(), 11);
+    assert_eq!(rows[0].mass, 5.0);
+    assert_eq!(rows[10].mass, 10.0);
+
+    // A step not dividing the range stops short of the end, 0.1 steps land on it despite rounding
+    assert_eq!(sweep(base(), Quantity::MASS, 5.0, 10.0, 2.0).count(), 3);
+    let tenths: Vec<_> = sweep(base(), Quantity::MASS, 0.1, 1.0, 0.1).collect::<Result<_, _>>().unwrap();
+    assert_eq!(tenths.len(), 10);
+    assert_eq!(tenths[9].mass, 1.0);
+
+    assert_eq!(sweep(base(), Quantity::MASS, 7.0, 7.0, 1.0).count(), 1);
+}
+
+#[test]
+fn refuses_ranges_too_long_or_incorrect() {
+    for (from, to, step) in [(1.0, 2.0, 1e-300), (1.0, 2.0, 1.0 / MAX_SWEEP_ROWS as f64), (2.0, 1.0, 1.0), (1.0, 2.0, 0.0), (1.0, f64::INFINITY, 1.0)] {
+        let results: Vec<_> = sweep(base(), Quantity::MASS, from, to, step).collect();
+        assert_eq!(results.len(), 1, "{} {} {}", from, to, step);
+        assert!(matches!(results[0], Err(MuzzleError::INVALID(_))));
+        assert!(matches!(energy_sweep_iter(Units::METRIC, 3200.0, from, to, step), Err(MuzzleError::INVALID(_))));
+    }
+
+    // Right at the cap is still run, lazily
+    assert_eq!(sweep(base(), Quantity::MASS, 1.0, 1.0 + (MAX_SWEEP_ROWS - 1) as f64, 1.0).size_hint().0, MAX_SWEEP_ROWS);
+}
@@ -0,0 +1,33 @@
+//! The one mass and speed making both a given energy and power factor, `--target-pf`.
+
+mod common;
+
+use common::muzzle;
+use muzzle::{run, Config, MuzzleError, Quantity, Units};
+
+fn solve(energy: &str, power_factor: &str) -> Result<muzzle::Params, MuzzleError> {
+    run(Config { units: Units::IMPERIAL, energy: Some(energy.to_owned()), power_factor: Some(power_factor.to_owned()), ..Config::default() })
+}
+
+#[test]
+fn reproduces_both_targets() {
+    for (energy, power_factor) in [(350.0, 130.0), (500.0, 170.0), (2610.8, 420.0)] {
+        let load = solve(&energy.to_string(), &power_factor.to_string()).unwrap();
+        assert_eq!(load.derived, Some(Quantity::SPEED));
+        assert!((load.energy / energy - 1.0).abs() < 1e-12, "{} {}", load.energy, energy);
+        assert!((load.power_factor() / power_factor - 1.0).abs() < 1e-12, "{} {}", load.power_factor(), power_factor);
+    }
+    // 150 gr at 2800 FPS
+    let rifle = solve("2610.8037545844472", "420").unwrap();
+    assert!((rifle.mass - 150.0).abs() < 1e-9 && (rifle.speed - 2800.0).abs() < 1e-9);
+    assert!(matches!(solve("350", "0"), Err(MuzzleError::INVALID(_))));
+}
+
+#[test]
+fn cli_solves_from_energy_alone() {
+    let output = muzzle(&["-i", "-e", "2610.8037545844472", "--target-pf", "420"]);
+    assert!(output.status.success());
+    let report = String::from_utf8(output.stdout).unwrap();
+    assert!(report.contains("Projectile mass:\t150.000 grains"), "{}", report);
+    assert!(report.contains("Projectile speed:\t2800.000 FPS"), "{}", report);
+}
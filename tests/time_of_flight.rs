@@ -0,0 +1,31 @@
+//! Time of flight to the downrange distance, with `bc` and `distance`.
+
+use muzzle::{run, Config, Units};
+
+fn time(speed: &str, distance: &str) -> f64 {
+    let config = Config {
+        units: Units::IMPERIAL,
+        mass: Some("150".to_owned()),
+        speed: Some(speed.to_owned()),
+        bc: Some("0.4".to_owned()),
+        distance: Some(distance.to_owned()),
+        ..Config::default()
+    };
+    run(config).unwrap().time_of_flight.unwrap()
+}
+
+#[test]
+fn grows_with_distance() {
+    assert_eq!(time("2800", "0"), 0.0);
+    let times: Vec<f64> = [50, 100, 200, 300, 500, 800].iter().map(|yards| time("2800", &yards.to_string())).collect();
+    assert!(times.windows(2).all(|pair| pair[0] < pair[1]), "{:?}", times);
+
+    // Slower than in a vacuum, as the bullet slows down on the way
+    assert!(times[1] > 300.0 / 2800.0);
+}
+
+#[test]
+fn shrinks_with_speed() {
+    let times: Vec<f64> = [1800, 2200, 2600, 3000].iter().map(|fps| time(&fps.to_string(), "300")).collect();
+    assert!(times.windows(2).all(|pair| pair[0] > pair[1]), "{:?}", times);
+}
@@ -0,0 +1,66 @@
+//! Speed and energy sampled along the way, `--trajectory-samples`.
+
+mod common;
+
+use common::{muzzle, stdout};
+use muzzle::{run, velocity_decay, Config, DragModel, Units};
+use serde_json::Value;
+
+fn config(distance: Option<&str>) -> Config {
+    Config {
+        units: Units::IMPERIAL,
+        mass: Some("150".to_owned()),
+        speed: Some("2800".to_owned()),
+        bc: distance.map(|_| "0.4".to_owned()),
+        distance: distance.map(str::to_owned),
+        ..Config::default()
+    }
+}
+
+#[test]
+fn starts_at_muzzle_and_decays_monotonically() {
+    let params = run(config(None)).unwrap();
+    let samples = velocity_decay(&params, DragModel::G1, 0.4, 600.0, 13).unwrap();
+
+    assert_eq!(samples.len(), 13);
+    assert_eq!(samples[0], (0.0, params.speed, params.energy));
+    for pair in samples.windows(2) {
+        assert!((pair[1].0 - pair[0].0 - 50.0).abs() < 1e-9);
+        assert!(pair[1].1 < pair[0].1 && pair[1].2 < pair[0].2);
+    }
+
+    // The last one is the estimate `run` makes at the distance
+    let downrange = run(config(Some("600"))).unwrap();
+    let last = samples[12];
+    assert_eq!(last.0, 600.0);
+    assert!((last.1 / downrange.retained_speed.unwrap() - 1.0).abs() < 1e-3);
+}
+
+#[test]
+fn fewer_than_two_samples_are_rejected() {
+    let params = run(config(None)).unwrap();
+    assert!(velocity_decay(&params, DragModel::G1, 0.4, 300.0, 1).is_err());
+    assert!(velocity_decay(&params, DragModel::G1, 0.0, 300.0, 5).is_err());
+}
+
+#[test]
+fn cli_prints_json_samples() {
+    let output = muzzle(&["-i", "-m", "150", "-s", "2800", "--bc", "0.4", "--distance", "300", "--trajectory-samples", "4", "-f", "json"]);
+    assert!(output.status.success());
+
+    let document: Value = serde_json::from_slice(&output.stdout).unwrap();
+    let samples = document["samples"].as_array().unwrap();
+    assert_eq!(samples.len(), 4);
+    assert_eq!(samples[0]["speed"], 2800.0);
+    assert_eq!(samples[3]["distance"], 300.0);
+}
+
+#[test]
+fn cli_prints_csv_samples_when_asked_for() {
+    let args = ["-m", "10", "-s", "800", "--bc", "0.4", "--distance", "300", "--trajectory-samples", "4"];
+    let csv = stdout(&[&args[..], &["--format", "csv"]].concat());
+
+    assert_eq!(csv, stdout(&args));
+    assert_eq!(csv.lines().count(), 5);
+    assert!(csv.starts_with("distance,speed,energy\n0,800,3200\n"), "{}", csv);
+}
@@ -0,0 +1,33 @@
+//! Derivations going through the conversion factors of `UnitSystem`.
+
+use muzzle::{run, Config, Quantity, Units};
+
+#[test]
+fn systems_carry_their_factors() {
+    let metric = Units::METRIC.system();
+    assert_eq!((metric.mass_scale(), metric.force_scale(), metric.meters_in_length(), metric.meters_in_distance()), (1000.0, 1.0, 1.0, 1.0));
+
+    let imperial = Units::IMPERIAL.system();
+    assert_eq!((imperial.mass_scale(), imperial.force_scale(), imperial.meters_in_length(), imperial.meters_in_distance()), (7000.0, 32.174, 0.3048, 0.9144));
+}
+
+#[test]
+fn derivations_match_formulas_of_each_system() {
+    let derive = |units: Units, mass: &str, speed: &str| {
+        let config = Config {
+            units,
+            mass: Some(mass.to_owned()),
+            speed: Some(speed.to_owned()),
+            ..Config::default()
+        };
+        run(config).unwrap()
+    };
+
+    let metric = derive(Units::METRIC, "10", "800");
+    assert_eq!(metric.derived, Some(Quantity::ENERGY));
+    assert_eq!(metric.energy, 0.01 * 800.0_f64.powi(2) / 2.0);
+
+    let imperial = derive(Units::IMPERIAL, "150", "2800");
+    assert_eq!(imperial.energy, (150.0 / 7000.0) * 2800.0_f64.powi(2) / (2.0 * 32.174));
+    assert_eq!(imperial.with_energy(imperial.energy).unwrap().speed, 2800.0);
+}
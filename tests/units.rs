@@ -0,0 +1,90 @@
+//! Results converted to other units than the inputs were given in.
+
+mod common;
+
+use common::stdout;
+use muzzle::{run, Config, Units};
+
+#[test]
+fn imperial_input_metric_output() {
+    let config = Config {
+        units: Units::IMPERIAL,
+        mass: Some("150".to_owned()),
+        speed: Some("2800".to_owned()),
+        diameter: Some("0.308".to_owned()),
+        ..Config::default()
+    };
+    let imperial = run(config).unwrap();
+    let metric = imperial.in_units(Units::METRIC);
+
+    // By hand: 1 gr = 0.06479891 g, 1 ft = 0.3048 m, 1 ft·lbf = 1.3558179483 J, 1 in = 25.4 mm
+    let close = |actual: f64, expected: f64| (actual - expected).abs() <= 1e-9 * expected.abs();
+    assert_eq!(metric.units, Units::METRIC);
+    assert!(close(metric.mass, 150.0 * 0.06479891));
+    assert!(close(metric.speed, 2800.0 * 0.3048));
+    assert!(close(metric.energy, imperial.energy * 1.3558179483314004));
+    assert!(close(metric.sectional_density.unwrap(), 150.0 * 0.06479891 / (0.308f64 * 25.4).powi(2)));
+    assert_eq!(metric.penetration_index, imperial.penetration_index);
+
+    // And back again
+    let back = metric.in_units(Units::IMPERIAL);
+    assert!(close(back.mass, 150.0) && close(back.speed, 2800.0) && close(back.energy, imperial.energy));
+}
+
+#[test]
+fn road_speed_input() {
+    let speed = |units: Units, speed: &str| run(Config { units, mass: Some("10".to_owned()), speed: Some(speed.to_owned()), ..Config::default() }).unwrap();
+
+    // 1 km/h is 1/3.6 m/s and 1 mph is 0.44704 m/s, both exactly
+    assert!((speed(Units::METRIC, "100kmh").speed - 100.0 / 3.6).abs() < 1e-12);
+    assert!((speed(Units::METRIC, "60 mph").speed - 60.0 * 0.44704).abs() < 1e-12);
+    assert!((speed(Units::IMPERIAL, "60mph").speed - 88.0).abs() < 1e-12);
+    assert!((speed(Units::METRIC, "100kmh").speed_kmh() - 100.0).abs() < 1e-12);
+}
+
+#[test]
+fn wind_drift() {
+    let drift = |wind_speed: &str, wind_angle: Option<&str>| {
+        let config = Config {
+            units: Units::IMPERIAL,
+            mass: Some("150".to_owned()),
+            speed: Some("2800".to_owned()),
+            bc: Some("0.4".to_owned()),
+            distance: Some("300".to_owned()),
+            wind_speed: Some(wind_speed.to_owned()),
+            wind_angle: wind_angle.map(str::to_owned),
+            ..Config::default()
+        };
+        run(config).unwrap().wind_drift.unwrap()
+    };
+
+    assert_eq!(drift("0", None), 0.0);
+    assert!(drift("10", None) > 0.0);
+    assert!((drift("10", Some("90")) - drift("10", None)).abs() < 1e-12);
+    assert!((drift("10", Some("270")) + drift("10", None)).abs() < 1e-12);
+    assert!(drift("10", Some("0")).abs() < 1e-9);
+}
+
+#[test]
+fn other_flips_measurment_system() {
+    assert_eq!(Units::METRIC.other(), Units::IMPERIAL);
+    assert_eq!(Units::IMPERIAL.other(), Units::METRIC);
+    for units in Units::ALL {
+        assert_eq!(units.other().other(), units);
+    }
+}
+
+#[test]
+fn distances_and_temperatures_convert() {
+    assert!((Units::IMPERIAL.convert_distance(100.0, Units::METRIC) - 91.44).abs() < 1e-12);
+    assert!((Units::METRIC.convert_distance(91.44, Units::IMPERIAL) - 100.0).abs() < 1e-12);
+    assert_eq!(Units::METRIC.convert_distance(50.0, Units::METRIC), 50.0);
+
+    assert!((Units::IMPERIAL.convert_temperature(59.0, Units::METRIC) - 15.0).abs() < 1e-12);
+    assert!((Units::METRIC.convert_temperature(-40.0, Units::IMPERIAL) + 40.0).abs() < 1e-12);
+    assert_eq!(Units::IMPERIAL.convert_temperature(70.0, Units::IMPERIAL), 70.0);
+
+    let report = stdout(&["-i", "-m", "150", "-s", "2800", "--bc", "0.4", "-d", "100", "--temp", "59", "--output-units", "metric"]);
+    assert!(report.contains("Speed at 91.44 meters:"), "{}", report);
+    assert!(report.contains("at 15.0 °C)"), "{}", report);
+}
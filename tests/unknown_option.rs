@@ -0,0 +1,24 @@
+//! Unknown options answered with the closest known one and usage.
+
+mod common;
+
+use common::muzzle;
+
+#[test]
+fn suggests_closest_option() {
+    let output = muzzle(&["--masss", "10", "-s", "800"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.starts_with("Failed to parse parameters with: unknown option `masss`\nDid you mean `--mass`?"), "{}", stderr);
+    assert!(stderr.contains("USAGE: muzzle"));
+}
+
+#[test]
+fn suggests_nothing_far_off() {
+    let output = muzzle(&["--zzzzzz"]);
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("unknown option `zzzzzz`"));
+    assert!(!stderr.contains("Did you mean"));
+}
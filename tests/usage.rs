@@ -0,0 +1,25 @@
+//! Usage shown when no parameters are given at all.
+
+mod common;
+
+use common::muzzle;
+use muzzle::{run, Config, MuzzleError};
+
+#[test]
+fn empty_config_is_told_apart() {
+    assert!(matches!(run(Config::default()), Err(MuzzleError::EMPTY)));
+    assert!(matches!(run(Config { mass: Some("10".to_owned()), ..Config::default() }), Err(MuzzleError::INSUFFICIENT)));
+}
+
+#[test]
+fn shows_usage_only_without_parameters() {
+    let empty = muzzle(&["--metric"]);
+    assert_eq!(empty.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&empty.stderr);
+    assert!(stderr.contains("No parameters given."));
+    assert!(stderr.contains("USAGE: muzzle"));
+
+    let insufficient = muzzle(&["-m", "10"]);
+    assert_eq!(insufficient.status.code(), Some(1));
+    assert!(!String::from_utf8_lossy(&insufficient.stderr).contains("USAGE: muzzle"));
+}